@@ -118,6 +118,8 @@ EVERYTHINGUSERAPI void EVERYTHINGAPI Everything_SetReplyWindow(HWND hWnd);
 EVERYTHINGUSERAPI void EVERYTHINGAPI Everything_SetReplyID(DWORD dwId);
 EVERYTHINGUSERAPI void EVERYTHINGAPI Everything_SetSort(DWORD dwSort); // Everything 1.4.1
 EVERYTHINGUSERAPI void EVERYTHINGAPI Everything_SetRequestFlags(DWORD dwRequestFlags); // Everything 1.4.1
+EVERYTHINGUSERAPI BOOL EVERYTHINGAPI Everything_SetInstanceNameW(LPCWSTR lpInstanceName); // everything-sdk-rs
+EVERYTHINGUSERAPI DWORD EVERYTHINGAPI Everything_GetIpcWindowClassW(LPWSTR lpString,DWORD dwBufferSizeInWchars); // everything-sdk-rs
 
 // read search state
 EVERYTHINGUSERAPI BOOL EVERYTHINGAPI Everything_GetMatchPath(void);
@@ -41,6 +41,7 @@
 #define _EVERYTHING_COPYDATA_QUERYREPLY		0
 
 #define _EVERYTHING_MSGFLT_ALLOW		1
+#define _EVERYTHING_IPC_WNDCLASS_MAX	256
 
 typedef struct _EVERYTHING_tagCHANGEFILTERSTRUCT 
 {
@@ -94,6 +95,7 @@ static volatile LONG _Everything_InterlockedCount = 0;
 static CRITICAL_SECTION _Everything_cs;
 static HWND _Everything_ReplyWindow = 0;
 static DWORD _Everything_ReplyID = 0;
+static WCHAR _Everything_IpcWindowClass[_EVERYTHING_IPC_WNDCLASS_MAX] = L"EVERYTHING_TASKBAR_NOTIFICATION"; // everything-sdk-rs: the default (unnamed) instance, see Everything_SetInstanceNameW
 static BOOL (WINAPI *_Everything_pChangeWindowMessageFilterEx)(HWND hWnd,UINT message,DWORD action,_EVERYTHING_PCHANGEFILTERSTRUCT pChangeFilterStruct) = 0;
 static HANDLE _Everything_user32_hdll = NULL;
 static BOOL _Everything_GotChangeWindowMessageFilterEx = FALSE;
@@ -361,6 +363,69 @@ void EVERYTHINGAPI Everything_SetReplyID(DWORD dwId)
 
 	_Everything_Unlock();
 }
+
+// everything-sdk-rs: set the instance of Everything to talk to, NULL or empty for the default instance.
+// The IPC window class of the instance is "EVERYTHING_TASKBAR_NOTIFICATION_(<name>)", such as the "1.5a" of Everything 1.5 alpha.
+BOOL EVERYTHINGAPI Everything_SetInstanceNameW(LPCWSTR lpInstanceName)
+{
+	static const WCHAR prefix[] = L"EVERYTHING_TASKBAR_NOTIFICATION";
+	DWORD prefix_len;
+	DWORD name_len;
+	BOOL ret;
+	
+	prefix_len = (sizeof(prefix) / sizeof(WCHAR)) - 1;
+	name_len = lpInstanceName ? _Everything_StringLengthW(lpInstanceName) : 0;
+	
+	_Everything_Lock();
+	
+	// prefix + "_(" + name + ")" + null
+	if (prefix_len + name_len + 4 <= _EVERYTHING_IPC_WNDCLASS_MAX)
+	{
+		WCHAR *d;
+		
+		d = _Everything_IpcWindowClass;
+		CopyMemory(d,prefix,prefix_len * sizeof(WCHAR));
+		d += prefix_len;
+		
+		if (name_len)
+		{
+			*d++ = '_';
+			*d++ = '(';
+			CopyMemory(d,lpInstanceName,name_len * sizeof(WCHAR));
+			d += name_len;
+			*d++ = ')';
+		}
+		
+		*d = 0;
+		
+		ret = TRUE;
+	}
+	else
+	{
+		_Everything_LastError = EVERYTHING_ERROR_INVALIDPARAMETER;
+		
+		ret = FALSE;
+	}
+	
+	_Everything_Unlock();
+	
+	return ret;
+}
+
+// everything-sdk-rs: get the IPC window class of the instance set by Everything_SetInstanceNameW, which all the IPC calls look for.
+// Returns the number of wchars copied excluding the null terminator, or the length of the class if lpString is NULL.
+DWORD EVERYTHINGAPI Everything_GetIpcWindowClassW(LPWSTR lpString,DWORD dwBufferSizeInWchars)
+{
+	DWORD ret;
+	
+	_Everything_Lock();
+	
+	ret = _Everything_CopyW(lpString,dwBufferSizeInWchars,0,_Everything_IpcWindowClass);
+	
+	_Everything_Unlock();
+	
+	return ret;
+}
 	
 BOOL EVERYTHINGAPI Everything_GetMatchPath(void)
 {
@@ -646,7 +711,7 @@ static DWORD EVERYTHINGAPI _Everything_query_thread_proc(void *param)
 	HWND everything_hwnd;
 
 	// everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
-	everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+	everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 	if (everything_hwnd)
 	{
 		WNDCLASSEX wcex;
@@ -851,7 +916,7 @@ static BOOL _Everything_SendIPCQuery(void)
 	BOOL ret;
 	
 		// find the everything ipc window.
-	everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+	everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 	if (everything_hwnd)
 	{
 		_Everything_QueryVersion = 2;
@@ -2844,7 +2909,7 @@ static BOOL _Everything_SendAPIBoolCommand(int command,LPARAM lParam)
 {
 	HWND everything_hwnd;
 
-	everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+	everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 	if (everything_hwnd)
 	{
 		_Everything_LastError = 0;
@@ -2873,7 +2938,7 @@ static DWORD _Everything_SendAPIDwordCommand(int command,LPARAM lParam)
 {
 	HWND everything_hwnd;
 
-	everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+	everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 	if (everything_hwnd)
 	{
 		_Everything_LastError = 0;
@@ -2969,7 +3034,7 @@ UINT EVERYTHINGAPI Everything_MSIExitAndStopService(void *msihandle)
 	{
 		HWND everything_hwnd;
 
-		everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+		everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 		if (everything_hwnd)
 		{
 			DWORD dwProcessId;
@@ -3160,7 +3225,7 @@ static LRESULT _Everything_SendCopyData(int command,const void *data,int size)
 {
 	HWND everything_hwnd;
 
-	everything_hwnd = FindWindow("EVERYTHING_TASKBAR_NOTIFICATION_(minum)", 0);
+	everything_hwnd = FindWindowW(_Everything_IpcWindowClass, 0);
 	if (everything_hwnd)
 	{
 		COPYDATASTRUCT cds;
@@ -1,35 +1,65 @@
-fn main() {
-    println!("cargo:warning=Hello everything-sdk-sys!");
-    #[cfg(windows)]
-    {
-        let vendored = std::env::var("CARGO_FEATURE_VENDORED").is_ok();
-        let link_dll = std::env::var("CARGO_FEATURE_DLL").is_ok();
-
-        assert!(
-            vendored,
-            "now only support build everything-sdk from source code"
-        );
-        assert!(!link_dll, "now only support link everything-sdk in static");
-
-        // now the rerun settings are by default
-        // Ref: https://doc.rust-lang.org/cargo/reference/build-scripts.html#rerun-if-changed
-        // println!("cargo:rerun-if-changed=Everything-SDK");
-
-        // Build everything from source code
-        cc::Build::new()
-            .file("Everything-SDK/src/Everything.c")
-            .compile("everything-sdk");
-
-        // !Depr: build from source code
-        // Tell cargo to look for shared libraries in the specified directory
-        // println!("cargo:rustc-link-search=native=Everything-SDK");
-        // println!("cargo:rustc-link-lib=Everything64"); // for Everything64.lib
-
-        // !Depr: dynamic link by windows-rs
-        // Tell cargo to tell rustc to link the system user32 and shell32 shared library.
-        // println!("cargo:rustc-link-lib=user32"); // for User32.lib
-        // println!("cargo:rustc-link-lib=shell32"); // for shell32.lib
-    }
-
-    println!("cargo:warning=Goodbye everything-sdk-sys!");
-}
+fn main() {
+    println!("cargo:warning=Hello everything-sdk-sys!");
+    #[cfg(windows)]
+    {
+        let vendored = std::env::var("CARGO_FEATURE_VENDORED").is_ok();
+        let link_dll = std::env::var("CARGO_FEATURE_DLL").is_ok();
+
+        assert!(
+            vendored,
+            "now only support build everything-sdk from source code"
+        );
+        assert!(!link_dll, "now only support link everything-sdk in static");
+
+        // now the rerun settings are by default
+        // Ref: https://doc.rust-lang.org/cargo/reference/build-scripts.html#rerun-if-changed
+        // println!("cargo:rerun-if-changed=Everything-SDK");
+
+        // Build everything from source code
+        cc::Build::new()
+            .file("Everything-SDK/src/Everything.c")
+            .compile("everything-sdk");
+
+        // The SDK 3 is not vendored, link the library from the SDK 3 of Everything 1.5, which is
+        // `Everything3.lib` in `EVERYTHING3_LIB_DIR` (or the lib search path) by default.
+        if std::env::var("CARGO_FEATURE_SDK3").is_ok() {
+            if let Ok(dir) = std::env::var("EVERYTHING3_LIB_DIR") {
+                println!("cargo:rustc-link-search=native={dir}");
+            }
+            let name = std::env::var("EVERYTHING3_LIB_NAME");
+            let name = name.as_deref().unwrap_or("Everything3");
+            println!("cargo:rustc-link-lib={name}");
+        }
+
+        // The handwritten bindings are still used, these are checked against them in tests.
+        #[cfg(feature = "bindgen")]
+        generate_bindings();
+
+        // !Depr: build from source code
+        // Tell cargo to look for shared libraries in the specified directory
+        // println!("cargo:rustc-link-search=native=Everything-SDK");
+        // println!("cargo:rustc-link-lib=Everything64"); // for Everything64.lib
+
+        // !Depr: dynamic link by windows-rs
+        // Tell cargo to tell rustc to link the system user32 and shell32 shared library.
+        // println!("cargo:rustc-link-lib=user32"); // for User32.lib
+        // println!("cargo:rustc-link-lib=shell32"); // for shell32.lib
+    }
+
+    println!("cargo:warning=Goodbye everything-sdk-sys!");
+}
+
+/// Generate the bindings of `Everything.h` into `$OUT_DIR/bindgen.rs` for `tests/bindgen.rs`.
+#[cfg(all(windows, feature = "bindgen"))]
+fn generate_bindings() {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    bindgen::Builder::default()
+        .header("Everything-SDK/include/Everything.h")
+        .allowlist_function("Everything_.*")
+        .allowlist_var("EVERYTHING_.*")
+        .layout_tests(false)
+        .generate()
+        .expect("failed to generate the bindings of Everything.h")
+        .write_to_file(out_dir.join("bindgen.rs"))
+        .expect("failed to write the generated bindings");
+}
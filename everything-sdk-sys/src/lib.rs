@@ -5,6 +5,10 @@
 mod bindings;
 pub use bindings::*;
 
+// the SDK 3 of Everything 1.5, linked from the library of it
+#[cfg(feature = "sdk3")]
+pub mod sdk3;
+
 #[cfg(not(feature = "vendored"))]
 #[allow(non_snake_case)]
 mod dummy_msi {
@@ -0,0 +1,199 @@
+//! Rust C-bindings for `Everything3.h` in the SDK 3 of Everything 1.5, handwritten too.
+//!
+//! Unlike the SDK 2, the states live in the objects created by the SDK (the client, the
+//! search state and the result list), so no global state is shared. The SDK 3 is not
+//! vendored, it is linked from the library `Everything3` (see the `sdk3` feature in
+//! `build.rs`).
+
+use windows::Win32::Foundation::BOOL;
+
+use super::DWORD;
+
+type LPCWSTR = windows::core::PCWSTR;
+type LPWSTR = windows::core::PWSTR;
+#[allow(non_camel_case_types)]
+pub type SIZE_T = usize;
+#[allow(non_camel_case_types)]
+pub type UINT64 = u64;
+
+/// The opaque client connected to an Everything 1.5 instance.
+#[repr(C)]
+pub struct EVERYTHING3_CLIENT {
+    _private: [u8; 0],
+}
+
+/// The opaque search state, that is the search text, the options, the sorts and the
+/// property requests.
+#[repr(C)]
+pub struct EVERYTHING3_SEARCH_STATE {
+    _private: [u8; 0],
+}
+
+/// The opaque result list of a search.
+#[repr(C)]
+pub struct EVERYTHING3_RESULT_LIST {
+    _private: [u8; 0],
+}
+
+pub const EVERYTHING3_OK: u32 = 0;
+pub const EVERYTHING3_ERROR_OUT_OF_MEMORY: u32 = 0xE0000001;
+pub const EVERYTHING3_ERROR_IPC_PIPE_NOT_FOUND: u32 = 0xE0000002; // Everything 1.5 is not running
+pub const EVERYTHING3_ERROR_DISCONNECTED: u32 = 0xE0000003;
+pub const EVERYTHING3_ERROR_INVALID_PARAMETER: u32 = 0xE0000004;
+pub const EVERYTHING3_ERROR_BAD_REQUEST: u32 = 0xE0000005;
+pub const EVERYTHING3_ERROR_CANCELLED: u32 = 0xE0000006;
+pub const EVERYTHING3_ERROR_PROPERTY_NOT_FOUND: u32 = 0xE0000007;
+pub const EVERYTHING3_ERROR_SERVER: u32 = 0xE0000008;
+pub const EVERYTHING3_ERROR_INVALID_COMMAND: u32 = 0xE0000009;
+pub const EVERYTHING3_ERROR_BAD_RESPONSE: u32 = 0xE000000A;
+pub const EVERYTHING3_ERROR_INSUFFICIENT_BUFFER: u32 = 0xE000000B;
+pub const EVERYTHING3_ERROR_SHUTDOWN: u32 = 0xE000000C;
+pub const EVERYTHING3_ERROR_INVALID_PROPERTY_VALUE_TYPE: u32 = 0xE000000D;
+
+pub const EVERYTHING3_INVALID_PROPERTY_ID: u32 = 0xFFFFFFFF;
+pub const EVERYTHING3_UINT64_UNKNOWN: u64 = 0xFFFFFFFFFFFFFFFF;
+pub const EVERYTHING3_DWORD_UNKNOWN: u32 = 0xFFFFFFFF;
+
+pub const EVERYTHING3_PROPERTY_ID_NAME: u32 = 0;
+pub const EVERYTHING3_PROPERTY_ID_PATH: u32 = 1;
+pub const EVERYTHING3_PROPERTY_ID_SIZE: u32 = 2;
+pub const EVERYTHING3_PROPERTY_ID_EXTENSION: u32 = 3;
+pub const EVERYTHING3_PROPERTY_ID_TYPE: u32 = 4;
+pub const EVERYTHING3_PROPERTY_ID_DATE_MODIFIED: u32 = 5;
+pub const EVERYTHING3_PROPERTY_ID_DATE_CREATED: u32 = 6;
+pub const EVERYTHING3_PROPERTY_ID_DATE_ACCESSED: u32 = 7;
+pub const EVERYTHING3_PROPERTY_ID_ATTRIBUTES: u32 = 8;
+
+// `__stdcall` in the header, which is "system" (the same as "C" except on x86).
+extern "system" {
+    // client
+    pub fn Everything3_ConnectW(instance_name: LPCWSTR) -> *mut EVERYTHING3_CLIENT;
+    pub fn Everything3_DestroyClient(client: *mut EVERYTHING3_CLIENT) -> BOOL;
+    pub fn Everything3_GetLastError() -> DWORD;
+    pub fn Everything3_GetMajorVersion(client: *mut EVERYTHING3_CLIENT) -> DWORD;
+    pub fn Everything3_GetMinorVersion(client: *mut EVERYTHING3_CLIENT) -> DWORD;
+    pub fn Everything3_GetRevision(client: *mut EVERYTHING3_CLIENT) -> DWORD;
+    pub fn Everything3_GetBuildNumber(client: *mut EVERYTHING3_CLIENT) -> DWORD;
+    pub fn Everything3_IsDBLoaded(client: *mut EVERYTHING3_CLIENT) -> BOOL;
+    pub fn Everything3_FindPropertyW(
+        client: *mut EVERYTHING3_CLIENT,
+        canonical_name: LPCWSTR,
+    ) -> DWORD;
+
+    // search state
+    pub fn Everything3_CreateSearchState() -> *mut EVERYTHING3_SEARCH_STATE;
+    pub fn Everything3_DestroySearchState(search_state: *mut EVERYTHING3_SEARCH_STATE) -> BOOL;
+    pub fn Everything3_SetSearchTextW(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        search: LPCWSTR,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchMatchCase(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        match_case: BOOL,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchMatchPath(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        match_path: BOOL,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchMatchWholeWords(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        match_whole_words: BOOL,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchRegex(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        regex: BOOL,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchViewportOffset(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        offset: SIZE_T,
+    ) -> BOOL;
+    pub fn Everything3_SetSearchViewportCount(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        count: SIZE_T,
+    ) -> BOOL;
+    pub fn Everything3_AddSearchSort(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        property_id: DWORD,
+        ascending: BOOL,
+    ) -> BOOL;
+    pub fn Everything3_AddSearchPropertyRequest(
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+        property_id: DWORD,
+    ) -> BOOL;
+
+    // search
+    pub fn Everything3_Search(
+        client: *mut EVERYTHING3_CLIENT,
+        search_state: *mut EVERYTHING3_SEARCH_STATE,
+    ) -> *mut EVERYTHING3_RESULT_LIST;
+
+    // result list
+    pub fn Everything3_DestroyResultList(result_list: *mut EVERYTHING3_RESULT_LIST) -> BOOL;
+    pub fn Everything3_GetResultListViewportCount(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultListFolderCount(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultListFileCount(result_list: *mut EVERYTHING3_RESULT_LIST) -> SIZE_T;
+    pub fn Everything3_GetResultListTotalSize(result_list: *mut EVERYTHING3_RESULT_LIST) -> UINT64;
+    pub fn Everything3_IsFolderResult(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> BOOL;
+    pub fn Everything3_GetResultNameW(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        wbuf: LPWSTR,
+        wbuf_size_in_wchars: SIZE_T,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultPathW(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        wbuf: LPWSTR,
+        wbuf_size_in_wchars: SIZE_T,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultFullPathNameW(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        wbuf: LPWSTR,
+        wbuf_size_in_wchars: SIZE_T,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultSize(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> UINT64;
+    pub fn Everything3_GetResultDateModified(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> UINT64;
+    pub fn Everything3_GetResultDateCreated(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> UINT64;
+    pub fn Everything3_GetResultDateAccessed(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> UINT64;
+    pub fn Everything3_GetResultAttributes(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+    ) -> DWORD;
+    pub fn Everything3_GetResultPropertyTextW(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        property_id: DWORD,
+        wbuf: LPWSTR,
+        wbuf_size_in_wchars: SIZE_T,
+    ) -> SIZE_T;
+    pub fn Everything3_GetResultPropertyDWORD(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        property_id: DWORD,
+    ) -> DWORD;
+    pub fn Everything3_GetResultPropertyUINT64(
+        result_list: *mut EVERYTHING3_RESULT_LIST,
+        result_index: SIZE_T,
+        property_id: DWORD,
+    ) -> UINT64;
+}
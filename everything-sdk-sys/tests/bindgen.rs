@@ -0,0 +1,113 @@
+//! Signature check between the handwritten bindings and the ones generated by bindgen from the
+//! vendored `Everything.h` (by the `bindgen` feature), to catch the drift after updating it.
+//!
+//! Only the types of the parameters and the return are compared, the parameter names may
+//! differ. Both are read as source text.
+
+use std::collections::BTreeMap;
+
+const BINDINGS: &str = include_str!("../src/bindings.rs");
+
+/// `void *msihandle` in C, and `*const c_void` in Rust on purpose.
+#[cfg(feature = "bindgen")]
+const KNOWN_DIFFERENCES: [&str; 2] = [
+    "Everything_MSIExitAndStopService",
+    "Everything_MSIStartService",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+struct Signature {
+    params: Vec<String>,
+    ret: Option<String>,
+}
+
+/// Drop the whitespace and the paths of the C types, so `::std::os::raw::c_void` is `c_void`.
+fn normalize(ty: &str) -> String {
+    let ty: String = ty.split_whitespace().collect();
+    [
+        "::std::os::raw::",
+        "::core::ffi::",
+        "std::ffi::",
+        "std::os::raw::",
+    ]
+    .iter()
+    .fold(ty, |ty, path| ty.replace(path, ""))
+}
+
+/// The `pub fn Everything_*` declarations in the source, by name.
+fn signatures(source: &str) -> BTreeMap<String, Signature> {
+    // Join the lines without the comments and the attributes, as a declaration may span lines.
+    let joined: String = source
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default().trim())
+        .filter(|line| !line.starts_with("#["))
+        .collect::<Vec<_>>()
+        .join(" ");
+    joined
+        .split("pub fn ")
+        .skip(1)
+        .filter_map(|decl| {
+            let decl = &decl[..decl.find(';')?];
+            let open = decl.find('(')?;
+            let close = decl.rfind(')')?;
+            let name = decl[..open].trim();
+            if !name.starts_with("Everything_") {
+                return None;
+            }
+            let params = decl[open + 1..close]
+                .split(',')
+                .filter(|param| !param.trim().is_empty())
+                .map(|param| normalize(param.split_once(':').map_or(param, |(_, ty)| ty)))
+                .collect();
+            let ret = decl[close + 1..].trim().strip_prefix("->").map(normalize);
+            Some((name.to_string(), Signature { params, ret }))
+        })
+        .collect()
+}
+
+#[test]
+fn handwritten_signatures_are_parsed() {
+    let handwritten = signatures(BINDINGS);
+    assert_eq!(handwritten.len(), 91);
+    assert_eq!(
+        handwritten["Everything_GetResultFullPathNameW"],
+        Signature {
+            params: vec!["DWORD".into(), "LPWSTR".into(), "DWORD".into()],
+            ret: Some("DWORD".into()),
+        }
+    );
+    assert_eq!(
+        handwritten["Everything_Reset"],
+        Signature {
+            params: vec![],
+            ret: None,
+        }
+    );
+}
+
+#[cfg(feature = "bindgen")]
+#[test]
+fn generated_signatures_match_handwritten() {
+    const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/bindgen.rs"));
+    let generated = signatures(GENERATED);
+    let handwritten = signatures(BINDINGS);
+
+    let missing: Vec<_> = generated
+        .keys()
+        .filter(|name| !handwritten.contains_key(*name))
+        .collect();
+    assert!(missing.is_empty(), "no Rust binding for: {missing:?}");
+
+    let drifted: Vec<_> = handwritten
+        .iter()
+        .filter(|(name, _)| !KNOWN_DIFFERENCES.contains(&name.as_str()))
+        .filter_map(|(name, signature)| match generated.get(name) {
+            Some(expected) if expected == signature => None,
+            expected => Some((name, signature, expected)),
+        })
+        .collect();
+    assert!(
+        drifted.is_empty(),
+        "the signatures differ from Everything.h (name, handwritten, generated): {drifted:#?}"
+    );
+}
@@ -0,0 +1,104 @@
+//! Export coverage check between the vendored Everything-SDK and the handwritten bindings.
+//!
+//! The `.def` file of Everything-SDK is not vendored in this crate, but it lists exactly the
+//! functions declared with `EVERYTHINGUSERAPI` in `Everything.h` (88 functions for now). The two
+//! `Everything_MSI*` functions are NOT in `.def`, they are checked separately. The functions
+//! added to the vendored source by this crate are not in `.def` either.
+//!
+//! These tests only read source text, so they run on any platform.
+
+use std::collections::BTreeSet;
+
+const HEADER: &str = include_str!("../Everything-SDK/include/Everything.h");
+const BINDINGS: &str = include_str!("../src/bindings.rs");
+
+const NOT_IN_DEF: [&str; 2] = [
+    "Everything_MSIExitAndStopService",
+    "Everything_MSIStartService",
+];
+
+/// Declared with `EVERYTHINGUSERAPI` in the vendored `Everything.h`, but only by this crate.
+const CRATE_ADDITIONS: [&str; 2] = [
+    "Everything_SetInstanceNameW",
+    "Everything_GetIpcWindowClassW",
+];
+
+/// Take the `Everything_*` identifier right after `prefix` in the line, if any.
+fn function_name<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = &line[line.find(prefix)? + prefix.len()..];
+    let rest = rest.trim_start();
+    let end = rest.find('(')?;
+    let name = rest[..end].trim();
+    name.starts_with("Everything_").then_some(name)
+}
+
+/// The functions exported in `.def`, that is the `EVERYTHINGUSERAPI` declarations.
+fn def_exports() -> BTreeSet<&'static str> {
+    HEADER
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("EVERYTHINGUSERAPI"))
+        .filter_map(|line| function_name(line, "EVERYTHINGAPI"))
+        .filter(|name| !CRATE_ADDITIONS.contains(name))
+        .collect()
+}
+
+/// The functions declared in the `extern "C"` block of `bindings.rs`.
+fn rust_bindings() -> BTreeSet<&'static str> {
+    BINDINGS
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//"))
+        .filter_map(|line| function_name(line, "pub fn"))
+        .collect()
+}
+
+#[test]
+fn def_exports_are_parsed() {
+    assert_eq!(def_exports().len(), 88);
+}
+
+#[test]
+fn every_def_export_has_binding() {
+    let bindings = rust_bindings();
+    let missing: Vec<_> = def_exports()
+        .into_iter()
+        .filter(|name| !bindings.contains(name))
+        .collect();
+    assert!(missing.is_empty(), "no Rust binding for: {missing:?}");
+}
+
+#[test]
+fn every_binding_is_exported() {
+    let exports = def_exports();
+    let unknown: Vec<_> = rust_bindings()
+        .into_iter()
+        .filter(|name| {
+            !exports.contains(name) && !NOT_IN_DEF.contains(name) && !CRATE_ADDITIONS.contains(name)
+        })
+        .collect();
+    assert!(
+        unknown.is_empty(),
+        "not exported by Everything-SDK: {unknown:?}"
+    );
+}
+
+#[test]
+fn msi_functions_are_declared_but_not_in_def() {
+    let exports = def_exports();
+    let bindings = rust_bindings();
+    for name in NOT_IN_DEF {
+        assert!(!exports.contains(name), "{name} should not be in .def");
+        assert!(bindings.contains(name), "no Rust binding for: {name}");
+        assert!(HEADER.contains(&format!("EVERYTHINGAPI {name}(")));
+    }
+}
+
+#[test]
+fn crate_additions_are_declared() {
+    let bindings = rust_bindings();
+    for name in CRATE_ADDITIONS {
+        assert!(bindings.contains(name), "no Rust binding for: {name}");
+        assert!(HEADER.contains(&format!("EVERYTHINGAPI {name}(")));
+    }
+}
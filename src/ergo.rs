@@ -1,912 +1,1935 @@
-use std::ffi::OsStr;
-use std::ffi::OsString;
-use std::marker::PhantomData;
-use std::path::Path;
-use std::path::PathBuf;
-use std::sync::OnceLock;
-
-use crate::raw;
-
-pub use raw::FileInfoType;
-pub use raw::RequestFlags;
-pub use raw::SortType;
-pub use raw::TargetMachine;
-
-pub mod error {
-    use super::RequestFlags;
-    use thiserror::Error as ThisError;
-
-    pub type Result<T> = std::result::Result<T, EverythingError>;
-
-    #[non_exhaustive]
-    #[derive(ThisError, Debug)]
-    pub enum EverythingError {
-        #[error("Failed to allocate memory for the search query.")]
-        Memory,
-        #[error("IPC is not available.")]
-        Ipc,
-        #[error("Failed to register the search query window class.")]
-        RegisterClassEx,
-        #[error("Failed to create the search query window.")]
-        CreateWindow,
-        #[error("Failed to create the search query thread.")]
-        CreateThread,
-        #[error("Invalid index. The index must be greater or equal to 0 and less than the number of visible results.")]
-        InvalidIndex,
-        #[error("Invalid call.")]
-        InvalidCall,
-        #[error("invalid request data, request data first.")]
-        InvalidRequest(#[from] InvalidRequestError),
-        #[error("bad parameter.")]
-        InvalidParameter,
-        #[error("not supported when using set_request_flags or set_sort to non-default value. (that is in query verison 2)")]
-        UnsupportedInQueryVersion2,
-    }
-
-    #[non_exhaustive]
-    #[derive(ThisError, Debug)]
-    pub enum InvalidRequestError {
-        #[error("should set the request flag {0:?}")]
-        RequestFlagsNotSet(RequestFlags),
-    }
-}
-
-pub use error::{EverythingError, InvalidRequestError, Result};
-
-use tracing::debug;
-use widestring::U16CStr;
-
-pub  mod helper {
-    use windows::Win32::Foundation::FILETIME;
-
-    use super::*;
-
-    pub fn is_default_request_flags(request_flags: RequestFlags) -> bool {
-        request_flags == RequestFlags::default()
-    }
-
-    pub fn is_default_sort_type(sort_type: SortType) -> bool {
-        sort_type == SortType::default()
-    }
-
-    // when send IPC query, try version 2 first (if we specified some non-version 1 request flags or sort)
-    pub fn should_use_query_version_2(request_flags: RequestFlags, sort_type: SortType) -> bool {
-        !is_default_request_flags(request_flags) || !is_default_sort_type(sort_type)
-    }
-
-}
-
-#[cfg(not(feature = "async"))]
-pub fn global() -> &'static std::sync::Mutex<EverythingGlobal> {
-    static EVERYTHING_CELL: OnceLock<std::sync::Mutex<EverythingGlobal>> = OnceLock::new();
-    EVERYTHING_CELL.get_or_init(|| std::sync::Mutex::new(EverythingGlobal {}))
-}
-
-#[cfg(feature = "async")]
-pub fn global() -> &'static futures::lock::Mutex<EverythingGlobal> {
-    static EVERYTHING_CELL: OnceLock<futures::lock::Mutex<EverythingGlobal>> = OnceLock::new();
-    EVERYTHING_CELL.get_or_init(|| futures::lock::Mutex::new(EverythingGlobal {}))
-}
-
-#[non_exhaustive]
-#[derive(Debug)]
-pub struct EverythingGlobal {}
-
-impl Drop for EverythingGlobal {
-    /// NEVER call this, as the static variable would not be dropped.
-    fn drop(&mut self) {
-        // So this will not be called too.
-        // We don't need this, `raw::Everything_Reset` in `EverythingSearcher` will
-        // free the allocated memory.
-        raw::Everything_CleanUp();
-        unreachable!()
-    }
-}
-
-impl EverythingGlobal {
-    /// New the only one searcher.
-    ///
-    /// There is **at most one** searcher can exist globally at the same time.
-    pub fn searcher<'a>(&'a mut self) -> EverythingSearcher<'a> {
-        EverythingSearcher {
-            _phantom: PhantomData::<&'a ()>,
-        }
-    }
-
-    // --- General ---
-
-    /// Everything uses the version format: `<major>.<minor>.<revision>.<build>`.
-    /// The build part is incremental and unique for all Everything versions.
-    pub fn version(&self) -> Result<(u32, u32, u32, u32, TargetMachine)> {
-        Ok((
-            self.get_major_version()?,
-            self.get_minor_version()?,
-            self.get_revision()?,
-            self.get_build_number()?,
-            self.get_target_machine()?,
-        ))
-    }
-
-    pub fn get_major_version(&self) -> Result<u32> {
-        raw::Everything_GetMajorVersion().ok_or(EverythingError::Ipc)
-    }
-
-    pub fn get_minor_version(&self) -> Result<u32> {
-        raw::Everything_GetMinorVersion().ok_or(EverythingError::Ipc)
-    }
-
-    pub fn get_revision(&self) -> Result<u32> {
-        raw::Everything_GetRevision().ok_or(EverythingError::Ipc)
-    }
-
-    pub fn get_build_number(&self) -> Result<u32> {
-        raw::Everything_GetBuildNumber().ok_or(EverythingError::Ipc)
-    }
-
-    pub fn get_target_machine(&self) -> Result<TargetMachine> {
-        raw::Everything_GetTargetMachine().ok_or(EverythingError::Ipc)
-    }
-
-    /// Request Everything to save settings and data to disk and exit.
-    pub fn save_and_exit(&mut self) -> Result<bool> {
-        raw::Everything_Exit().ok_or(EverythingError::Ipc)
-    }
-
-    /// Check if Everything's database is loaded.
-    ///
-    /// When Everything is loading, any queries will appear to return no results.
-    /// Use this to determine if the database has been loaded before performing a query.
-    pub fn is_db_loaded(&self) -> Result<bool> {
-        raw::Everything_IsDBLoaded().ok_or(EverythingError::Ipc)
-    }
-
-    /// Check if Everything is running as administrator or as a standard user.
-    pub fn is_admin(&self) -> Result<bool> {
-        raw::Everything_IsAdmin().ok_or(EverythingError::Ipc)
-    }
-
-    /// Check if Everything is saving settings and data to `%APPDATA%\Everything` or to the same location
-    /// as the `Everything.exe`.
-    pub fn is_appdata(&self) -> Result<bool> {
-        raw::Everything_IsAppData().ok_or(EverythingError::Ipc)
-    }
-
-    /// Request Everything to forcefully rebuild the Everything index.
-    ///
-    /// Requesting a rebuild will mark all indexes as dirty and start the rebuild process.
-    /// Use `self.is_db_loaded()` to determine if the database has been rebuilt before
-    /// performing a query.
-    pub fn rebuild_db(&mut self) -> Result<bool> {
-        // rebuild the database.
-        raw::Everything_RebuildDB().ok_or(EverythingError::Ipc)
-    }
-
-    /// Request Everything to rescan all folder indexes.
-    ///
-    /// Everything will begin updating all folder indexes in the background.
-    pub fn update_all_folder_indexes(&mut self) -> Result<bool> {
-        // Request all folder indexes be rescanned.
-        raw::Everything_UpdateAllFolderIndexes().ok_or(EverythingError::Ipc)
-    }
-
-    /// Request Everything to save the index to disk.
-    ///
-    /// The index is only saved to disk when you exit Everything.
-    /// Call this to write the index to the file: `Everything.db`.
-    pub fn save_db(&mut self) -> Result<bool> {
-        // flush index to disk
-        raw::Everything_SaveDB().ok_or(EverythingError::Ipc)
-    }
-
-    // --- Run History ---
-
-    /// Request Everything to save the run history to disk.
-    ///
-    /// The run history is only saved to disk when you close an Everything search window or
-    /// exit Everything.
-    /// Call this to write the run history to the file: `Run History.csv`.
-    pub fn save_run_history(&mut self) -> Result<bool> {
-        // flush run history to disk
-        raw::Everything_SaveRunHistory().ok_or(EverythingError::Ipc)
-    }
-
-    /// Delete all run history.
-    ///
-    /// Calling this function will clear all run history from memory and disk.
-    pub fn delete_run_history(&mut self) -> Result<bool> {
-        // clear run history
-        raw::Everything_DeleteRunHistory().ok_or(EverythingError::Ipc)
-    }
-
-    /// Gets the run count from a specified file in the Everything index by file name.
-    pub fn get_run_count(&self, filename: impl AsRef<Path>) -> Result<u32> {
-        raw::Everything_GetRunCountFromFileName(filename.as_ref()).ok_or(EverythingError::Ipc)
-    }
-
-    /// Sets the run count for a specified file in the Everything index by file name.
-    pub fn set_run_count(&mut self, filename: impl AsRef<Path>, run_count: u32) -> Result<()> {
-        if raw::Everything_SetRunCountFromFileName(filename.as_ref(), run_count) {
-            Ok(())
-        } else {
-            Err(EverythingError::Ipc)
-        }
-    }
-
-    /// Increments the run count by one for a specified file in the Everything by file name.
-    pub fn inc_run_count(&mut self, filename: impl AsRef<Path>) -> Result<u32> {
-        raw::Everything_IncRunCountFromFileName(filename.as_ref())
-            .map(|n| n.get())
-            .ok_or(EverythingError::Ipc)
-    }
-
-    // --- Others ---
-
-    /// Check if the specified file information is indexed and has fast sort enabled.
-    pub fn is_fast_sort(&self, sort_type: SortType) -> Result<bool> {
-        raw::Everything_IsFastSort(sort_type).ok_or(EverythingError::Ipc)
-    }
-
-    /// Check if the specified file information is indexed.
-    pub fn is_file_info_indexed(&self, file_info_type: FileInfoType) -> Result<bool> {
-        raw::Everything_IsFileInfoIndexed(file_info_type).ok_or(EverythingError::Ipc)
-    }
-}
-
-#[non_exhaustive]
-pub struct EverythingSearcher<'a> {
-    _phantom: PhantomData<&'a ()>,
-}
-
-impl Drop for EverythingSearcher<'_> {
-    fn drop(&mut self) {
-        raw::Everything_Reset(); // CAUTION!
-        debug!("[Drop] EverythingSearcher is dropped! (did Reset)");
-    }
-}
-
-impl<'a> EverythingSearcher<'a> {
-    // --- Manipulating the search state ---
-    /// empty string "" by default.
-    pub fn set_search(&mut self, text: impl AsRef<OsStr>) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetSearch(text);
-        self
-    }
-
-    /// disable (false) by default.
-    pub fn set_match_path(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetMatchPath(enable);
-        self
-    }
-
-    /// disable (false) by default.
-    pub fn set_match_case(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetMatchCase(enable);
-        self
-    }
-
-    /// disable (false) by default.
-    pub fn set_match_whole_word(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetMatchWholeWord(enable);
-        self
-    }
-
-    /// disable (false) by default.
-    pub fn set_regex(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetRegex(enable);
-        self
-    }
-
-    /// `u32::MAX` (0xffffffff) by default, which means all results.
-    pub fn set_max(&mut self, max_results: u32) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetMax(max_results);
-        self
-    }
-
-    /// zero (0) by default.
-    pub fn set_offset(&mut self, offset: u32) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetOffset(offset);
-        self
-    }
-
-    /// The default sort is EVERYTHING_SORT_NAME_ASCENDING (1). This sort is free.
-    pub fn set_sort(&mut self, sort_type: SortType) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetSort(sort_type);
-        self
-    }
-
-    /// The default request flags are EVERYTHING_REQUEST_FILE_NAME | EVERYTHING_REQUEST_PATH (0x00000003).
-    pub fn set_request_flags(&mut self, flags: RequestFlags) -> &'_ mut EverythingSearcher<'a> {
-        raw::Everything_SetRequestFlags(flags);
-        self
-    }
-
-    // --- Reading the search state ---
-    pub fn get_search(&self) -> OsString {
-        raw::Everything_GetSearch()
-    }
-
-    pub fn get_match_path(&self) -> bool {
-        raw::Everything_GetMatchPath()
-    }
-
-    pub fn get_match_case(&self) -> bool {
-        raw::Everything_GetMatchCase()
-    }
-
-    pub fn get_match_whole_word(&self) -> bool {
-        raw::Everything_GetMatchWholeWord()
-    }
-
-    pub fn get_regex(&self) -> bool {
-        raw::Everything_GetRegex()
-    }
-
-    pub fn get_max(&self) -> u32 {
-        raw::Everything_GetMax()
-    }
-
-    pub fn get_offset(&self) -> u32 {
-        raw::Everything_GetOffset()
-    }
-
-    pub fn get_sort(&self) -> SortType {
-        raw::Everything_GetSort()
-    }
-
-    pub fn get_request_flags(&self) -> RequestFlags {
-        raw::Everything_GetRequestFlags()
-    }
-}
-
-impl<'a> EverythingSearcher<'a> {
-    #[cfg(not(feature = "async"))]
-    /// Execute an Everything IPC query with the current search state.
-    ///
-    /// It may take some time if you query a lot of items. Therefore, blocking needs to be
-    /// considered in specific situations. (run it in new thread or use the `async` feature)
-    pub fn query<'b>(&'b mut self) -> EverythingResults<'b> {
-        raw::Everything_Query(true);
-        EverythingResults {
-            _phantom: PhantomData::<&'b ()>,
-        }
-    }
-
-    #[cfg(feature = "async")]
-    pub async fn query<'b>(&'b mut self) -> EverythingResults<'b> {
-        non_blocking::QueryFuture::<'b>::new().await
-    }
-
-    /// Query and sort the results by path then file name in place.
-    ///
-    /// **NOT RECOMMENDED!** Use searcher.set_sort(_) instead.
-    pub fn _query_and_sort_by_path<'b>(&'b mut self) -> EverythingResults<'b> {
-        raw::Everything_Query(true);
-        // SortResultsByPath is CPU Intensive. Sorting by path can take several seconds.
-        // For improved performance, use [`raw::Everything_SetSort`]
-        raw::Everything_SortResultsByPath();
-        EverythingResults {
-            _phantom: PhantomData::<&'b ()>,
-        }
-    }
-}
-
-#[cfg(feature = "async")]
-mod non_blocking {
-    use std::{
-        marker::PhantomData,
-        pin::Pin,
-        sync::{Arc, Mutex},
-        task::{Context, Poll, Waker},
-        thread,
-    };
-
-    use windows::{
-        core::w,
-        Win32::{
-            Foundation::{FALSE, HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
-            System::LibraryLoader::GetModuleHandleW,
-            UI::WindowsAndMessaging::{
-                CreateWindowExW, DefWindowProcW, DestroyWindow, GetClassInfoExW, PeekMessageW,
-                PostMessageW, RegisterClassExW, WaitMessage, HWND_MESSAGE, MSG, PM_NOREMOVE,
-                WINDOW_EX_STYLE, WM_COPYDATA, WM_USER, WNDCLASSEXW, WS_OVERLAPPED,
-            },
-        },
-    };
-
-    use tracing::debug;
-
-    use super::EverythingResults;
-    use crate::raw;
-
-    #[non_exhaustive]
-    pub struct QueryFuture<'a> {
-        // query_expected: ExpectedParams,
-        shared_state: Arc<Mutex<SharedState>>,
-        _phantom: PhantomData<&'a ()>,
-    }
-
-    /// Shared state between the future and the waiting thread
-    struct SharedState {
-        /// Whether or not the sleep time has elapsed
-        completed: bool,
-
-        /// The waker for the task that `TimerFuture` is running on.
-        /// The thread can use this after setting `completed = true` to tell
-        /// `TimerFuture`'s task to wake up, see that `completed = true`, and
-        /// move forward.
-        waker: Option<Waker>,
-    }
-
-    impl<'a> std::future::Future for QueryFuture<'a> {
-        type Output = EverythingResults<'a>;
-        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-            debug!("poll() called");
-            let mut shared_state = self.shared_state.lock().unwrap();
-            if shared_state.completed {
-                let results = EverythingResults {
-                    _phantom: PhantomData::<&'a ()>,
-                };
-                debug!("Poll::Ready(_)!");
-                Poll::Ready(results)
-            } else {
-                shared_state.waker = Some(cx.waker().clone());
-                debug!("Poll::Pending");
-                Poll::Pending
-            }
-        }
-    }
-
-    impl<'a> QueryFuture<'a> {
-        pub fn new() -> Self {
-            debug!("QueryFuture::new() start");
-
-            let shared_state = Arc::new(Mutex::new(SharedState {
-                completed: false,
-                waker: None,
-            }));
-
-            // Spawn the new thread
-            let thread_shared_state = shared_state.clone();
-            thread::spawn(move || {
-                debug!("thread::spawn");
-                unsafe {
-                    debug!("first time for init");
-                    raw::Everything_SetReplyID(CUSTOM_REPLY_ID);
-                    debug_assert_eq!(raw::Everything_GetReplyID(), CUSTOM_REPLY_ID);
-                    let hwnd = create_window().unwrap();
-                    raw::Everything_SetReplyWindow(hwnd);
-                    debug_assert_eq!(raw::Everything_GetReplyWindow(), hwnd);
-
-                    debug!("Execute Query with _FALSE_");
-                    assert!(raw::Everything_Query(false));
-
-                    let mut msg: MSG = MSG::default();
-                    debug!("WaitMessage()...");
-                    WaitMessage().unwrap(); // will blocking
-                    debug!("WaitMessage() Done, One msg at least, then PeekMessageW()...");
-                    if PeekMessageW(&mut msg, hwnd, 0, 0, PM_NOREMOVE) == FALSE {
-                        panic!("There must be a message in the queue after WaitMessage().");
-                    }
-                    debug!("Gooooooot it! WM_{:#06x} ({})", msg.message, msg.message);
-                    if msg.message != WM_USER_IS_QUERY_REPLY_DONE {
-                        panic!("Must be only one type message set by us.");
-                    }
-                    debug!("Yes, we did it. (now we have results)");
-                    DestroyWindow(hwnd).unwrap();
-                    debug!("DestroyWindow() Done");
-
-                    let mut shared_state = thread_shared_state.lock().unwrap();
-                    // Signal that the Query has completed and wake up the last
-                    // task on which the future was polled, if one exists.
-                    shared_state.completed = true;
-                    debug!("set .completed to true");
-                    if let Some(waker) = shared_state.waker.take() {
-                        debug!("waker.wake()");
-                        waker.wake()
-                    }
-                }
-            });
-
-            debug!("QueryFuture::new() end");
-            Self {
-                shared_state,
-                _phantom: PhantomData::<&'a ()>,
-            }
-        }
-    }
-
-    const WM_USER_IS_QUERY_REPLY_DONE: u32 = WM_USER + 42;
-    const CUSTOM_REPLY_ID: u32 = 9527;
-
-    extern "system" fn wndproc(
-        hwnd: HWND,
-        message: u32,
-        wparam: WPARAM,
-        lparam: LPARAM,
-    ) -> LRESULT {
-        unsafe {
-            match message {
-                WM_COPYDATA => {
-                    if raw::Everything_IsQueryReply(message, wparam, lparam, CUSTOM_REPLY_ID) {
-                        debug!("[wndproc] Everything_IsQueryReply() -> YEEEESSSSSS!! (So copy done and PostMessage(WM_USER_IS_QUERY_REPLY_DONE))");
-                        PostMessageW(hwnd, WM_USER_IS_QUERY_REPLY_DONE, WPARAM(0), LPARAM(0))
-                            .unwrap();
-                        LRESULT(1)
-                    } else {
-                        // DefWindowProcW(hwnd, message, wparam, lparam)
-                        panic!("!!!! Everything_IsQueryReply() -> NOOOO!!");
-                    }
-                }
-                _ => {
-                    debug!(
-                        "[wndproc] DefWindowProcW( msg => WM_{:#06x} ({}) )",
-                        message, message
-                    );
-                    DefWindowProcW(hwnd, message, wparam, lparam)
-                }
-            }
-        }
-    }
-
-    fn create_window() -> windows::core::Result<HWND> {
-        unsafe {
-            let instance: HINSTANCE = GetModuleHandleW(None)?.into();
-            assert!(!instance.is_invalid());
-
-            let window_class_name = w!("EVERYTHING_SDK_RUST");
-
-            let mut wc = WNDCLASSEXW {
-                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
-                hInstance: instance,
-                lpszClassName: window_class_name,
-                lpfnWndProc: Some(wndproc),
-                ..Default::default()
-            };
-
-            if GetClassInfoExW(instance, window_class_name, &mut wc).is_err() {
-                let atom = RegisterClassExW(&wc);
-                assert!(atom != 0);
-            }
-
-            let hwnd = CreateWindowExW(
-                WINDOW_EX_STYLE::default(),
-                window_class_name,
-                w!("The window for async query in everything-sdk-rs crate"),
-                WS_OVERLAPPED,
-                0,
-                0,
-                0,
-                0,
-                // Ref: https://devblogs.microsoft.com/oldnewthing/20171218-00/?p=97595
-                HWND_MESSAGE,
-                None,
-                instance,
-                None,
-            );
-
-            assert_ne!(hwnd, HWND(0));
-
-            Ok(hwnd)
-        }
-    }
-}
-
-#[non_exhaustive]
-pub struct EverythingResults<'a> {
-    _phantom: PhantomData<&'a ()>,
-}
-
-impl<'a> Drop for EverythingResults<'a> {
-    fn drop(&mut self) {
-        // I want to free memory for the results, but no api just for it.
-        // and should not call [`raw::Everything_Reset`], for long live reuse EverythingSearcher.
-        debug!("[Drop] EverythingResults is dropped!");
-    }
-}
-
-impl<'a> EverythingResults<'a> {
-    /// the results logic length, for available index in iterator.
-    pub fn len(&self) -> u32 {
-        self.num()
-    }
-
-    pub fn at(&self, index: u32) -> Option<EverythingItem<'a>> {
-        self.iter().nth(index as usize)
-    }
-
-    pub fn iter(&self) -> Iter<'a> {
-        Iter {
-            next_index: 0,
-            length: self.len(),
-            request_flags: self.request_flags(),
-            _phantom: PhantomData::<&'a ()>,
-        }
-    }
-
-    pub fn request_flags(&self) -> RequestFlags {
-        raw::Everything_GetResultListRequestFlags()
-    }
-
-    pub fn sort_type(&self) -> SortType {
-        raw::Everything_GetResultListSort()
-    }
-
-    fn is_query_version_2(&self) -> bool {
-        helper::should_use_query_version_2(self.request_flags(), self.sort_type())
-    }
-
-    pub fn num_files(&self) -> Result<u32> {
-        if self.is_query_version_2() {
-            Err(EverythingError::UnsupportedInQueryVersion2)
-        } else {
-            let num = raw::Everything_GetNumFileResults();
-            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-        }
-    }
-
-    pub fn num_folders(&self) -> Result<u32> {
-        if self.is_query_version_2() {
-            Err(EverythingError::UnsupportedInQueryVersion2)
-        } else {
-            let num = raw::Everything_GetNumFolderResults();
-            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-        }
-    }
-
-    /// the number of visible file and folder results.
-    pub fn num(&self) -> u32 {
-        let num = raw::Everything_GetNumResults();
-        num // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-    }
-
-    pub fn total_files(&self) -> Result<u32> {
-        if self.is_query_version_2() {
-            Err(EverythingError::UnsupportedInQueryVersion2)
-        } else {
-            let num = raw::Everything_GetTotFileResults();
-            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-        }
-    }
-
-    pub fn total_folders(&self) -> Result<u32> {
-        if self.is_query_version_2() {
-            Err(EverythingError::UnsupportedInQueryVersion2)
-        } else {
-            let num = raw::Everything_GetTotFolderResults();
-            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-        }
-    }
-
-    pub fn total(&self) -> u32 {
-        let total = raw::Everything_GetTotResults();
-        total // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
-    }
-}
-
-#[non_exhaustive]
-pub struct EverythingItem<'a> {
-    index: u32,
-    request_flags: RequestFlags,
-    _phantom: PhantomData<&'a ()>,
-}
-
-#[non_exhaustive]
-pub struct Iter<'a> {
-    next_index: u32,
-    length: u32,
-    request_flags: RequestFlags,
-    _phantom: PhantomData<&'a ()>,
-}
-
-impl<'a> Iterator for Iter<'a> {
-    type Item = EverythingItem<'a>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.next_index < self.length {
-            let index = self.next_index;
-            self.next_index += 1;
-            Some(EverythingItem {
-                index,
-                request_flags: self.request_flags,
-                _phantom: PhantomData::<&'a ()>,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let rest = usize::try_from(self.length - self.next_index).unwrap();
-        (rest, Some(rest))
-    }
-
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let index = self.next_index + u32::try_from(n).unwrap();
-        if index < self.length {
-            self.next_index = index + 1;
-            Some(EverythingItem {
-                index,
-                request_flags: self.request_flags,
-                _phantom: PhantomData::<&'a ()>,
-            })
-        } else {
-            self.next_index = self.length;
-            None
-        }
-    }
-}
-
-impl<'a> ExactSizeIterator for Iter<'a> {}
-
-impl<'a> IntoIterator for EverythingResults<'a> {
-    type Item = EverythingItem<'a>;
-    type IntoIter = Iter<'a>;
-    fn into_iter(self) -> Self::IntoIter {
-        Iter {
-            next_index: 0,
-            length: self.len(),
-            request_flags: self.request_flags(),
-            _phantom: PhantomData::<&'a ()>,
-        }
-    }
-}
-
-impl<'a> EverythingItem<'a> {
-    pub fn index(&self) -> u32 {
-        self.index
-    }
-
-    pub fn is_volume(&self) -> bool {
-        raw::Everything_IsVolumeResult(self.index)
-    }
-
-    pub fn is_folder(&self) -> bool {
-        raw::Everything_IsFolderResult(self.index)
-    }
-
-    pub fn is_file(&self) -> bool {
-        raw::Everything_IsFileResult(self.index)
-    }
-
-    pub fn filename(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FILE_NAME)?;
-        Ok(raw::Everything_GetResultFileName(self.index).unwrap())
-    }
-
-    pub fn path(&self) -> Result<PathBuf> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_PATH)?;
-        Ok(raw::Everything_GetResultPath(self.index).unwrap().into())
-    }
-
-    /// A convenient function to get the full path by Everything_GetResultFullPathName.
-    ///
-    /// Different from the [`full_path_name`], this is an unofficial function provided for
-    /// the special case. (We can use [`raw::Everything_GetResultFullPathName`] with the
-    /// two default flags EVERYTHING_REQUEST_PATH and EVERYTHING_REQUEST_FILE_NAME)
-    pub fn filepath(&self) -> Result<PathBuf> {
-        // A bit weird but this is a special case in the official documentation.
-        self.need_flags_set(
-            RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
-        )?;
-        let buf_len = u32::from(raw::Everything_GetResultFullPathNameSizeHint(self.index).unwrap());
-        let mut buf = vec![0; buf_len as usize];
-        let n_wchar =
-            u32::from(raw::Everything_GetResultFullPathName(self.index, &mut buf).unwrap());
-        assert_eq!(buf_len, n_wchar + 1);
-        Ok(U16CStr::from_slice(&buf).unwrap().to_os_string().into())
-    }
-
-    /// Get the full path name, can be with len limit if you need.
-    ///
-    /// Similar to x.path().join(x.filename()) if parent path is NOT drive root (like C:).
-    /// (Ref: <https://github.com/nodejs/node/issues/14405>)
-    ///
-    /// Buf if the pathname is too long, you can choose to cut off the tail, reduce the
-    /// memory consumption, or limit the max size of buffer memory allocation.
-    pub fn full_path_name(&self, max_len: Option<u32>) -> Result<PathBuf> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)?;
-        let size_hint =
-            u32::from(raw::Everything_GetResultFullPathNameSizeHint(self.index).unwrap());
-        let buf_len = std::cmp::min(size_hint, max_len.unwrap_or(u32::MAX)) as usize;
-        let mut buf = vec![0; buf_len];
-        let n_wchar =
-            u32::from(raw::Everything_GetResultFullPathName(self.index, &mut buf).unwrap());
-        assert_eq!(size_hint, n_wchar + 1);
-        Ok(U16CStr::from_slice(&buf).unwrap().to_os_string().into())
-    }
-
-    // Check if the corresponding flags are set. (usually just check a single flag)
-    fn need_flags_set(&self, flags: RequestFlags) -> Result<()> {
-        if self.request_flags.contains(flags) {
-            Ok(())
-        } else {
-            Err(EverythingError::InvalidRequest(
-                InvalidRequestError::RequestFlagsNotSet(flags),
-            ))
-        }
-    }
-
-    pub fn extension(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_EXTENSION)?;
-        Ok(raw::Everything_GetResultExtension(self.index).unwrap())
-    }
-
-    pub fn size(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_SIZE)?;
-        let file_size = raw::Everything_GetResultSize(self.index).unwrap();
-        // If request flag `RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES` is not set, the GetResultSize function
-        // will success, but the file_size for folder will be Some(-1). If the ATTRIBUTES flag is set. the
-        // GetResultSize will success too, but the file_size for folder will be Some(0).
-        //
-        // There is no relevant explanation in the documentation about that. (so wired, maybe we do not know
-        // whether this index points to a file or a directory unless we have ATTRIBUTES.)
-        //
-        // So for consistency, we will get Ok(0) for folder index regardless of whether the request flag
-        // `RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES` had been set.
-        u64::try_from(file_size).or_else(|_e| {
-            if raw::Everything_IsFolderResult(self.index) {
-                debug_assert_eq!(file_size, -1); // file_size will most likely be -1
-                Ok(0)
-            } else {
-                panic!(
-                    "file size should not be a negative integer => {}",
-                    file_size
-                )
-            }
-        })
-    }
-
-    pub fn date_created(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)?;
-        Ok(raw::Everything_GetResultDateCreated(self.index).unwrap())
-    }
-
-    pub fn date_modified(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)?;
-        Ok(raw::Everything_GetResultDateModified(self.index).unwrap())
-    }
-
-    pub fn date_accessed(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED)?;
-        Ok(raw::Everything_GetResultDateAccessed(self.index).unwrap())
-    }
-
-    pub fn attributes(&self) -> Result<u32> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES)?;
-        Ok(raw::Everything_GetResultAttributes(self.index).unwrap())
-    }
-
-    pub fn file_list_filename(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME)?;
-        Ok(raw::Everything_GetResultFileListFileName(self.index).unwrap())
-    }
-
-    pub fn run_count(&self) -> Result<u32> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT)?;
-        Ok(raw::Everything_GetResultRunCount(self.index))
-    }
-
-    pub fn date_run(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_RUN)?;
-        Ok(raw::Everything_GetResultDateRun(self.index).unwrap())
-    }
-
-    pub fn date_recently_changed(&self) -> Result<u64> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED)?;
-        Ok(raw::Everything_GetResultDateRecentlyChanged(self.index).unwrap())
-    }
-
-    pub fn highlighted_filename(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME)?;
-        Ok(raw::Everything_GetResultHighlightedFileName(self.index).unwrap())
-    }
-
-    pub fn highlighted_path(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_PATH)?;
-        Ok(raw::Everything_GetResultHighlightedPath(self.index).unwrap())
-    }
-
-    pub fn highlighted_full_path_and_filename(&self) -> Result<OsString> {
-        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME)?;
-        Ok(raw::Everything_GetResultHighlightedFullPathAndFileName(self.index).unwrap())
-    }
-}
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::marker::PhantomData;
+use std::os::windows::ffi::OsStringExt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use crate::raw;
+
+pub use raw::FileAttributes;
+pub use raw::FileInfoType;
+pub use raw::RequestFlags;
+pub use raw::SortType;
+pub use raw::TargetMachine;
+
+pub mod error {
+    use super::raw::LastError;
+    use super::{Feature, RequestFlags};
+    use thiserror::Error as ThisError;
+
+    pub type Result<T> = std::result::Result<T, EverythingError>;
+
+    #[non_exhaustive]
+    #[derive(ThisError, Debug)]
+    pub enum EverythingError {
+        #[error("Failed to allocate memory for the search query.")]
+        Memory,
+        #[error("IPC is not available.")]
+        Ipc,
+        #[error("Failed to register the search query window class.")]
+        RegisterClassEx,
+        #[error("Failed to create the search query window.")]
+        CreateWindow,
+        #[error("Failed to create the search query thread.")]
+        CreateThread,
+        #[error("Invalid index. The index must be greater or equal to 0 and less than the number of visible results.")]
+        InvalidIndex,
+        #[error("Invalid call.")]
+        InvalidCall,
+        #[error("invalid request data, request data first.")]
+        InvalidRequest(#[from] InvalidRequestError),
+        #[error("bad parameter.")]
+        InvalidParameter,
+        #[error("not supported when using set_request_flags or set_sort to non-default value. (that is in query verison 2)")]
+        UnsupportedInQueryVersion2,
+        #[error("{0} is required.")]
+        Unsupported(Feature),
+        #[error("the request flags {0:?} are not indexed, enable them in Everything (Tools > Options > Indexes) or remove them.")]
+        NotIndexed(RequestFlags),
+        #[error("timed out waiting for the reply of Everything.")]
+        Timeout,
+        #[error("the worker thread is gone.")]
+        Disconnected,
+        #[error("unknown error code {0} of Everything-SDK.")]
+        Unknown(u32),
+        #[error("Everything.exe is not found, install Everything or set the path of it.")]
+        NotFound,
+        #[error("failed to launch Everything: {0}")]
+        Launch(std::io::Error),
+        #[cfg(feature = "etp")]
+        #[error("ETP failed: {0}")]
+        Etp(#[from] std::io::Error),
+        #[cfg(feature = "windows-search")]
+        #[error("Windows Search failed: {0}")]
+        WindowsSearch(#[from] windows::core::Error),
+    }
+
+    impl From<LastError> for EverythingError {
+        fn from(last_error: LastError) -> Self {
+            match last_error {
+                LastError::EVERYTHING_ERROR_MEMORY => EverythingError::Memory,
+                LastError::EVERYTHING_ERROR_REGISTERCLASSEX => EverythingError::RegisterClassEx,
+                LastError::EVERYTHING_ERROR_CREATEWINDOW => EverythingError::CreateWindow,
+                LastError::EVERYTHING_ERROR_CREATETHREAD => EverythingError::CreateThread,
+                LastError::EVERYTHING_ERROR_INVALIDINDEX => EverythingError::InvalidIndex,
+                LastError::EVERYTHING_ERROR_INVALIDCALL => EverythingError::InvalidCall,
+                LastError::EVERYTHING_ERROR_INVALIDREQUEST => {
+                    EverythingError::InvalidRequest(InvalidRequestError::Unspecified)
+                }
+                LastError::EVERYTHING_ERROR_INVALIDPARAMETER => EverythingError::InvalidParameter,
+                LastError::Unknown(code) => EverythingError::Unknown(code),
+                // A failure without the error code, which is the IPC in practice.
+                LastError::EVERYTHING_OK | LastError::EVERYTHING_ERROR_IPC => EverythingError::Ipc,
+            }
+        }
+    }
+
+    #[non_exhaustive]
+    #[derive(ThisError, Debug)]
+    pub enum InvalidRequestError {
+        #[error("should set the request flag {0:?}")]
+        RequestFlagsNotSet(RequestFlags),
+        #[error("the SDK reported an invalid request, without the flag")]
+        Unspecified,
+    }
+}
+
+pub use error::{EverythingError, InvalidRequestError, Result};
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod backend;
+pub mod broker;
+pub mod channel;
+pub mod checked;
+pub mod compact;
+mod data;
+pub mod dedup;
+pub mod efu;
+#[cfg(feature = "egui")]
+pub mod egui_table;
+pub mod export;
+mod feature;
+pub mod filter;
+pub mod folder_size;
+pub mod format;
+#[cfg(feature = "fuzzy")]
+pub mod fuzzy;
+pub mod incremental;
+mod indexed;
+pub mod install;
+mod instance;
+#[cfg(feature = "ipc")]
+pub mod ipc;
+pub mod launch;
+mod logging;
+#[cfg(feature = "serde")]
+pub mod message;
+pub mod named_mutex;
+pub mod observer;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+pub mod persist;
+pub mod query;
+pub mod rate_limit;
+mod report;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod run_history;
+#[cfg(any(feature = "async-tokio", feature = "async-smol"))]
+pub mod runtime;
+pub mod score;
+pub mod service;
+pub mod snapshot;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+pub mod stats;
+pub mod streamed;
+mod time;
+pub mod tree;
+pub mod walk;
+pub mod watch;
+
+pub use broker::EverythingBroker;
+pub use data::{ItemData, ItemKind, OwnedItem, ResultRecord};
+pub use feature::Feature;
+pub use incremental::IncrementalSearch;
+pub use instance::Instance;
+#[cfg(feature = "serde")]
+pub use message::{SearchItem, SearchRequest, SearchResponse};
+pub use non_blocking::PendingQuery;
+pub use options::{spawn_query, SearchOptions};
+pub use report::{IndexReport, IndexedInfo};
+pub use snapshot::OwnedResults;
+pub use time::FileTime;
+
+use logging::debug;
+use widestring::U16CStr;
+
+pub  mod helper {
+    use windows::Win32::Foundation::FILETIME;
+
+    use super::*;
+
+    pub fn is_default_request_flags(request_flags: RequestFlags) -> bool {
+        request_flags == RequestFlags::default()
+    }
+
+    pub fn is_default_sort_type(sort_type: SortType) -> bool {
+        sort_type == SortType::default()
+    }
+
+    // when send IPC query, try version 2 first (if we specified some non-version 1 request flags or sort)
+    pub fn should_use_query_version_2(request_flags: RequestFlags, sort_type: SortType) -> bool {
+        !is_default_request_flags(request_flags) || !is_default_sort_type(sort_type)
+    }
+
+    /// The number of 100-nanosecond intervals between 1601-01-01 (the epoch of FILETIME)
+    /// and 1970-01-01 (the UNIX epoch).
+    pub const FILETIME_UNIX_EPOCH: u64 = 11_644_473_600 * 10_000_000;
+
+    /// The time elapsed since the FILETIME-as-u64 `filetime` until now.
+    ///
+    /// It is zero if the `filetime` is in the future. (clock skew or wrong timestamps)
+    pub fn filetime_age(filetime: u64) -> std::time::Duration {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        let now_intervals = (now.as_nanos() / 100) as u64 + FILETIME_UNIX_EPOCH;
+        let intervals = now_intervals.saturating_sub(filetime);
+        std::time::Duration::from_nanos(intervals.saturating_mul(100))
+    }
+
+    /// The root of a bare volume like `C:` (the path of the items in `C:\`) is `C:\`, the
+    /// others are as is.
+    pub(crate) fn with_root_dir(path: PathBuf) -> PathBuf {
+        let mut components = path.components();
+        let bare = matches!(
+            (components.next(), components.next()),
+            (Some(Component::Prefix(_)), None)
+        );
+        if bare {
+            path.join(Component::RootDir)
+        } else {
+            path
+        }
+    }
+
+}
+
+#[cfg(not(feature = "async"))]
+pub fn global() -> &'static std::sync::Mutex<EverythingGlobal> {
+    static EVERYTHING_CELL: OnceLock<std::sync::Mutex<EverythingGlobal>> = OnceLock::new();
+    EVERYTHING_CELL.get_or_init(|| std::sync::Mutex::new(EverythingGlobal::take()))
+}
+
+#[cfg(feature = "async")]
+pub fn global() -> &'static futures::lock::Mutex<EverythingGlobal> {
+    static EVERYTHING_CELL: OnceLock<futures::lock::Mutex<EverythingGlobal>> = OnceLock::new();
+    EVERYTHING_CELL.get_or_init(|| futures::lock::Mutex::new(EverythingGlobal::take()))
+}
+
+/// Lock the [`global`] by blocking the current thread, for the sync code in an async app.
+///
+/// Then query by [`EverythingSearcher::query_blocking`]. Never call it in an async task, as
+/// it blocks the executor until the lock is released.
+#[cfg(feature = "async")]
+pub fn global_blocking() -> futures::lock::MutexGuard<'static, EverythingGlobal> {
+    futures::executor::block_on(global().lock())
+}
+
+/// Clean up the SDK when the process exits, by the `atexit` of the C runtime.
+///
+/// It is only for the tools checking the leaks at exit, as the OS frees all the memory of the
+/// process anyway. Nothing is done if the [`global`] lock is still held at exit. Only the first
+/// call registers it.
+pub fn cleanup_at_exit() {
+    extern "C" {
+        fn atexit(callback: extern "C" fn()) -> std::ffi::c_int;
+    }
+
+    extern "C" fn cleanup() {
+        #[cfg(not(feature = "async"))]
+        let everything = global().try_lock().ok();
+        #[cfg(feature = "async")]
+        let everything = global().try_lock();
+        if let Some(mut everything) = everything {
+            everything.cleanup();
+        }
+    }
+
+    static REGISTERED: std::sync::Once = std::sync::Once::new();
+    // SAFETY: the callback is a plain function which never unwinds.
+    REGISTERED.call_once(|| unsafe {
+        atexit(cleanup);
+    });
+}
+
+/// The raw token used by all the ergonomic wrappers.
+///
+/// Every type here (searcher, results and items) is borrowed from the only one
+/// [`EverythingGlobal`] behind the lock of [`global`], which took the only one token of the
+/// process, so the raw calls are serialized already.
+fn access() -> raw::RawAccess {
+    // SAFETY: the caller holds (or borrows from) the guard of the global lock, which owns the
+    // token taken by `EverythingGlobal::take`.
+    unsafe { raw::RawAccess::new_unchecked() }
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct EverythingGlobal {
+    instance: Option<Instance>,
+}
+
+// No `Drop` for it, as the static variable would never be dropped, see `cleanup` instead.
+
+impl EverythingGlobal {
+    /// The only one global, which takes the only one raw token, so nothing else can lock it.
+    fn take() -> Self {
+        // The token is a zero-sized proof, taking it is enough to own it.
+        let _access = raw::RawAccess::take().expect("the raw token is taken by the global only");
+        EverythingGlobal { instance: None }
+    }
+
+    /// New the only one searcher.
+    ///
+    /// There is **at most one** searcher can exist globally at the same time.
+    pub fn searcher<'a>(&'a mut self) -> EverythingSearcher<'a> {
+        EverythingSearcher {
+            scratch: Mutex::new(Vec::new()),
+            _phantom: PhantomData::<&'a ()>,
+        }
+    }
+
+    /// Free all the memory allocated by the SDK, that is the search text and the results, and
+    /// reset the search state to the default.
+    ///
+    /// The global is in a static variable which is never dropped, so the long-running apps
+    /// call this to release a huge result list in time. It is fine to query again after it,
+    /// the SDK initializes itself again. The instance name is kept.
+    pub fn cleanup(&mut self) {
+        raw::Everything_CleanUp(&access());
+    }
+
+    // --- General ---
+
+    /// Everything uses the version format: `<major>.<minor>.<revision>.<build>`.
+    /// The build part is incremental and unique for all Everything versions.
+    pub fn version(&self) -> Result<(u32, u32, u32, u32, TargetMachine)> {
+        Ok((
+            self.get_major_version()?,
+            self.get_minor_version()?,
+            self.get_revision()?,
+            self.get_build_number()?,
+            self.get_target_machine()?,
+        ))
+    }
+
+    pub fn get_major_version(&self) -> Result<u32> {
+        raw::Everything_GetMajorVersion(&access()).map_err(Into::into)
+    }
+
+    pub fn get_minor_version(&self) -> Result<u32> {
+        raw::Everything_GetMinorVersion(&access()).map_err(Into::into)
+    }
+
+    pub fn get_revision(&self) -> Result<u32> {
+        raw::Everything_GetRevision(&access()).map_err(Into::into)
+    }
+
+    pub fn get_build_number(&self) -> Result<u32> {
+        raw::Everything_GetBuildNumber(&access()).map_err(Into::into)
+    }
+
+    pub fn get_target_machine(&self) -> Result<TargetMachine> {
+        raw::Everything_GetTargetMachine(&access()).ok_or(EverythingError::Ipc)
+    }
+
+    /// Request Everything to save settings and data to disk and exit.
+    pub fn save_and_exit(&mut self) -> Result<bool> {
+        raw::Everything_Exit(&access()).map_err(Into::into)
+    }
+
+    /// Check if Everything's database is loaded.
+    ///
+    /// When Everything is loading, any queries will appear to return no results.
+    /// Use this to determine if the database has been loaded before performing a query.
+    pub fn is_db_loaded(&self) -> Result<bool> {
+        raw::Everything_IsDBLoaded(&access()).map_err(Into::into)
+    }
+
+    /// Check if Everything is running as administrator or as a standard user.
+    pub fn is_admin(&self) -> Result<bool> {
+        raw::Everything_IsAdmin(&access()).map_err(Into::into)
+    }
+
+    /// Check if Everything is saving settings and data to `%APPDATA%\Everything` or to the same location
+    /// as the `Everything.exe`.
+    pub fn is_appdata(&self) -> Result<bool> {
+        raw::Everything_IsAppData(&access()).map_err(Into::into)
+    }
+
+    /// Request Everything to forcefully rebuild the Everything index.
+    ///
+    /// Requesting a rebuild will mark all indexes as dirty and start the rebuild process.
+    /// Use `self.is_db_loaded()` to determine if the database has been rebuilt before
+    /// performing a query.
+    pub fn rebuild_db(&mut self) -> Result<bool> {
+        // rebuild the database.
+        raw::Everything_RebuildDB(&access()).map_err(Into::into)
+    }
+
+    /// Request Everything to rescan all folder indexes.
+    ///
+    /// Everything will begin updating all folder indexes in the background.
+    pub fn update_all_folder_indexes(&mut self) -> Result<bool> {
+        // Request all folder indexes be rescanned.
+        raw::Everything_UpdateAllFolderIndexes(&access()).map_err(Into::into)
+    }
+
+    /// Request Everything to save the index to disk.
+    ///
+    /// The index is only saved to disk when you exit Everything.
+    /// Call this to write the index to the file: `Everything.db`.
+    pub fn save_db(&mut self) -> Result<bool> {
+        // flush index to disk
+        raw::Everything_SaveDB(&access()).map_err(Into::into)
+    }
+
+    // --- Run History ---
+
+    /// Request Everything to save the run history to disk.
+    ///
+    /// The run history is only saved to disk when you close an Everything search window or
+    /// exit Everything.
+    /// Call this to write the run history to the file: `Run History.csv`.
+    pub fn save_run_history(&mut self) -> Result<bool> {
+        self.require(Feature::RunHistory)?;
+        // flush run history to disk
+        raw::Everything_SaveRunHistory(&access()).map_err(Into::into)
+    }
+
+    /// Delete all run history.
+    ///
+    /// Calling this function will clear all run history from memory and disk.
+    pub fn delete_run_history(&mut self) -> Result<bool> {
+        self.require(Feature::RunHistory)?;
+        // clear run history
+        raw::Everything_DeleteRunHistory(&access()).map_err(Into::into)
+    }
+
+    /// Gets the run count from a specified file in the Everything index by file name.
+    pub fn get_run_count(&self, filename: impl AsRef<Path>) -> Result<u32> {
+        self.require(Feature::RunHistory)?;
+        raw::Everything_GetRunCountFromFileName(&access(), filename.as_ref()).map_err(Into::into)
+    }
+
+    /// Sets the run count for a specified file in the Everything index by file name.
+    pub fn set_run_count(&mut self, filename: impl AsRef<Path>, run_count: u32) -> Result<()> {
+        self.require(Feature::RunHistory)?;
+        if raw::Everything_SetRunCountFromFileName(&access(), filename.as_ref(), run_count) {
+            Ok(())
+        } else {
+            Err(EverythingError::Ipc)
+        }
+    }
+
+    /// Increments the run count by one for a specified file in the Everything by file name.
+    pub fn inc_run_count(&mut self, filename: impl AsRef<Path>) -> Result<u32> {
+        self.require(Feature::RunHistory)?;
+        raw::Everything_IncRunCountFromFileName(&access(), filename.as_ref())
+            .map(|n| n.get())
+            .map_err(Into::into)
+    }
+
+    // --- Others ---
+
+    /// Check if the specified file information is indexed and has fast sort enabled.
+    pub fn is_fast_sort(&self, sort_type: SortType) -> Result<bool> {
+        self.require(Feature::FastSort)?;
+        raw::Everything_IsFastSort(&access(), sort_type).map_err(Into::into)
+    }
+
+    /// Check if the specified file information is indexed.
+    pub fn is_file_info_indexed(&self, file_info_type: FileInfoType) -> Result<bool> {
+        self.require(Feature::FileInfoIndexed)?;
+        raw::Everything_IsFileInfoIndexed(&access(), file_info_type).map_err(Into::into)
+    }
+}
+
+#[non_exhaustive]
+pub struct EverythingSearcher<'a> {
+    /// The buffer for reading the long full paths in UTF-16, reused by all the items.
+    scratch: Mutex<Vec<u16>>,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Drop for EverythingSearcher<'_> {
+    fn drop(&mut self) {
+        raw::Everything_Reset(&access()); // CAUTION!
+        debug!("[Drop] EverythingSearcher is dropped! (did Reset)");
+    }
+}
+
+/// The `MAX_PATH` of Windows, the most of the full paths fit in it.
+const SMALL_PATH: usize = 260;
+
+/// Read the full path name of the result at `index` into a buffer of `len` wchars (including
+/// the null terminator, truncated if too small), and pass it to `f` without the terminator.
+///
+/// The buffer is on the stack if `len` fits in [`SMALL_PATH`], otherwise the `scratch` buffer of
+/// the searcher, which must not be in use by the caller. `None` if the SDK fails.
+fn with_full_path<R>(
+    scratch: &Mutex<Vec<u16>>,
+    index: u32,
+    len: usize,
+    f: impl FnOnce(&[u16]) -> R,
+) -> Option<R> {
+    let read = |buf: &mut [u16]| {
+        raw::Everything_GetResultFullPathName(&access(), index, buf)
+            .map(|n_wchar| u32::from(n_wchar) as usize)
+    };
+    if len <= SMALL_PATH {
+        let mut buf = [0; SMALL_PATH];
+        let n_wchar = read(&mut buf[..len])?;
+        Some(f(&buf[..n_wchar]))
+    } else {
+        let mut buf = scratch.lock().unwrap_or_else(PoisonError::into_inner);
+        buf.clear();
+        buf.resize(len, 0);
+        let n_wchar = read(&mut buf)?;
+        Some(f(&buf[..n_wchar]))
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    // --- Manipulating the search state ---
+    /// empty string "" by default.
+    pub fn set_search(&mut self, text: impl AsRef<OsStr>) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetSearch(&access(), text);
+        self
+    }
+
+    /// Search the `text` verbatim, the operators and spaces in it are escaped by
+    /// [`query::escape`]. For a raw path or any user input.
+    pub fn set_search_literal(&mut self, text: impl AsRef<str>) -> &'_ mut EverythingSearcher<'a> {
+        self.set_search(query::escape(text.as_ref()))
+    }
+
+    /// Only search in the file list (like an `.efu`) loaded in Everything, by the
+    /// `filelistfilename:` function (`filelist:` is for the list of names), and request
+    /// `EVERYTHING_REQUEST_FILE_LIST_FILE_NAME` so that [`EverythingItem::file_list_filename`]
+    /// works.
+    ///
+    /// It scopes the current search text, so call it after [`set_search`](Self::set_search).
+    /// The file list should be added in Tools > Options > Indexes > File Lists, or opened by
+    /// File > Open File List, otherwise nothing is found.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.iso").in_file_list(r"D:\backup.efu");
+    /// for item in searcher.query() {
+    ///     println!("{:?} in {:?}", item.filename(), item.file_list_filename());
+    /// }
+    /// ```
+    pub fn in_file_list(&mut self, path: impl AsRef<Path>) -> &'_ mut EverythingSearcher<'a> {
+        let mut search = OsString::from("filelistfilename:");
+        search.push(query::escape(&path.as_ref().to_string_lossy()));
+        let text = self.get_search();
+        if !text.is_empty() {
+            search.push(" ");
+            search.push(text);
+        }
+        let flags = self.get_request_flags() | RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME;
+        self.set_search(search).set_request_flags(flags)
+    }
+
+    /// disable (false) by default.
+    pub fn set_match_path(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetMatchPath(&access(), enable);
+        self
+    }
+
+    /// disable (false) by default.
+    pub fn set_match_case(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetMatchCase(&access(), enable);
+        self
+    }
+
+    /// disable (false) by default.
+    pub fn set_match_whole_word(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetMatchWholeWord(&access(), enable);
+        self
+    }
+
+    /// disable (false) by default.
+    pub fn set_regex(&mut self, enable: bool) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetRegex(&access(), enable);
+        self
+    }
+
+    /// `u32::MAX` (0xffffffff) by default, which means all results.
+    pub fn set_max(&mut self, max_results: u32) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetMax(&access(), max_results);
+        self
+    }
+
+    /// zero (0) by default.
+    pub fn set_offset(&mut self, offset: u32) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetOffset(&access(), offset);
+        self
+    }
+
+    /// The default sort is EVERYTHING_SORT_NAME_ASCENDING (1). This sort is free.
+    pub fn set_sort(&mut self, sort_type: SortType) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetSort(&access(), sort_type);
+        self
+    }
+
+    /// The default request flags are EVERYTHING_REQUEST_FILE_NAME | EVERYTHING_REQUEST_PATH (0x00000003).
+    pub fn set_request_flags(&mut self, flags: RequestFlags) -> &'_ mut EverythingSearcher<'a> {
+        raw::Everything_SetRequestFlags(&access(), flags);
+        self
+    }
+
+    // --- Reading the search state ---
+    pub fn get_search(&self) -> OsString {
+        raw::Everything_GetSearch(&access())
+    }
+
+    pub fn get_match_path(&self) -> bool {
+        raw::Everything_GetMatchPath(&access())
+    }
+
+    pub fn get_match_case(&self) -> bool {
+        raw::Everything_GetMatchCase(&access())
+    }
+
+    pub fn get_match_whole_word(&self) -> bool {
+        raw::Everything_GetMatchWholeWord(&access())
+    }
+
+    pub fn get_regex(&self) -> bool {
+        raw::Everything_GetRegex(&access())
+    }
+
+    pub fn get_max(&self) -> u32 {
+        raw::Everything_GetMax(&access())
+    }
+
+    pub fn get_offset(&self) -> u32 {
+        raw::Everything_GetOffset(&access())
+    }
+
+    pub fn get_sort(&self) -> SortType {
+        raw::Everything_GetSort(&access())
+    }
+
+    pub fn get_request_flags(&self) -> RequestFlags {
+        raw::Everything_GetRequestFlags(&access())
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    #[cfg(not(feature = "async"))]
+    /// Execute an Everything IPC query with the current search state.
+    ///
+    /// It may take some time if you query a lot of items. Therefore, blocking needs to be
+    /// considered in specific situations. (run it in new thread or use the `async` feature)
+    pub fn query<'b>(&'b mut self) -> EverythingResults<'b> {
+        let observation = observer::start();
+        let ok = raw::Everything_Query(&access(), true);
+        if let Some(observation) = observation {
+            observation.finish_blocking(ok);
+        }
+        EverythingResults {
+            scratch: &self.scratch,
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn query<'b>(&'b mut self) -> EverythingResults<'b> {
+        let observation = observer::start();
+        let result = match non_blocking::QueryFuture::<'b>::new(&self.scratch, None) {
+            Ok(future) => future.await,
+            Err(err) => Err(err),
+        };
+        if let Some(observation) = observation {
+            observation.finish(&result);
+        }
+        match result {
+            Ok(results) => results,
+            // Ignore the failure like the sync query does, see `query_timeout` for the error.
+            Err(_) => EverythingResults {
+                scratch: &self.scratch,
+            },
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    /// Execute the query like [`query`](Self::query), but give up if the reply of Everything
+    /// doesn't arrive in `timeout`, with [`EverythingError::Timeout`].
+    ///
+    /// The late reply is discarded when it arrives, so the searcher can be used again at once.
+    pub fn query_timeout<'b>(&'b mut self, timeout: Duration) -> Result<EverythingResults<'b>> {
+        let observation = observer::start();
+        let result = non_blocking::QueryFuture::<'b>::new(&self.scratch, Some(timeout))
+            .and_then(non_blocking::QueryFuture::wait);
+        if let Some(observation) = observation {
+            observation.finish(&result);
+        }
+        result
+    }
+
+    #[cfg(feature = "async")]
+    /// Execute the query like [`query`](Self::query), but give up if the reply of Everything
+    /// doesn't arrive in `timeout`, with [`EverythingError::Timeout`].
+    ///
+    /// The late reply is discarded when it arrives, so the searcher can be used again at once.
+    pub async fn query_timeout<'b>(
+        &'b mut self,
+        timeout: Duration,
+    ) -> Result<EverythingResults<'b>> {
+        let observation = observer::start();
+        let result = match non_blocking::QueryFuture::<'b>::new(&self.scratch, Some(timeout)) {
+            Ok(future) => future.await,
+            Err(err) => Err(err),
+        };
+        if let Some(observation) = observation {
+            observation.finish(&result);
+        }
+        result
+    }
+
+    /// Send the query with the current search state, and get the owned results later from the
+    /// [`PendingQuery`], which doesn't borrow the searcher.
+    ///
+    /// Each query has its own reply ID, so many of them can be pending at the same time (such
+    /// as one for each tab of the UI), and each reply is routed to its own `PendingQuery`.
+    ///
+    /// Fail with [`EverythingError::Ipc`] at once if Everything is not running.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// let pdf = searcher.set_search("*.pdf").submit()?;
+    /// let docx = searcher.set_search("*.docx").submit()?;
+    /// drop(searcher);
+    /// drop(everything);
+    /// println!("{} and {}", pdf.wait()?.len(), docx.wait()?.len());
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn submit(&mut self) -> Result<PendingQuery> {
+        non_blocking::PendingQuery::send(self.get_options(), None)
+    }
+
+    /// The same as [`submit`](Self::submit), but the `PendingQuery` fails with
+    /// [`EverythingError::Timeout`] if the reply doesn't arrive in `timeout`.
+    pub fn submit_timeout(&mut self, timeout: Duration) -> Result<PendingQuery> {
+        non_blocking::PendingQuery::send(self.get_options(), Some(timeout))
+    }
+
+    #[cfg(feature = "async")]
+    /// Execute the query by blocking the current thread, the same as the `query` without the
+    /// `async` feature, see [`global_blocking`].
+    pub fn query_blocking<'b>(&'b mut self) -> EverythingResults<'b> {
+        let observation = observer::start();
+        let ok = raw::Everything_Query(&access(), true);
+        if let Some(observation) = observation {
+            observation.finish_blocking(ok);
+        }
+        EverythingResults {
+            scratch: &self.scratch,
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    /// Execute the query and copy all the visible results into `out`.
+    ///
+    /// The `out` is cleared and refilled, but the allocation of itself and the full path
+    /// buffers of the items in it are reused, so the polling applications will not
+    /// re-allocate a large snapshot on every refresh.
+    pub fn query_into(&mut self, out: &mut Vec<ItemData>) {
+        self.query().fill_data(out);
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn query_into(&mut self, out: &mut Vec<ItemData>) {
+        self.query().await.fill_data(out);
+    }
+
+    /// Query and sort the results by path then file name in place.
+    ///
+    /// **NOT RECOMMENDED!** Use searcher.set_sort(_) instead.
+    pub fn _query_and_sort_by_path<'b>(&'b mut self) -> EverythingResults<'b> {
+        raw::Everything_Query(&access(), true);
+        // SortResultsByPath is CPU Intensive. Sorting by path can take several seconds.
+        // For improved performance, use [`raw::Everything_SetSort`]
+        raw::Everything_SortResultsByPath(&access());
+        EverythingResults {
+            scratch: &self.scratch,
+        }
+    }
+}
+
+mod non_blocking {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            mpsc, Arc, Condvar, Mutex, MutexGuard, PoisonError,
+        },
+        task::{Context, Poll, Waker},
+        thread,
+        time::Duration,
+    };
+
+    use windows::{
+        core::w,
+        Win32::{
+            Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+            System::LibraryLoader::GetModuleHandleW,
+            UI::WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetClassInfoExW, GetMessageW,
+                KillTimer, PostMessageW, RegisterClassExW, SetTimer, HWND_MESSAGE, MSG,
+                WINDOW_EX_STYLE, WM_COPYDATA, WM_TIMER, WM_USER, WNDCLASSEXW, WS_OVERLAPPED,
+            },
+        },
+    };
+
+    use super::logging::{debug, warn};
+    use super::observer::{self, Observation};
+    use super::{access, EverythingError, EverythingResults, OwnedResults, Result, SearchOptions};
+    use crate::raw::{self, copydata_of, parse_query_reply, QueryReply, QueryVersion};
+
+    #[non_exhaustive]
+    pub struct QueryFuture<'a> {
+        // query_expected: ExpectedParams,
+        query: Query,
+        scratch: &'a Mutex<Vec<u16>>,
+    }
+
+    /// The query sent with its own reply ID, shared by [`QueryFuture`] and [`PendingQuery`].
+    struct Query {
+        reply_id: u32,
+        shared: Arc<Shared>,
+    }
+
+    /// The state with the condvar for the blocking waiters, see [`Query::wait`].
+    struct Shared {
+        state: Mutex<SharedState>,
+        done: Condvar,
+    }
+
+    /// Shared state between the future and the message loop thread
+    struct SharedState {
+        /// What happened to the query, `None` if still waiting for the reply
+        outcome: Option<Outcome>,
+
+        /// The waker for the task that the future is running on.
+        /// The wndproc can use this after setting the `outcome` to tell
+        /// the future's task to wake up, see the `outcome`, and
+        /// move forward.
+        waker: Option<Waker>,
+    }
+
+    enum Outcome {
+        /// The reply has been copied into the global state.
+        Copied,
+        /// The reply decoded by ourselves, `None` if malformed.
+        Decoded(Option<QueryReply>),
+        TimedOut,
+        /// The reply could not be copied into the global state.
+        Failed,
+    }
+
+    /// A query in flight.
+    struct Pending {
+        reply_id: u32,
+        /// Decode the reply in this layout, or copy it into the global state if `None`.
+        decode: Option<QueryVersion>,
+        shared: Arc<Shared>,
+    }
+
+    /// All the queries in flight, only a few at the same time so a `Vec` is fine.
+    ///
+    /// At most one of them copies into the global state, as the caller holds the global lock.
+    static PENDING: Mutex<Vec<Pending>> = Mutex::new(Vec::new());
+
+    /// Each query has its own reply ID, so the replies are routed to the right query, and the
+    /// late reply of a dropped query is told apart.
+    static NEXT_REPLY_ID: AtomicU32 = AtomicU32::new(9527);
+
+    /// Ask the message loop thread to time out the query, as only the thread owning the window
+    /// can set the timer of it. The `wparam` is the reply ID and the `lparam` is in millis.
+    const WM_USER_SET_QUERY_TIMEOUT: u32 = WM_USER + 42;
+
+    fn pending() -> MutexGuard<'static, Vec<Pending>> {
+        PENDING.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Take the query of `reply_id` out of the [`PENDING`], if still there.
+    fn take_pending(reply_id: u32) -> Option<Pending> {
+        let mut pending = pending();
+        let at = pending.iter().position(|p| p.reply_id == reply_id)?;
+        Some(pending.swap_remove(at))
+    }
+
+    impl<'a> Future for QueryFuture<'a> {
+        type Output = Result<EverythingResults<'a>>;
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            debug!("poll() called");
+            let scratch = self.scratch;
+            self.query
+                .poll(cx)
+                .map(|outcome| results_of(outcome, scratch))
+        }
+    }
+
+    fn results_of(outcome: Outcome, scratch: &Mutex<Vec<u16>>) -> Result<EverythingResults<'_>> {
+        match outcome {
+            Outcome::Copied => Ok(EverythingResults { scratch }),
+            Outcome::Decoded(_) => unreachable!("the reply to copy is not decoded"),
+            Outcome::TimedOut => Err(EverythingError::Timeout),
+            Outcome::Failed => Err(EverythingError::Ipc),
+        }
+    }
+
+    impl Shared {
+        fn lock(&self) -> MutexGuard<'_, SharedState> {
+            self.state.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        /// Set the outcome by the message loop thread, and wake up the waiters.
+        fn finish(&self, outcome: Outcome) {
+            let mut shared_state = self.lock();
+            shared_state.outcome = Some(outcome);
+            // Wake up the last task on which the future was polled, if one exists.
+            if let Some(waker) = shared_state.waker.take() {
+                debug!("waker.wake()");
+                waker.wake()
+            }
+            self.done.notify_all();
+        }
+    }
+
+    impl Query {
+        /// Send the query with the current search state, which times out if no reply in
+        /// `timeout`. The caller must hold the global lock.
+        ///
+        /// Fail with [`EverythingError::Ipc`] if Everything is not running, and then nothing
+        /// is left pending, or with the error of [`reply_window`] if there is no window to
+        /// receive the reply.
+        fn send(decode: Option<QueryVersion>, timeout: Option<Duration>) -> Result<Self> {
+            let shared = Arc::new(Shared {
+                state: Mutex::new(SharedState {
+                    outcome: None,
+                    waker: None,
+                }),
+                done: Condvar::new(),
+            });
+
+            let hwnd = reply_window()?;
+            let reply_id = NEXT_REPLY_ID.fetch_add(1, Ordering::Relaxed);
+            raw::Everything_SetReplyID(&access(), reply_id);
+            raw::Everything_SetReplyWindow(&access(), hwnd);
+            pending().push(Pending {
+                reply_id,
+                decode,
+                shared: shared.clone(),
+            });
+
+            if let Some(timeout) = timeout {
+                let millis = timeout.as_millis().min(u32::MAX as u128) as u32;
+                // If failed, the thread is gone and the reply will never come either.
+                let _ = unsafe {
+                    PostMessageW(
+                        hwnd,
+                        WM_USER_SET_QUERY_TIMEOUT,
+                        WPARAM(reply_id as usize),
+                        LPARAM(millis as isize),
+                    )
+                };
+            }
+
+            debug!("Execute Query {} with _FALSE_", reply_id);
+            if !raw::Everything_Query(&access(), false) {
+                debug!("Query {} failed to send", reply_id);
+                // The timer (if any) finds nothing pending when it fires, that is fine.
+                take_pending(reply_id);
+                raw::Everything_SetReplyID(&access(), 0);
+                return Err(EverythingError::Ipc);
+            }
+
+            Ok(Query { reply_id, shared })
+        }
+
+        fn poll(&self, cx: &mut Context<'_>) -> Poll<Outcome> {
+            let mut shared_state = self.shared.lock();
+            match shared_state.outcome.take() {
+                Some(outcome) => {
+                    debug!("Poll::Ready(_)!");
+                    Poll::Ready(outcome)
+                }
+                None => {
+                    shared_state.waker = Some(cx.waker().clone());
+                    debug!("Poll::Pending");
+                    Poll::Pending
+                }
+            }
+        }
+
+        /// Block the current thread until the reply or the timeout.
+        fn wait(&self) -> Outcome {
+            let mut shared_state = self.shared.lock();
+            loop {
+                if let Some(outcome) = shared_state.outcome.take() {
+                    return outcome;
+                }
+                shared_state = self
+                    .shared
+                    .done
+                    .wait(shared_state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+    }
+
+    impl Drop for Query {
+        /// Cancel the query if not finished yet, its reply will be discarded when it arrives.
+        fn drop(&mut self) {
+            if take_pending(self.reply_id).is_some() {
+                debug!("[Drop] Query {} is cancelled", self.reply_id);
+            }
+        }
+    }
+
+    impl<'a> QueryFuture<'a> {
+        /// Send the query, which fails with [`EverythingError::Timeout`] if no reply in `timeout`,
+        /// or at once with [`EverythingError::Ipc`] if Everything is not running.
+        pub fn new(scratch: &'a Mutex<Vec<u16>>, timeout: Option<Duration>) -> Result<Self> {
+            debug!("QueryFuture::new() start");
+            let query = Query::send(None, timeout)?;
+            debug!("QueryFuture::new() end");
+            Ok(Self { query, scratch })
+        }
+
+        /// Block the current thread until the reply or the timeout.
+        #[cfg_attr(feature = "async", allow(dead_code))]
+        pub fn wait(self) -> Result<EverythingResults<'a>> {
+            results_of(self.query.wait(), self.scratch)
+        }
+    }
+
+    impl Drop for QueryFuture<'_> {
+        /// The late reply of the cancelled query would not land in the global state of the
+        /// next query, as it is discarded by the reply ID. Reset the reply ID for the queries
+        /// sent by the SDK later.
+        fn drop(&mut self) {
+            // The future borrows the searcher, so we still hold the global lock here.
+            raw::Everything_SetReplyID(&access(), 0);
+        }
+    }
+
+    /// A query in flight decoded into an owned snapshot, see
+    /// [`EverythingSearcher::submit`](super::EverythingSearcher::submit).
+    ///
+    /// It is a future of the results, or [`wait`](Self::wait) for them in the blocking way.
+    /// Dropping it cancels the query, its reply is discarded when it arrives.
+    #[must_use = "the query is cancelled when dropped"]
+    pub struct PendingQuery {
+        query: Query,
+        options: SearchOptions,
+        observation: Option<Observation>,
+    }
+
+    impl std::fmt::Debug for PendingQuery {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PendingQuery")
+                .field("reply_id", &self.query.reply_id)
+                .field("options", &self.options)
+                .finish()
+        }
+    }
+
+    impl PendingQuery {
+        /// Send the query with the current search state. The caller must hold the global lock.
+        pub(super) fn send(options: SearchOptions, timeout: Option<Duration>) -> Result<Self> {
+            let version = QueryVersion::for_request(options.request_flags, options.sort);
+            let observation = observer::start_with(&options);
+            match Query::send(Some(version), timeout) {
+                Ok(query) => Ok(PendingQuery {
+                    query,
+                    options,
+                    observation,
+                }),
+                Err(err) => {
+                    if let Some(observation) = observation {
+                        observation.error(&err);
+                    }
+                    Err(err)
+                }
+            }
+        }
+
+        /// The reply ID of the query, unique in the process.
+        pub fn reply_id(&self) -> u32 {
+            self.query.reply_id
+        }
+
+        /// The options of the query.
+        pub fn options(&self) -> &SearchOptions {
+            &self.options
+        }
+
+        fn results_of(&mut self, outcome: Outcome) -> Result<OwnedResults> {
+            let results = match outcome {
+                Outcome::Decoded(Some(reply)) => {
+                    Ok(OwnedResults::from_reply(reply, self.options.clone()))
+                }
+                Outcome::Decoded(None) | Outcome::Failed => Err(EverythingError::Ipc),
+                Outcome::Copied => unreachable!("the reply to decode is not copied"),
+                Outcome::TimedOut => Err(EverythingError::Timeout),
+            };
+            if let Some(observation) = self.observation.take() {
+                observation.finish_owned(&results);
+            }
+            results
+        }
+
+        /// Block the current thread until the results.
+        pub fn wait(mut self) -> Result<OwnedResults> {
+            let outcome = self.query.wait();
+            self.results_of(outcome)
+        }
+    }
+
+    impl Future for PendingQuery {
+        type Output = Result<OwnedResults>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            self.query.poll(cx).map(|outcome| self.results_of(outcome))
+        }
+    }
+
+    /// The message-only window receiving all the replies, created with its message loop thread
+    /// by the first query, and living until the process exits.
+    ///
+    /// Fail with [`EverythingError::CreateThread`] or [`EverythingError::CreateWindow`], and
+    /// then it is tried again by the next query.
+    fn reply_window() -> Result<HWND> {
+        static WINDOW: Mutex<Option<HWND>> = Mutex::new(None);
+        let mut window = WINDOW.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(hwnd) = *window {
+            return Ok(hwnd);
+        }
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("everything-sdk-reply".to_string())
+            .spawn(move || message_loop(sender))
+            .map_err(|err| {
+                warn!("failed to spawn the message loop thread: {err}");
+                EverythingError::CreateThread
+            })?;
+        let hwnd = match receiver.recv() {
+            Ok(Ok(hwnd)) => hwnd,
+            Ok(Err(err)) => {
+                warn!("failed to create the reply window: {err}");
+                return Err(EverythingError::CreateWindow);
+            }
+            Err(_) => {
+                warn!("the message loop thread exited early");
+                return Err(EverythingError::CreateWindow);
+            }
+        };
+        *window = Some(hwnd);
+        Ok(hwnd)
+    }
+
+    fn message_loop(sender: mpsc::Sender<windows::core::Result<HWND>>) {
+        debug!("message loop start");
+        let hwnd = create_window();
+        let created = hwnd.is_ok();
+        let _ = sender.send(hwnd);
+        if !created {
+            return;
+        }
+        let mut msg = MSG::default();
+        // The WM_COPYDATA sent by Everything is dispatched to the wndproc inside GetMessageW.
+        while unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 > 0 {
+            unsafe { DispatchMessageW(&msg) };
+        }
+        debug!("message loop end");
+    }
+
+    extern "system" fn wndproc(
+        hwnd: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        // Unwinding out of an `extern "system"` callback is UB, and the errors are sent to the
+        // futures through the shared state anyway.
+        let handled = std::panic::catch_unwind(|| handle_message(hwnd, message, wparam, lparam));
+        handled.unwrap_or_else(|_| {
+            debug!("[wndproc] panicked at WM_{:#06x} ({})", message, message);
+            LRESULT(0)
+        })
+    }
+
+    fn handle_message(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        unsafe {
+            match message {
+                WM_COPYDATA => {
+                    let Some((reply_id, data)) = copydata_of(lparam) else {
+                        return LRESULT(0);
+                    };
+                    let Some(pending) = take_pending(reply_id) else {
+                        debug!("[wndproc] discard the late reply {}", reply_id);
+                        return LRESULT(1);
+                    };
+                    let _ = KillTimer(hwnd, reply_id as usize);
+                    match pending.decode {
+                        Some(version) => {
+                            debug!("[wndproc] decode the reply {}", reply_id);
+                            let reply = parse_query_reply(data, version);
+                            pending.shared.finish(Outcome::Decoded(reply));
+                            LRESULT(1)
+                        }
+                        None if raw::Everything_IsQueryReply(
+                            &access(),
+                            message,
+                            wparam,
+                            lparam,
+                            reply_id,
+                        ) =>
+                        {
+                            debug!("[wndproc] Everything_IsQueryReply() -> YEEEESSSSSS!! (So copy done and wake the future)");
+                            // Signal that the Query has completed.
+                            pending.shared.finish(Outcome::Copied);
+                            LRESULT(1)
+                        }
+                        None => {
+                            debug!("[wndproc] Everything_IsQueryReply() -> NOOOO!!");
+                            pending.shared.finish(Outcome::Failed);
+                            LRESULT(0)
+                        }
+                    }
+                }
+                WM_USER_SET_QUERY_TIMEOUT => {
+                    SetTimer(hwnd, wparam.0, lparam.0 as u32, None);
+                    LRESULT(0)
+                }
+                WM_TIMER => {
+                    let _ = KillTimer(hwnd, wparam.0);
+                    if let Some(pending) = take_pending(wparam.0 as u32) {
+                        debug!("[wndproc] the query {} timed out", wparam.0);
+                        pending.shared.finish(Outcome::TimedOut);
+                    }
+                    LRESULT(0)
+                }
+                _ => {
+                    debug!(
+                        "[wndproc] DefWindowProcW( msg => WM_{:#06x} ({}) )",
+                        message, message
+                    );
+                    DefWindowProcW(hwnd, message, wparam, lparam)
+                }
+            }
+        }
+    }
+
+    fn create_window() -> windows::core::Result<HWND> {
+        unsafe {
+            let instance: HINSTANCE = GetModuleHandleW(None)?.into();
+            if instance.is_invalid() {
+                return Err(windows::core::Error::from_win32());
+            }
+
+            let window_class_name = w!("EVERYTHING_SDK_RUST");
+
+            let mut wc = WNDCLASSEXW {
+                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+                hInstance: instance,
+                lpszClassName: window_class_name,
+                lpfnWndProc: Some(wndproc),
+                ..Default::default()
+            };
+
+            if GetClassInfoExW(instance, window_class_name, &mut wc).is_err() {
+                let atom = RegisterClassExW(&wc);
+                if atom == 0 {
+                    return Err(windows::core::Error::from_win32());
+                }
+            }
+
+            let hwnd = CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                window_class_name,
+                w!("The window for async query in everything-sdk-rs crate"),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                // Ref: https://devblogs.microsoft.com/oldnewthing/20171218-00/?p=97595
+                HWND_MESSAGE,
+                None,
+                instance,
+                None,
+            );
+
+            if hwnd == HWND(0) {
+                return Err(windows::core::Error::from_win32());
+            }
+
+            Ok(hwnd)
+        }
+    }
+}
+
+#[non_exhaustive]
+pub struct EverythingResults<'a> {
+    scratch: &'a Mutex<Vec<u16>>,
+}
+
+impl<'a> Drop for EverythingResults<'a> {
+    fn drop(&mut self) {
+        // I want to free memory for the results, but no api just for it.
+        // and should not call [`raw::Everything_Reset`], for long live reuse EverythingSearcher.
+        debug!("[Drop] EverythingResults is dropped!");
+    }
+}
+
+impl<'a> EverythingResults<'a> {
+    /// the results logic length, for available index in iterator.
+    pub fn len(&self) -> u32 {
+        self.num()
+    }
+
+    /// No visible results. (there may be some results if offset or max is set)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn at(&self, index: u32) -> Option<EverythingItem<'a>> {
+        self.iter().nth(index as usize)
+    }
+
+    /// The first visible result, the same as `self.at(0)`.
+    pub fn first(&self) -> Option<EverythingItem<'a>> {
+        self.at(0)
+    }
+
+    /// The last visible result, the same as `self.at(self.len() - 1)` if not empty.
+    pub fn last(&self) -> Option<EverythingItem<'a>> {
+        self.len().checked_sub(1).and_then(|index| self.at(index))
+    }
+
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            next_index: 0,
+            length: self.len(),
+            request_flags: self.request_flags(),
+            scratch: self.scratch,
+        }
+    }
+
+    /// Iterate the visible results but skip the hidden or system ones, like the Explorer does
+    /// by default. It needs the `EVERYTHING_REQUEST_ATTRIBUTES` flag.
+    ///
+    /// To skip them in the search instead, see [`query::Expr::skip_hidden_system`].
+    pub fn skip_hidden_system(&self) -> Result<impl Iterator<Item = EverythingItem<'a>>> {
+        let flags = RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES;
+        if !self.request_flags().contains(flags) {
+            return Err(InvalidRequestError::RequestFlagsNotSet(flags).into());
+        }
+        let hidden_or_system = FileAttributes::HIDDEN | FileAttributes::SYSTEM;
+        Ok(self.iter().filter(move |item| {
+            item.attributes()
+                .is_ok_and(|attr| !attr.intersects(hidden_or_system))
+        }))
+    }
+
+    pub fn request_flags(&self) -> RequestFlags {
+        raw::Everything_GetResultListRequestFlags(&access())
+    }
+
+    pub fn sort_type(&self) -> SortType {
+        raw::Everything_GetResultListSort(&access())
+    }
+
+    fn is_query_version_2(&self) -> bool {
+        helper::should_use_query_version_2(self.request_flags(), self.sort_type())
+    }
+
+    pub fn num_files(&self) -> Result<u32> {
+        if self.is_query_version_2() {
+            Err(EverythingError::UnsupportedInQueryVersion2)
+        } else {
+            let num = raw::Everything_GetNumFileResults(&access());
+            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+        }
+    }
+
+    pub fn num_folders(&self) -> Result<u32> {
+        if self.is_query_version_2() {
+            Err(EverythingError::UnsupportedInQueryVersion2)
+        } else {
+            let num = raw::Everything_GetNumFolderResults(&access());
+            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+        }
+    }
+
+    /// the number of visible file and folder results.
+    pub fn num(&self) -> u32 {
+        let num = raw::Everything_GetNumResults(&access());
+        num // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+    }
+
+    pub fn total_files(&self) -> Result<u32> {
+        if self.is_query_version_2() {
+            Err(EverythingError::UnsupportedInQueryVersion2)
+        } else {
+            let num = raw::Everything_GetTotFileResults(&access());
+            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+        }
+    }
+
+    pub fn total_folders(&self) -> Result<u32> {
+        if self.is_query_version_2() {
+            Err(EverythingError::UnsupportedInQueryVersion2)
+        } else {
+            let num = raw::Everything_GetTotFolderResults(&access());
+            Ok(num) // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        let total = raw::Everything_GetTotResults(&access());
+        total // would not be error (EVERYTHING_ERROR_INVALIDCALL), zero is valid.
+    }
+}
+
+#[non_exhaustive]
+pub struct EverythingItem<'a> {
+    index: u32,
+    request_flags: RequestFlags,
+    scratch: &'a Mutex<Vec<u16>>,
+}
+
+/// The iterator over the visible results, which is just indices and flags, so it is cheap to clone.
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct Iter<'a> {
+    next_index: u32,
+    length: u32,
+    request_flags: RequestFlags,
+    scratch: &'a Mutex<Vec<u16>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = EverythingItem<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_index < self.length {
+            let index = self.next_index;
+            self.next_index += 1;
+            Some(EverythingItem {
+                index,
+                request_flags: self.request_flags,
+                scratch: self.scratch,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = usize::try_from(self.length - self.next_index).unwrap();
+        (rest, Some(rest))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = self.next_index + u32::try_from(n).unwrap();
+        if index < self.length {
+            self.next_index = index + 1;
+            Some(EverythingItem {
+                index,
+                request_flags: self.request_flags,
+                scratch: self.scratch,
+            })
+        } else {
+            self.next_index = self.length;
+            None
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next_index < self.length {
+            self.length -= 1;
+            Some(EverythingItem {
+                index: self.length,
+                request_flags: self.request_flags,
+                scratch: self.scratch,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let rest = self.length - self.next_index;
+        match u32::try_from(n) {
+            Ok(n) if n < rest => {
+                self.length -= n + 1;
+                Some(EverythingItem {
+                    index: self.length,
+                    request_flags: self.request_flags,
+                    scratch: self.scratch,
+                })
+            }
+            _ => {
+                self.length = self.next_index;
+                None
+            }
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> IntoIterator for EverythingResults<'a> {
+    type Item = EverythingItem<'a>;
+    type IntoIter = Iter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            next_index: 0,
+            length: self.len(),
+            request_flags: self.request_flags(),
+            scratch: self.scratch,
+        }
+    }
+}
+
+impl<'a> EverythingItem<'a> {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn is_volume(&self) -> bool {
+        raw::Everything_IsVolumeResult(&access(), self.index)
+    }
+
+    pub fn is_folder(&self) -> bool {
+        raw::Everything_IsFolderResult(&access(), self.index)
+    }
+
+    pub fn is_file(&self) -> bool {
+        raw::Everything_IsFileResult(&access(), self.index)
+    }
+
+    pub fn filename(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FILE_NAME)?;
+        Ok(raw::Everything_GetResultFileName(&access(), self.index).unwrap())
+    }
+
+    pub fn path(&self) -> Result<PathBuf> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_PATH)?;
+        Ok(raw::Everything_GetResultPath(&access(), self.index)
+            .unwrap()
+            .into())
+    }
+
+    /// The parent folder of this item, which is just the path part of the result.
+    ///
+    /// Similar to [`Path::parent`], but it is an empty path for the volume (like `C:`) instead
+    /// of `None`. No need more flags than `EVERYTHING_REQUEST_PATH`.
+    pub fn parent(&self) -> Result<PathBuf> {
+        self.path()
+    }
+
+    /// The folders containing this item, from the parent up to the volume (like `C:\`), with
+    /// only one FFI call for the path part. Empty for the volume.
+    ///
+    /// The volume is always the last one with the root, even for the items directly in it, whose
+    /// parent is the bare `C:` (see [`EverythingItem::parent`]).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("desktop.ini");
+    /// for item in searcher.query() {
+    ///     let git_repo = item.ancestors()?.find(|folder| folder.join(".git").exists());
+    ///     println!("{:?}", git_repo);
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn ancestors(&self) -> Result<impl Iterator<Item = PathBuf>> {
+        let parent = Some(self.path()?)
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(helper::with_root_dir);
+        Ok(std::iter::successors(parent, |path| {
+            path.parent().map(Path::to_path_buf)
+        }))
+    }
+
+    /// How deep this item is under the `root` folder, 1 for its direct children, or `None` if
+    /// it is not under the `root`.
+    ///
+    /// The paths are compared component by component, and ASCII case-insensitively like the
+    /// file system, so `c:/users` is the same root as `C:\Users\`. It only needs
+    /// `EVERYTHING_REQUEST_PATH`.
+    pub fn depth(&self, root: impl AsRef<Path>) -> Result<Option<usize>> {
+        // `C:` is the path of the items in `C:\`, so skip the root dirs to match the both.
+        fn not_root_dir(component: &Component<'_>) -> bool {
+            !matches!(component, Component::RootDir)
+        }
+        let path = self.path()?;
+        let mut components = path.components().filter(not_root_dir);
+        for root in root.as_ref().components().filter(not_root_dir) {
+            match components.next() {
+                Some(component) if component.as_os_str().eq_ignore_ascii_case(root) => {}
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(components.count() + 1))
+    }
+
+    /// The file name without the final extension, the same as [`Path::file_stem`] on the
+    /// file name part. (e.g. `"foo.tar"` for `"foo.tar.gz"`, `".bashrc"` for `".bashrc"`)
+    pub fn file_stem(&self) -> Result<OsString> {
+        let filename = self.filename()?;
+        Ok(Path::new(&filename)
+            .file_stem()
+            .map(OsStr::to_os_string)
+            .unwrap_or_default())
+    }
+
+    /// The final extension of the file name part, or an empty string if no extension, the
+    /// same as [`Path::extension`].
+    ///
+    /// Different from [`EverythingItem::extension`], it does not need the
+    /// `EVERYTHING_REQUEST_EXTENSION` flag, only `EVERYTHING_REQUEST_FILE_NAME`.
+    pub fn extension_or_empty(&self) -> Result<OsString> {
+        let filename = self.filename()?;
+        Ok(Path::new(&filename)
+            .extension()
+            .map(OsStr::to_os_string)
+            .unwrap_or_default())
+    }
+
+    /// A convenient function to get the full path by Everything_GetResultFullPathName.
+    ///
+    /// Different from the [`full_path_name`], this is an unofficial function provided for
+    /// the special case. (We can use [`raw::Everything_GetResultFullPathName`] with the
+    /// two default flags EVERYTHING_REQUEST_PATH and EVERYTHING_REQUEST_FILE_NAME)
+    pub fn filepath(&self) -> Result<PathBuf> {
+        // A bit weird but this is a special case in the official documentation.
+        self.need_flags_set(
+            RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+        )?;
+        let buf_len = u32::from(
+            raw::Everything_GetResultFullPathNameSizeHint(&access(), self.index).unwrap(),
+        );
+        let path = with_full_path(self.scratch, self.index, buf_len as usize, |wide| {
+            assert_eq!(buf_len as usize, wide.len() + 1);
+            OsString::from_wide(wide).into()
+        });
+        Ok(path.unwrap())
+    }
+
+    /// Get the full path name, can be with len limit if you need.
+    ///
+    /// Similar to x.path().join(x.filename()) if parent path is NOT drive root (like C:).
+    /// (Ref: <https://github.com/nodejs/node/issues/14405>)
+    ///
+    /// Buf if the pathname is too long, you can choose to cut off the tail, reduce the
+    /// memory consumption, or limit the max size of buffer memory allocation.
+    pub fn full_path_name(&self, max_len: Option<u32>) -> Result<PathBuf> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)?;
+        let size_hint = u32::from(
+            raw::Everything_GetResultFullPathNameSizeHint(&access(), self.index).unwrap(),
+        );
+        let buf_len = std::cmp::min(size_hint, max_len.unwrap_or(u32::MAX)) as usize;
+        let path = with_full_path(self.scratch, self.index, buf_len, |wide| {
+            assert_eq!(size_hint as usize, wide.len() + 1);
+            OsString::from_wide(wide).into()
+        });
+        Ok(path.unwrap())
+    }
+
+    // Check if the corresponding flags are set. (usually just check a single flag)
+    fn need_flags_set(&self, flags: RequestFlags) -> Result<()> {
+        if self.request_flags.contains(flags) {
+            Ok(())
+        } else {
+            Err(EverythingError::InvalidRequest(
+                InvalidRequestError::RequestFlagsNotSet(flags),
+            ))
+        }
+    }
+
+    pub fn extension(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_EXTENSION)?;
+        Ok(raw::Everything_GetResultExtension(&access(), self.index).unwrap())
+    }
+
+    /// The file name borrowed from the results, without copying, for the hot loops.
+    ///
+    /// It is valid as long as the results, since the next query or reset needs the searcher
+    /// borrowed by the results.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.rs");
+    /// let results = searcher.query();
+    /// let long_names = results
+    ///     .iter()
+    ///     .filter(|item| item.filename_ref().is_ok_and(|name| name.len() > 32))
+    ///     .count();
+    /// ```
+    pub fn filename_ref(&self) -> Result<&'a U16CStr> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FILE_NAME)?;
+        // SAFETY: the flag is set, and the string lives until the next query or reset.
+        Ok(unsafe { raw::Everything_GetResultFileNameRef(&access(), self.index) }.unwrap())
+    }
+
+    /// The path borrowed from the results, see [`EverythingItem::filename_ref`].
+    pub fn path_ref(&self) -> Result<&'a U16CStr> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_PATH)?;
+        // SAFETY: the same as `filename_ref`.
+        Ok(unsafe { raw::Everything_GetResultPathRef(&access(), self.index) }.unwrap())
+    }
+
+    /// The extension borrowed from the results, see [`EverythingItem::filename_ref`].
+    pub fn extension_ref(&self) -> Result<&'a U16CStr> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_EXTENSION)?;
+        // SAFETY: the same as `filename_ref`.
+        Ok(unsafe { raw::Everything_GetResultExtensionRef(&access(), self.index) }.unwrap())
+    }
+
+    pub fn size(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_SIZE)?;
+        let file_size = raw::Everything_GetResultSize(&access(), self.index).unwrap();
+        // If request flag `RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES` is not set, the GetResultSize function
+        // will success, but the file_size for folder will be Some(-1). If the ATTRIBUTES flag is set. the
+        // GetResultSize will success too, but the file_size for folder will be Some(0).
+        //
+        // There is no relevant explanation in the documentation about that. (so wired, maybe we do not know
+        // whether this index points to a file or a directory unless we have ATTRIBUTES.)
+        //
+        // So for consistency, we will get Ok(0) for folder index regardless of whether the request flag
+        // `RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES` had been set.
+        u64::try_from(file_size).or_else(|_e| {
+            if raw::Everything_IsFolderResult(&access(), self.index) {
+                debug_assert_eq!(file_size, -1); // file_size will most likely be -1
+                Ok(0)
+            } else {
+                panic!(
+                    "file size should not be a negative integer => {}",
+                    file_size
+                )
+            }
+        })
+    }
+
+    pub fn date_created(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)?;
+        Ok(raw::Everything_GetResultDateCreated(&access(), self.index).unwrap())
+    }
+
+    pub fn date_modified(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)?;
+        Ok(raw::Everything_GetResultDateModified(&access(), self.index).unwrap())
+    }
+
+    pub fn date_accessed(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED)?;
+        Ok(raw::Everything_GetResultDateAccessed(&access(), self.index).unwrap())
+    }
+
+    pub fn attributes(&self) -> Result<FileAttributes> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES)?;
+        Ok(raw::Everything_GetResultAttributes(&access(), self.index).unwrap())
+    }
+
+    /// Whether the item is a reparse point (symbolic link, junction, mount point, cloud file
+    /// placeholder and so on), by the `FILE_ATTRIBUTE_REPARSE_POINT` in the attributes.
+    pub fn is_reparse_point(&self) -> Result<bool> {
+        Ok(self.attributes()?.is_reparse_point())
+    }
+
+    /// Whether the item is a symbolic link or a junction, which should be skipped when you
+    /// walk or sum up a folder tree, otherwise the linked tree is counted twice.
+    ///
+    /// Without `confirm_on_disk`, it is the same as [`EverythingItem::is_reparse_point`]
+    /// by the index, which may be stale and also includes the other kinds of reparse point.
+    /// With `confirm_on_disk`, the reparse points are checked again by
+    /// [`std::fs::symlink_metadata`] (fall back to the index if it failed to read the disk).
+    pub fn is_symlink(&self, confirm_on_disk: bool) -> Result<bool> {
+        let is_reparse_point = self.is_reparse_point()?;
+        if !is_reparse_point || !confirm_on_disk {
+            return Ok(is_reparse_point);
+        }
+        let path = self.filepath()?;
+        Ok(std::fs::symlink_metadata(path).map_or(true, |meta| meta.file_type().is_symlink()))
+    }
+
+    pub fn file_list_filename(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME)?;
+        Ok(raw::Everything_GetResultFileListFileName(&access(), self.index).unwrap())
+    }
+
+    pub fn run_count(&self) -> Result<u32> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT)?;
+        Ok(raw::Everything_GetResultRunCount(&access(), self.index))
+    }
+
+    /// The time elapsed since the item was created.
+    pub fn created_age(&self) -> Result<Duration> {
+        Ok(helper::filetime_age(self.date_created()?))
+    }
+
+    /// The time elapsed since the item was modified, e.g. for "files older than 30 days".
+    pub fn modified_age(&self) -> Result<Duration> {
+        Ok(helper::filetime_age(self.date_modified()?))
+    }
+
+    /// The time elapsed since the item was accessed.
+    pub fn accessed_age(&self) -> Result<Duration> {
+        Ok(helper::filetime_age(self.date_accessed()?))
+    }
+
+    pub fn date_run(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_RUN)?;
+        Ok(raw::Everything_GetResultDateRun(&access(), self.index).unwrap())
+    }
+
+    pub fn date_recently_changed(&self) -> Result<u64> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED)?;
+        Ok(raw::Everything_GetResultDateRecentlyChanged(&access(), self.index).unwrap())
+    }
+
+    pub fn highlighted_filename(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME)?;
+        Ok(raw::Everything_GetResultHighlightedFileName(&access(), self.index).unwrap())
+    }
+
+    pub fn highlighted_path(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_PATH)?;
+        Ok(raw::Everything_GetResultHighlightedPath(&access(), self.index).unwrap())
+    }
+
+    pub fn highlighted_full_path_and_filename(&self) -> Result<OsString> {
+        self.need_flags_set(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME)?;
+        Ok(raw::Everything_GetResultHighlightedFullPathAndFileName(&access(), self.index).unwrap())
+    }
+
+    // --- Run history ---
+
+    /// The full path for the run count functions, by whichever of the path flags is set.
+    fn run_path(&self) -> Result<PathBuf> {
+        if self
+            .request_flags
+            .contains(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+        {
+            self.full_path_name(None)
+        } else {
+            self.filepath()
+        }
+    }
+
+    /// Increment the run count of this item by one, and return the new run count.
+    ///
+    /// It needs `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`, or both of
+    /// `EVERYTHING_REQUEST_PATH` and `EVERYTHING_REQUEST_FILE_NAME`, and fails with
+    /// [`EverythingError::Unsupported`] before Everything 1.4.1, like
+    /// [`EverythingGlobal::inc_run_count`]. The results are not changed, query again to see the
+    /// new run count.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("notepad.exe");
+    /// if let Some(item) = searcher.query().iter().next() {
+    ///     item.inc_run_count()?; // the user launched it from our UI
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn inc_run_count(&self) -> Result<u32> {
+        feature::require(Feature::RunHistory)?;
+        let path = self.run_path()?;
+        raw::Everything_IncRunCountFromFileName(&access(), &path)
+            .map(|n| n.get())
+            .map_err(Into::into)
+    }
+
+    /// Set the run count of this item, see [`EverythingItem::inc_run_count`].
+    pub fn set_run_count(&self, run_count: u32) -> Result<()> {
+        feature::require(Feature::RunHistory)?;
+        let path = self.run_path()?;
+        if raw::Everything_SetRunCountFromFileName(&access(), &path, run_count) {
+            Ok(())
+        } else {
+            Err(EverythingError::Ipc)
+        }
+    }
+
+    /// Remove this item from the run history, that is the run count set to zero.
+    ///
+    /// Use [`EverythingGlobal::delete_run_history`] for all the items.
+    pub fn clear_run_history(&self) -> Result<()> {
+        self.set_run_count(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_root_dir() {
+        let root = |path: &str| helper::with_root_dir(PathBuf::from(path));
+        assert_eq!(root("C:"), Path::new("C:\\"));
+        assert_eq!(root("C:\\"), Path::new("C:\\"));
+        assert_eq!(root("C:\\Windows"), Path::new("C:\\Windows"));
+    }
+
+    #[test]
+    fn ancestors_end_at_the_same_root() {
+        let ancestors = |path: &str| {
+            std::iter::successors(Some(helper::with_root_dir(PathBuf::from(path))), |path| {
+                path.parent().map(Path::to_path_buf)
+            })
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(ancestors("C:"), [PathBuf::from("C:\\")]);
+        assert_eq!(
+            ancestors("C:\\Windows"),
+            [PathBuf::from("C:\\Windows"), PathBuf::from("C:\\")]
+        );
+    }
+}
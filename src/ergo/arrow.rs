@@ -0,0 +1,247 @@
+//! Export the results to [Apache Arrow](https://arrow.apache.org), for Polars, DataFusion and
+//! the other data-analysis tools over Arrow. (feature `arrow`)
+//!
+//! Each column of [`ItemData`] requested is an Arrow column of the same name, where the names
+//! and the paths are in UTF-8 (converted lossily from UTF-16), and the dates are the UTC
+//! timestamps in microseconds. The unknown values are nulls.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut options = SearchOptions::new("ext:mp4");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+//!     | RequestFlags::EVERYTHING_REQUEST_SIZE
+//!     | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED;
+//! let batch = spawn_query(options).join().unwrap()?.to_record_batch();
+//! println!("{:?}", batch.schema());
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::ffi::OsStr;
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, RecordBatch, StringArray, TimestampMicrosecondArray, UInt32Array, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+
+use super::{FileTime, ItemData, OwnedResults, RequestFlags};
+
+/// The time zone of the timestamp columns.
+const UTC: &str = "UTC";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Column {
+    Kind,
+    Filename,
+    Path,
+    FullPath,
+    Extension,
+    Size,
+    DateCreated,
+    DateModified,
+    DateAccessed,
+    Attributes,
+    FileListFilename,
+    RunCount,
+    DateRun,
+    DateRecentlyChanged,
+}
+
+impl Column {
+    /// The `kind` column, and the columns of the data available by the `flags`.
+    fn from_request_flags(flags: RequestFlags) -> Vec<Column> {
+        let has_full_path = flags
+            .contains(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+            || flags.contains(
+                RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            );
+        [
+            (Column::Kind, true),
+            (
+                Column::Filename,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_FILE_NAME),
+            ),
+            (
+                Column::Path,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_PATH),
+            ),
+            (Column::FullPath, has_full_path),
+            (
+                Column::Extension,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_EXTENSION),
+            ),
+            (
+                Column::Size,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_SIZE),
+            ),
+            (
+                Column::DateCreated,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED),
+            ),
+            (
+                Column::DateModified,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED),
+            ),
+            (
+                Column::DateAccessed,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED),
+            ),
+            (
+                Column::Attributes,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES),
+            ),
+            (
+                Column::FileListFilename,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME),
+            ),
+            (
+                Column::RunCount,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT),
+            ),
+            (
+                Column::DateRun,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_RUN),
+            ),
+            (
+                Column::DateRecentlyChanged,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(column, available)| available.then_some(column))
+        .collect()
+    }
+
+    /// The same as the field name of [`ItemData`].
+    fn name(self) -> &'static str {
+        match self {
+            Column::Kind => "kind",
+            Column::Filename => "filename",
+            Column::Path => "path",
+            Column::FullPath => "full_path",
+            Column::Extension => "extension",
+            Column::Size => "size",
+            Column::DateCreated => "date_created",
+            Column::DateModified => "date_modified",
+            Column::DateAccessed => "date_accessed",
+            Column::Attributes => "attributes",
+            Column::FileListFilename => "file_list_filename",
+            Column::RunCount => "run_count",
+            Column::DateRun => "date_run",
+            Column::DateRecentlyChanged => "date_recently_changed",
+        }
+    }
+
+    fn field(self) -> Field {
+        let data_type = match self {
+            Column::Kind
+            | Column::Filename
+            | Column::Path
+            | Column::FullPath
+            | Column::Extension
+            | Column::FileListFilename => DataType::Utf8,
+            Column::Size => DataType::UInt64,
+            Column::Attributes | Column::RunCount => DataType::UInt32,
+            Column::DateCreated
+            | Column::DateModified
+            | Column::DateAccessed
+            | Column::DateRun
+            | Column::DateRecentlyChanged => {
+                DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+            }
+        };
+        // `kind` is always known.
+        Field::new(self.name(), data_type, self != Column::Kind)
+    }
+
+    fn to_array(self, items: &[ItemData]) -> ArrayRef {
+        match self {
+            Column::Kind => Arc::new(
+                items
+                    .iter()
+                    .map(|data| Some(kind_name(data)))
+                    .collect::<StringArray>(),
+            ),
+            Column::Filename => strings(items, |data| data.filename.as_deref()),
+            Column::Path => strings(items, |data| data.path.as_deref().map(OsStr::new)),
+            Column::FullPath => strings(items, |data| data.full_path.as_deref().map(OsStr::new)),
+            Column::Extension => strings(items, |data| data.extension.as_deref()),
+            Column::FileListFilename => strings(items, |data| data.file_list_filename.as_deref()),
+            Column::Size => Arc::new(items.iter().map(|data| data.size).collect::<UInt64Array>()),
+            Column::Attributes => Arc::new(
+                items
+                    .iter()
+                    .map(|data| data.attributes)
+                    .collect::<UInt32Array>(),
+            ),
+            Column::RunCount => Arc::new(
+                items
+                    .iter()
+                    .map(|data| data.run_count)
+                    .collect::<UInt32Array>(),
+            ),
+            Column::DateCreated => timestamps(items, |data| data.date_created),
+            Column::DateModified => timestamps(items, |data| data.date_modified),
+            Column::DateAccessed => timestamps(items, |data| data.date_accessed),
+            Column::DateRun => timestamps(items, |data| data.date_run),
+            Column::DateRecentlyChanged => timestamps(items, |data| data.date_recently_changed),
+        }
+    }
+}
+
+fn kind_name(data: &ItemData) -> &'static str {
+    if data.is_volume() {
+        "volume"
+    } else if data.is_folder() {
+        "folder"
+    } else {
+        "file"
+    }
+}
+
+fn strings<'a>(items: &'a [ItemData], get: impl Fn(&'a ItemData) -> Option<&'a OsStr>) -> ArrayRef {
+    let array: StringArray = items
+        .iter()
+        .map(|data| get(data).map(OsStr::to_string_lossy))
+        .collect();
+    Arc::new(array)
+}
+
+fn timestamps(items: &[ItemData], get: impl Fn(&ItemData) -> Option<u64>) -> ArrayRef {
+    let array: TimestampMicrosecondArray = items
+        .iter()
+        .map(|data| get(data).and_then(to_unix_micros))
+        .collect();
+    Arc::new(array.with_timezone(UTC))
+}
+
+/// `None` if the date is unknown (`u64::MAX`).
+fn to_unix_micros(filetime: u64) -> Option<i64> {
+    // never overflow, the u64::MAX intervals is less than 2^61 microseconds.
+    (filetime != u64::MAX).then(|| FileTime::new(filetime).to_unix_nanos().div_euclid(1000) as i64)
+}
+
+/// The Arrow schema of the results of the `flags`, the same as
+/// [`OwnedResults::to_record_batch`] makes.
+pub fn schema(flags: RequestFlags) -> SchemaRef {
+    let fields: Vec<Field> = Column::from_request_flags(flags)
+        .into_iter()
+        .map(Column::field)
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+impl OwnedResults {
+    /// Convert the items into an Arrow record batch, with the columns of [`schema`] by the
+    /// request flags of the results. (feature `arrow`)
+    pub fn to_record_batch(&self) -> RecordBatch {
+        let columns = Column::from_request_flags(self.request_flags());
+        let arrays = columns
+            .iter()
+            .map(|column| column.to_array(&self.items))
+            .collect();
+        RecordBatch::try_new(schema(self.request_flags()), arrays)
+            .expect("the arrays are built by the schema")
+    }
+}
@@ -0,0 +1,126 @@
+//! The search backends behind one interface, to present the results everywhere.
+//!
+//! Everything is not installed (or not running) on every machine. The apps can pick the
+//! backend by [`preferred`], which prefers Everything and falls back to Windows Search (the
+//! `windows-search` feature), and then search by the same [`SearchOptions`].
+//!
+//! The apps built on the trait can be tested without Everything by [`MockBackend`].
+
+use super::{global, OwnedResults, RequestFlags, Result, SearchOptions};
+
+#[cfg(feature = "etp")]
+mod etp;
+#[cfg(feature = "etp")]
+pub use etp::{EtpBackend, DEFAULT_PORT};
+mod mock;
+pub use mock::MockBackend;
+#[cfg(feature = "windows-search")]
+mod windows_search;
+#[cfg(feature = "windows-search")]
+pub use windows_search::WindowsSearchBackend;
+
+/// A search engine answering the [`SearchOptions`] with a snapshot of the results.
+pub trait SearchBackend: Send + Sync {
+    /// The name for the logs and the UIs, like `Everything`.
+    fn name(&self) -> &str;
+
+    /// Whether it can search now.
+    fn is_available(&self) -> bool;
+
+    /// Search by the `options`, the backend may support only part of them, see its docs.
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults>;
+
+    /// Which part of the [`SearchOptions`] it supports, all of them by default.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::all()
+    }
+}
+
+/// The part of the [`SearchOptions`] supported by a backend, the others are ignored.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    /// The request flags which can be answered.
+    pub request_flags: RequestFlags,
+    pub match_path: bool,
+    pub match_case: bool,
+    pub match_whole_word: bool,
+    pub regex: bool,
+    /// Whether the sort is applied, some of the sorts at least.
+    pub sort: bool,
+    /// Whether the search text is in the Everything search syntax, like `ext:` and `size:`.
+    pub everything_syntax: bool,
+}
+
+impl Capabilities {
+    /// Everything supports all.
+    pub const fn all() -> Self {
+        Capabilities {
+            request_flags: RequestFlags::all(),
+            match_path: true,
+            match_case: true,
+            match_whole_word: true,
+            regex: true,
+            sort: true,
+            everything_syntax: true,
+        }
+    }
+
+    /// The request flags in `options` which can NOT be answered.
+    pub fn unsupported_flags(&self, options: &SearchOptions) -> RequestFlags {
+        options.request_flags - self.request_flags
+    }
+
+    /// Whether all the `options` are supported, that is the results are the same as Everything.
+    pub fn supports(&self, options: &SearchOptions) -> bool {
+        self.unsupported_flags(options).is_empty()
+            && (self.match_path || !options.match_path)
+            && (self.match_case || !options.match_case)
+            && (self.match_whole_word || !options.match_whole_word)
+            && (self.regex || !options.regex)
+            && (self.sort || options.sort == Default::default())
+    }
+}
+
+/// Everything over IPC, which supports all the [`SearchOptions`].
+///
+/// It takes the [`global`] lock for each search, so do NOT call it while holding the lock.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct EverythingBackend;
+
+impl SearchBackend for EverythingBackend {
+    fn name(&self) -> &str {
+        "Everything"
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn is_available(&self) -> bool {
+        let everything = global()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        everything.is_db_loaded().unwrap_or(false)
+    }
+
+    #[cfg(feature = "async")]
+    fn is_available(&self) -> bool {
+        futures::executor::block_on(async {
+            let everything = global().lock().await;
+            everything.is_db_loaded().unwrap_or(false)
+        })
+    }
+
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        options.query_snapshot()
+    }
+}
+
+/// The first available backend, in order: Everything, and then Windows Search (with the
+/// `windows-search` feature). `None` if none of them is available.
+pub fn preferred() -> Option<Box<dyn SearchBackend>> {
+    let backends: Vec<Box<dyn SearchBackend>> = vec![
+        Box::new(EverythingBackend),
+        #[cfg(feature = "windows-search")]
+        Box::new(WindowsSearchBackend::new()),
+    ];
+    backends.into_iter().find(|backend| backend.is_available())
+}
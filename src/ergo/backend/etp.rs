@@ -0,0 +1,343 @@
+//! Everything on another machine over ETP, the FTP with the `EVERYTHING` commands. (feature
+//! `etp`)
+//!
+//! Enable the ETP server in Everything (Tools > Options > ETP/FTP Server) on the remote machine.
+//! The server answers the queries in UTF-8 text lines, such as `RESULT_COUNT 2`, `PATH C:\`,
+//! `FILE a.txt` and `SIZE 10`, ended by `200 End.`.
+
+use std::ffi::OsString;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::{Capabilities, SearchBackend};
+use crate::{
+    EverythingError, ItemData, ItemKind, OwnedResults, RequestFlags, Result, SearchOptions,
+};
+
+/// The default port of the ETP server, the same as FTP.
+pub const DEFAULT_PORT: u16 = 21;
+
+/// The request flags answered by ETP, and the column command of each one.
+const COLUMNS: [(RequestFlags, &str); 7] = [
+    (RequestFlags::EVERYTHING_REQUEST_SIZE, "SIZE_COLUMN"),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_CREATED,
+        "DATE_CREATED_COLUMN",
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED,
+        "DATE_MODIFIED_COLUMN",
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+        "ATTRIBUTES_COLUMN",
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME,
+        "FILE_LIST_FILENAME_COLUMN",
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_RUN_COUNT,
+        "RUN_COUNT_COLUMN",
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED,
+        "DATE_RECENTLY_CHANGED_COLUMN",
+    ),
+];
+
+/// A remote Everything over ETP, it connects and logs in for each search.
+///
+/// The file name, path, full path and extension are always available, and the others in
+/// [`EtpBackend::SUPPORTED_FLAGS`] if requested. All the search options are supported.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::backend::{EtpBackend, SearchBackend};
+/// use everything_sdk::*;
+///
+/// let remote = EtpBackend::new("192.168.1.10:21").login("user", "secret");
+/// let results = remote.search(&SearchOptions::new("*.mkv"))?;
+/// for item in results.items() {
+///     println!("{:?}", item.full_path);
+/// }
+/// # Ok::<(), EverythingError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EtpBackend {
+    addr: String,
+    user: String,
+    password: String,
+    timeout: Duration,
+}
+
+impl EtpBackend {
+    /// The request flags answered by ETP.
+    pub const SUPPORTED_FLAGS: RequestFlags = RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+        .union(RequestFlags::EVERYTHING_REQUEST_PATH)
+        .union(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+        .union(RequestFlags::EVERYTHING_REQUEST_EXTENSION)
+        .union(RequestFlags::EVERYTHING_REQUEST_SIZE)
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)
+        .union(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES)
+        .union(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME)
+        .union(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT)
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED);
+
+    /// The server at `addr` like `host:port` (or `host` for [`DEFAULT_PORT`]), logged in as
+    /// `anonymous` by default.
+    pub fn new(addr: impl Into<String>) -> Self {
+        EtpBackend {
+            addr: addr.into(),
+            user: "anonymous".to_string(),
+            password: String::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn login(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.user = user.into();
+        self.password = password.into();
+        self
+    }
+
+    /// The timeout of connecting, and of reading or writing each line.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn connect(&self) -> io::Result<Session> {
+        let addr = self.resolve()?;
+        let stream = TcpStream::connect_timeout(&addr, self.timeout)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        let mut session = Session {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+        session.expect(220)?;
+        session.send(&format!("USER {}", self.user))?;
+        if session.reply()?.0 == 331 {
+            session.command(&format!("PASS {}", self.password), 230)?;
+        }
+        Ok(session)
+    }
+
+    fn resolve(&self) -> io::Result<SocketAddr> {
+        let mut addrs = self
+            .addr
+            .to_socket_addrs()
+            .or_else(|_| (self.addr.as_str(), DEFAULT_PORT).to_socket_addrs())?;
+        addrs
+            .next()
+            .ok_or_else(|| invalid(format!("no address for {}", self.addr)))
+    }
+}
+
+impl SearchBackend for EtpBackend {
+    fn name(&self) -> &str {
+        "Everything (ETP)"
+    }
+
+    /// Whether it can connect and log in.
+    fn is_available(&self) -> bool {
+        self.connect()
+            .and_then(|mut session| session.quit())
+            .is_ok()
+    }
+
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        let mut session = self.connect()?;
+        let results = session.search(options)?;
+        let _ = session.quit();
+        Ok(results)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            request_flags: Self::SUPPORTED_FLAGS,
+            ..Capabilities::all()
+        }
+    }
+}
+
+/// A logged-in connection.
+struct Session {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Session {
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        if line.contains(['\r', '\n']) {
+            return Err(invalid("no line break allowed in the command"));
+        }
+        self.writer.write_all(format!("{line}\r\n").as_bytes())
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    }
+
+    /// Read a reply, the multiline one is `123-first`, the lines, and then `123 last`.
+    fn reply(&mut self) -> io::Result<(u16, Vec<String>)> {
+        let first = self.read_line()?;
+        let code = first
+            .get(..3)
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| invalid(format!("bad reply: {first}")))?;
+        let mut lines = vec![first[3..].trim_start_matches(['-', ' ']).to_string()];
+        if first.as_bytes().get(3) == Some(&b'-') {
+            let end = format!("{code} ");
+            loop {
+                let line = self.read_line()?;
+                if let Some(last) = line.strip_prefix(&end) {
+                    lines.push(last.to_string());
+                    break;
+                }
+                lines.push(line);
+            }
+        }
+        Ok((code, lines))
+    }
+
+    fn expect(&mut self, code: u16) -> io::Result<Vec<String>> {
+        match self.reply()? {
+            (got, lines) if got == code => Ok(lines),
+            (got, lines) => Err(invalid(format!("expected {code}, got {got} {lines:?}"))),
+        }
+    }
+
+    fn command(&mut self, line: &str, code: u16) -> io::Result<Vec<String>> {
+        self.send(line)?;
+        self.expect(code)
+    }
+
+    fn quit(&mut self) -> io::Result<()> {
+        self.send("QUIT")
+    }
+
+    fn search(&mut self, options: &SearchOptions) -> io::Result<OwnedResults> {
+        let flag = |on: bool| u8::from(on);
+        let sort = format!("{:?}", options.sort);
+        let sort = sort.trim_start_matches("EVERYTHING_SORT_");
+        let (sort, ascending) = match sort.strip_suffix("_DESCENDING") {
+            Some(sort) => (sort, false),
+            None => (sort.trim_end_matches("_ASCENDING"), true),
+        };
+        let mut commands = vec![
+            format!("EVERYTHING SEARCH {}", options.search),
+            format!("EVERYTHING CASE {}", flag(options.match_case)),
+            format!("EVERYTHING WHOLE_WORD {}", flag(options.match_whole_word)),
+            format!("EVERYTHING PATH {}", flag(options.match_path)),
+            format!("EVERYTHING REGEX {}", flag(options.regex)),
+            format!("EVERYTHING OFFSET {}", options.offset),
+            format!("EVERYTHING COUNT {}", options.max),
+            format!("EVERYTHING SORT {sort} {}", flag(ascending)),
+            "EVERYTHING PATH_COLUMN 1".to_string(),
+        ];
+        for (flag, column) in COLUMNS {
+            let on = options.request_flags.contains(flag);
+            commands.push(format!("EVERYTHING {column} {}", flag(on)));
+        }
+        for command in &commands {
+            self.command(command, 200)?;
+        }
+        self.send("EVERYTHING QUERY")?;
+        let lines = self.expect(200)?;
+
+        let request_flags = options.request_flags & EtpBackend::SUPPORTED_FLAGS;
+        let mut total = 0;
+        let mut items = Vec::new();
+        let mut path = OsString::new();
+        for line in &lines {
+            let line = line.trim_start();
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let number = || value.trim().parse::<u64>().ok();
+            let kind = match key {
+                "RESULT_COUNT" => {
+                    total = number().unwrap_or(0) as u32;
+                    continue;
+                }
+                "PATH" => {
+                    path = value.into();
+                    continue;
+                }
+                "FILE" => Some(ItemKind::File),
+                "FOLDER" => Some(ItemKind::Folder),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                let index = options.offset + items.len() as u32;
+                items.push(to_data(index, kind, &path, value, request_flags));
+                continue;
+            }
+            // The columns of the last item.
+            let Some(item) = items.last_mut() else {
+                continue;
+            };
+            match key {
+                "SIZE" => item.size = number(),
+                "DATE_CREATED" => item.date_created = number(),
+                "DATE_MODIFIED" => item.date_modified = number(),
+                "ATTRIBUTES" => item.attributes = number().map(|n| n as u32),
+                "FILE_LIST_FILENAME" => item.file_list_filename = Some(value.into()),
+                "RUN_COUNT" => item.run_count = number().map(|n| n as u32),
+                "DATE_RECENTLY_CHANGED" => item.date_recently_changed = number(),
+                _ => {} // the end text, or the columns not known
+            }
+        }
+        Ok(OwnedResults::from_parts(
+            items,
+            request_flags,
+            options.sort,
+            total,
+            options.clone(),
+        ))
+    }
+}
+
+fn to_data(
+    index: u32,
+    kind: ItemKind,
+    path: &OsString,
+    filename: &str,
+    request_flags: RequestFlags,
+) -> ItemData {
+    let has = |flag| request_flags.contains(flag);
+    let full_path = if path.is_empty() {
+        PathBuf::from(filename)
+    } else {
+        Path::new(path).join(filename)
+    };
+    let extension = match kind {
+        ItemKind::File => Path::new(filename).extension().unwrap_or_default().into(),
+        _ => OsString::new(),
+    };
+    ItemData {
+        index,
+        kind,
+        request_flags,
+        filename: Some(filename.into()).filter(|_| has(RequestFlags::EVERYTHING_REQUEST_FILE_NAME)),
+        path: Some(PathBuf::from(path)).filter(|_| has(RequestFlags::EVERYTHING_REQUEST_PATH)),
+        full_path: Some(full_path).filter(|_| {
+            has(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+                || has(RequestFlags::EVERYTHING_REQUEST_PATH
+                    | RequestFlags::EVERYTHING_REQUEST_FILE_NAME)
+        }),
+        extension: Some(extension).filter(|_| has(RequestFlags::EVERYTHING_REQUEST_EXTENSION)),
+        ..Default::default()
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,127 @@
+//! The in-memory backend for the tests of the apps, on the machines without Everything.
+
+use std::sync::{Mutex, PoisonError};
+
+use super::{Capabilities, SearchBackend};
+use crate::snapshot::Key;
+use crate::{EverythingError, ItemData, OwnedResults, RequestFlags, Result, SearchOptions};
+
+/// A backend searching the given items in memory, and recording the searches.
+///
+/// The search text is matched client-side like [`OwnedResults::refine`], that is each word
+/// is a substring of the name (or of the path if it contains `\` or match path is on), so the
+/// Everything search syntax and the regex are not supported. The sort, offset and max are
+/// applied, and the items keep the data as given, regardless of the request flags.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use everything_sdk::backend::{MockBackend, SearchBackend};
+/// use everything_sdk::*;
+///
+/// let mut item = ItemData::default();
+/// item.filename = Some("main.rs".into());
+/// item.path = Some(PathBuf::from("C:\\src"));
+/// item.full_path = Some(PathBuf::from("C:\\src\\main.rs"));
+/// let backend = MockBackend::new(vec![item]);
+///
+/// let results = backend.search(&SearchOptions::new("main"))?;
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(backend.searches()[0].search, "main");
+/// # Ok::<(), EverythingError>(())
+/// ```
+#[derive(Debug)]
+pub struct MockBackend {
+    items: Vec<ItemData>,
+    available: bool,
+    searches: Mutex<Vec<SearchOptions>>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl MockBackend {
+    /// The available backend of the `items`.
+    pub fn new(items: Vec<ItemData>) -> Self {
+        MockBackend {
+            items,
+            available: true,
+            searches: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Pretend to be unavailable (like Everything not running), then all searches fail with
+    /// [`EverythingError::Ipc`].
+    pub fn set_available(&mut self, available: bool) -> &mut Self {
+        self.available = available;
+        self
+    }
+
+    /// All the options searched so far, in order.
+    pub fn searches(&self) -> Vec<SearchOptions> {
+        self.lock_searches().clone()
+    }
+
+    fn lock_searches(&self) -> std::sync::MutexGuard<'_, Vec<SearchOptions>> {
+        self.searches.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SearchBackend for MockBackend {
+    fn name(&self) -> &str {
+        "Mock"
+    }
+
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        self.lock_searches().push(options.clone());
+        if !self.available {
+            return Err(EverythingError::Ipc);
+        }
+        let all = OwnedResults::from_parts(
+            self.items.clone(),
+            RequestFlags::all(),
+            options.sort,
+            self.items.len() as u32,
+            options.clone(),
+        );
+        let mut matched = all.refine(&options.search)?;
+        if let Some(key) = Key::from_sort_type(options.sort) {
+            matched.sort_by_keys(&[Key::FoldersFirst, key]);
+        }
+        let total = matched.total();
+        let items = matched
+            .into_items()
+            .into_iter()
+            .skip(options.offset as usize)
+            .take(options.max as usize)
+            .enumerate()
+            .map(|(index, mut item)| {
+                item.index = index as u32;
+                item
+            })
+            .collect();
+        Ok(OwnedResults::from_parts(
+            items,
+            RequestFlags::all(),
+            options.sort,
+            total,
+            options.clone(),
+        ))
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            match_whole_word: false,
+            regex: false,
+            everything_syntax: false,
+            ..Capabilities::all()
+        }
+    }
+}
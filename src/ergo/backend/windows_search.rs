@@ -0,0 +1,408 @@
+//! Windows Search, that is the `SystemIndex` catalog over OLE DB. (feature `windows-search`)
+
+use std::ffi::{c_void, OsString};
+use std::mem::{self, size_of, ManuallyDrop};
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::ptr::{self, addr_of};
+
+use windows::core::{ComInterface, IUnknown, Result as WinResult, GUID, HSTRING, PWSTR};
+use windows::Win32::Foundation::E_POINTER;
+use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_DIRECTORY;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_INPROC_SERVER,
+    CLSCTX_LOCAL_SERVER, COINIT_MULTITHREADED,
+};
+use windows::Win32::System::Search::{
+    IAccessor, ICommandText, IDBCreateCommand, IDBCreateSession, IDBInitialize, IDataInitialize,
+    IRowset, ISearchManager, ISearchQueryHelper, DBBINDING,
+};
+
+use super::{Capabilities, SearchBackend};
+use crate::{ItemData, ItemKind, OwnedResults, RequestFlags, Result, SearchOptions, SortType};
+
+/// `CLSID_CSearchManager`
+const CLSID_SEARCH_MANAGER: GUID = GUID::from_u128(0x7d096c5f_ac08_4f1f_beb7_5c22c517ce39);
+/// `CLSID_MSDAINITIALIZE`
+const CLSID_MSDAINITIALIZE: GUID = GUID::from_u128(0x2206cdb0_19c1_11d1_89e0_00c04fd7a829);
+/// `DBGUID_DEFAULT`, the SQL dialect of the provider.
+const DBGUID_DEFAULT: GUID = GUID::from_u128(0xc8b521fb_5cf3_11ce_ade5_00aa0044773d);
+
+// From oledb.h, as plain numbers to bind the columns.
+const DBACCESSOR_ROWDATA: u32 = 0x2;
+const DBPART_VALUE: u32 = 0x1;
+const DBPART_LENGTH: u32 = 0x2;
+const DBPART_STATUS: u32 = 0x4;
+const DBMEMOWNER_CLIENTOWNED: u32 = 0;
+const DBPARAMIO_NOTPARAM: u32 = 0;
+const DBTYPE_UI4: u16 = 19;
+const DBTYPE_UI8: u16 = 21;
+const DBTYPE_FILETIME: u16 = 64;
+const DBTYPE_WSTR: u16 = 130;
+const DBSTATUS_S_OK: u32 = 0;
+
+/// The selected columns, in the order of the fields in [`Row`].
+const COLUMNS: &str =
+    "System.ItemPathDisplay,System.Size,System.DateCreated,System.DateModified,System.FileAttributes";
+
+/// The max length of the path in UTF-16, including the null terminator.
+const PATH_CAPACITY: usize = 32_768;
+
+/// The number of rows fetched at once.
+const BATCH: usize = 256;
+
+/// Windows Search as the degraded fallback, when Everything is not installed or not running.
+///
+/// Only the indexed locations (the user folders by default) are searched, and the search text
+/// is taken as the [AQS](https://learn.microsoft.com/en-us/windows/win32/search/-search-3x-advancedquerysyntax)
+/// query, so the simple words and `*.ext` work the same, but the Everything search syntax
+/// does not. The match path, match case, match whole word and regex are ignored, and only
+/// the name, path, size, dates (created and modified) and attributes are available. The sort
+/// by the other columns falls back to by name. The total is the number of the results read,
+/// as Windows Search does not count the rest.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct WindowsSearchBackend {
+    _private: (),
+}
+
+impl WindowsSearchBackend {
+    /// The request flags Windows Search can answer.
+    pub const SUPPORTED_FLAGS: RequestFlags = RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+        .union(RequestFlags::EVERYTHING_REQUEST_PATH)
+        .union(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+        .union(RequestFlags::EVERYTHING_REQUEST_EXTENSION)
+        .union(RequestFlags::EVERYTHING_REQUEST_SIZE)
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)
+        .union(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SearchBackend for WindowsSearchBackend {
+    fn name(&self) -> &str {
+        "Windows Search"
+    }
+
+    /// Whether the Windows Search service is running.
+    fn is_available(&self) -> bool {
+        with_com(|| query_helper().map(|_| ())).is_ok()
+    }
+
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        Ok(with_com(|| search(options))?)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            request_flags: Self::SUPPORTED_FLAGS,
+            match_path: false,
+            match_case: false,
+            match_whole_word: false,
+            regex: false,
+            sort: true,
+            everything_syntax: false,
+        }
+    }
+}
+
+/// Run `f` with COM initialized on this thread, or as it was if already initialized.
+fn with_com<T>(f: impl FnOnce() -> WinResult<T>) -> WinResult<T> {
+    let initialized = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok();
+    let result = f(); // drop all the COM objects before uninitializing
+    if initialized {
+        unsafe { CoUninitialize() };
+    }
+    result
+}
+
+fn query_helper() -> WinResult<ISearchQueryHelper> {
+    unsafe {
+        let manager: ISearchManager =
+            CoCreateInstance(&CLSID_SEARCH_MANAGER, None, CLSCTX_LOCAL_SERVER)?;
+        manager
+            .GetCatalog(&HSTRING::from("SystemIndex"))?
+            .GetQueryHelper()
+    }
+}
+
+/// The `ORDER BY` of the sort, `None` if not supported.
+fn sorting_of(sort: SortType) -> Option<&'static str> {
+    use SortType::*;
+    let sorting = match sort {
+        EVERYTHING_SORT_NAME_ASCENDING => "System.ItemNameDisplay ASC",
+        EVERYTHING_SORT_NAME_DESCENDING => "System.ItemNameDisplay DESC",
+        EVERYTHING_SORT_PATH_ASCENDING => "System.ItemPathDisplay ASC",
+        EVERYTHING_SORT_PATH_DESCENDING => "System.ItemPathDisplay DESC",
+        EVERYTHING_SORT_SIZE_ASCENDING => "System.Size ASC",
+        EVERYTHING_SORT_SIZE_DESCENDING => "System.Size DESC",
+        EVERYTHING_SORT_EXTENSION_ASCENDING => "System.FileExtension ASC",
+        EVERYTHING_SORT_EXTENSION_DESCENDING => "System.FileExtension DESC",
+        EVERYTHING_SORT_DATE_CREATED_ASCENDING => "System.DateCreated ASC",
+        EVERYTHING_SORT_DATE_CREATED_DESCENDING => "System.DateCreated DESC",
+        EVERYTHING_SORT_DATE_MODIFIED_ASCENDING => "System.DateModified ASC",
+        EVERYTHING_SORT_DATE_MODIFIED_DESCENDING => "System.DateModified DESC",
+        EVERYTHING_SORT_ATTRIBUTES_ASCENDING => "System.FileAttributes ASC",
+        EVERYTHING_SORT_ATTRIBUTES_DESCENDING => "System.FileAttributes DESC",
+        _ => return None,
+    };
+    Some(sorting)
+}
+
+fn search(options: &SearchOptions) -> WinResult<OwnedResults> {
+    let sort = match sorting_of(options.sort) {
+        Some(_) => options.sort,
+        None => SortType::default(),
+    };
+    let helper = query_helper()?;
+    let (connection, sql) = unsafe {
+        helper.SetQuerySelectColumns(&HSTRING::from(COLUMNS))?;
+        helper.SetQuerySorting(&HSTRING::from(sorting_of(sort).unwrap_or_default()))?;
+        // The offset is skipped client-side.
+        let wanted = options.offset.saturating_add(options.max);
+        if let Ok(wanted) = i32::try_from(wanted) {
+            helper.SetQueryMaxResults(wanted)?;
+        }
+        let sql = helper.GenerateSQLFromUserQuery(&HSTRING::from(options.search.as_str()))?;
+        (take_co_str(helper.ConnectionString()?)?, take_co_str(sql)?)
+    };
+    let rowset = execute(&connection, &sql)?;
+
+    let request_flags = options.request_flags & WindowsSearchBackend::SUPPORTED_FLAGS;
+    let mut items = Vec::new();
+    let mut skip = options.offset;
+    read_rows(&rowset, |row| {
+        if skip > 0 {
+            skip -= 1;
+        } else if items.len() < options.max as usize {
+            items.push(row.to_data(items.len() as u32, request_flags));
+        }
+    })?;
+    let total = options.offset.saturating_sub(skip) + items.len() as u32;
+    Ok(OwnedResults::from_parts(
+        items,
+        request_flags,
+        sort,
+        total,
+        options.clone(),
+    ))
+}
+
+/// Copy out the string allocated by COM, and free it.
+unsafe fn take_co_str(s: PWSTR) -> WinResult<HSTRING> {
+    let copied = HSTRING::from_wide(s.as_wide());
+    CoTaskMemFree(Some(s.0 as *const c_void));
+    copied
+}
+
+/// The interface from the `IUnknown` out parameter.
+fn out<T: ComInterface>(unknown: Option<IUnknown>) -> WinResult<T> {
+    unknown
+        .ok_or_else(|| windows::core::Error::from(E_POINTER))?
+        .cast()
+}
+
+/// Run the `sql` on the provider of `connection`.
+fn execute(connection: &HSTRING, sql: &HSTRING) -> WinResult<IRowset> {
+    unsafe {
+        let init: IDataInitialize =
+            CoCreateInstance(&CLSID_MSDAINITIALIZE, None, CLSCTX_INPROC_SERVER)?;
+        let mut source = None;
+        init.GetDataSource(
+            None,
+            CLSCTX_INPROC_SERVER.0,
+            connection,
+            &IDBInitialize::IID,
+            &mut source,
+        )?;
+        let source: IDBInitialize = out(source)?;
+        source.Initialize()?;
+
+        let mut session = None;
+        source.cast::<IDBCreateSession>()?.CreateSession(
+            None,
+            &IDBCreateCommand::IID,
+            &mut session,
+        )?;
+        let session: IDBCreateCommand = out(session)?;
+        let mut command = None;
+        session.CreateCommand(None, &ICommandText::IID, &mut command)?;
+        let command: ICommandText = out(command)?;
+        command.SetCommandText(&DBGUID_DEFAULT, sql)?;
+
+        let mut rowset = None;
+        command.Execute(
+            None,
+            &IRowset::IID,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut rowset,
+        )?;
+        out(rowset)
+    }
+}
+
+/// Call `f` with each row, in order.
+fn read_rows(rowset: &IRowset, mut f: impl FnMut(&Row)) -> WinResult<()> {
+    // SAFETY: all zeros is a valid `Row`, which is only integers.
+    let mut row: Box<Row> = Box::new(unsafe { mem::zeroed() });
+    let bindings = row.bindings();
+    let accessor: IAccessor = rowset.cast()?;
+    let mut haccessor = 0;
+    unsafe {
+        accessor.CreateAccessor(
+            DBACCESSOR_ROWDATA,
+            bindings.len(),
+            bindings.as_ptr(),
+            size_of::<Row>(),
+            &mut haccessor,
+            ptr::null_mut(),
+        )?;
+    }
+    let result = (|| loop {
+        let mut handles = [0usize; BATCH];
+        let mut phandles = handles.as_mut_ptr();
+        let mut obtained = 0;
+        unsafe { rowset.GetNextRows(0, 0, BATCH as isize, &mut obtained, &mut phandles)? };
+        if obtained == 0 {
+            return Ok(());
+        }
+        let fetched: WinResult<()> = handles[..obtained].iter().try_for_each(|&handle| {
+            unsafe { rowset.GetData(handle, haccessor, &mut *row as *mut Row as *mut c_void)? };
+            f(&row);
+            Ok(())
+        });
+        unsafe {
+            rowset.ReleaseRows(
+                obtained,
+                handles.as_ptr(),
+                ptr::null(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )?;
+        }
+        fetched?;
+    })();
+    unsafe { accessor.ReleaseAccessor(haccessor, ptr::null_mut())? };
+    result
+}
+
+/// The value of a column in [`Row`], with its length in bytes and its status.
+#[repr(C)]
+struct Column<T> {
+    status: u32,
+    length: usize,
+    value: T,
+}
+
+impl<T: Copy> Column<T> {
+    fn get(&self) -> Option<T> {
+        (self.status == DBSTATUS_S_OK).then_some(self.value)
+    }
+}
+
+/// The buffer of one row, the fields are in the order of [`COLUMNS`].
+#[repr(C)]
+struct Row {
+    path: Column<[u16; PATH_CAPACITY]>,
+    size: Column<u64>,
+    date_created: Column<u64>,
+    date_modified: Column<u64>,
+    attributes: Column<u32>,
+}
+
+impl Row {
+    fn bindings(&self) -> [DBBINDING; 5] {
+        let base = self as *const Row as usize;
+        [
+            binding(1, &self.path, base, DBTYPE_WSTR),
+            binding(2, &self.size, base, DBTYPE_UI8),
+            binding(3, &self.date_created, base, DBTYPE_FILETIME),
+            binding(4, &self.date_modified, base, DBTYPE_FILETIME),
+            binding(5, &self.attributes, base, DBTYPE_UI4),
+        ]
+    }
+
+    fn full_path(&self) -> Option<PathBuf> {
+        if self.path.status != DBSTATUS_S_OK {
+            return None;
+        }
+        let len = (self.path.length / 2).min(PATH_CAPACITY);
+        Some(OsString::from_wide(&self.path.value[..len]).into())
+    }
+
+    fn to_data(&self, index: u32, request_flags: RequestFlags) -> ItemData {
+        let attributes = self.attributes.get();
+        let kind = match attributes {
+            Some(attr) if attr & FILE_ATTRIBUTE_DIRECTORY.0 != 0 => ItemKind::Folder,
+            _ => ItemKind::File,
+        };
+        let full_path = self.full_path();
+        let filename = full_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(OsString::from);
+        let path = full_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(PathBuf::from);
+        let extension = match (kind, &full_path) {
+            (ItemKind::File, Some(path)) => Some(path.extension().unwrap_or_default().into()),
+            (_, Some(_)) => Some(OsString::new()),
+            (_, None) => None,
+        };
+        // The same as Everything, the size of a folder is 0.
+        let size = match kind {
+            ItemKind::File => self.size.get(),
+            _ => Some(0),
+        };
+        let has = |flag| request_flags.contains(flag);
+        ItemData {
+            index,
+            kind,
+            request_flags,
+            full_path: full_path.filter(|_| {
+                has(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+                    || has(RequestFlags::EVERYTHING_REQUEST_PATH
+                        | RequestFlags::EVERYTHING_REQUEST_FILE_NAME)
+            }),
+            filename: filename.filter(|_| has(RequestFlags::EVERYTHING_REQUEST_FILE_NAME)),
+            path: path.filter(|_| has(RequestFlags::EVERYTHING_REQUEST_PATH)),
+            extension: extension.filter(|_| has(RequestFlags::EVERYTHING_REQUEST_EXTENSION)),
+            size: size.filter(|_| has(RequestFlags::EVERYTHING_REQUEST_SIZE)),
+            date_created: self
+                .date_created
+                .get()
+                .filter(|_| has(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)),
+            date_modified: self
+                .date_modified
+                .get()
+                .filter(|_| has(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)),
+            attributes: attributes.filter(|_| has(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Bind the `column` in the row at `base` to the column `ordinal` (starts from 1).
+fn binding<T>(ordinal: usize, column: &Column<T>, base: usize, db_type: u16) -> DBBINDING {
+    DBBINDING {
+        iOrdinal: ordinal,
+        obValue: addr_of!(column.value) as usize - base,
+        obLength: addr_of!(column.length) as usize - base,
+        obStatus: addr_of!(column.status) as usize - base,
+        pTypeInfo: ManuallyDrop::new(None),
+        pObject: ptr::null_mut(),
+        pBindExt: ptr::null_mut(),
+        dwPart: DBPART_VALUE | DBPART_LENGTH | DBPART_STATUS,
+        dwMemOwner: DBMEMOWNER_CLIENTOWNED,
+        eParamIO: DBPARAMIO_NOTPARAM,
+        cbMaxLen: size_of::<T>(),
+        dwFlags: 0,
+        wType: db_type,
+        bPrecision: 0,
+        bScale: 0,
+    }
+}
@@ -0,0 +1,203 @@
+//! A worker thread taking the searches from many threads over a channel.
+//!
+//! Each thread locking [`global`](super::global) by itself works, but every caller then deals
+//! with the lock and the lifetimes of the results. The [`EverythingBroker`] does the queries
+//! on its own thread instead, and sends back the owned results. The clones of it are cheap, so
+//! give one to each thread.
+//!
+//! The same searches waiting in the queue are queried only once, and each caller gets a copy
+//! of the results. With a [`RateLimiter`], the queries are also throttled, so more of the
+//! repeated searches (like from a UI) are coalesced.
+//!
+//! Or stream the items into a channel by [`EverythingBroker::query_to_channel`], which is
+//! never coalesced.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TryRecvError};
+use std::thread;
+
+use super::channel::{self, ItemSender};
+use super::rate_limit::RateLimiter;
+use super::{EverythingError, OwnedResults, Result, SearchOptions};
+
+/// Where to send the results of a job.
+enum Reply {
+    Blocking(SyncSender<Result<OwnedResults>>),
+    #[cfg(feature = "async")]
+    Async(futures::channel::oneshot::Sender<Result<OwnedResults>>),
+    Stream(Box<dyn ItemSender>),
+}
+
+impl Reply {
+    fn send(self, results: Result<OwnedResults>) {
+        // The caller may be gone, that is fine.
+        match self {
+            Reply::Blocking(reply) => {
+                let _ = reply.send(results);
+            }
+            #[cfg(feature = "async")]
+            Reply::Async(reply) => {
+                let _ = reply.send(results);
+            }
+            Reply::Stream(sender) => match results {
+                Ok(results) => {
+                    for data in results {
+                        if !sender.send_item(Ok(data)) {
+                            break;
+                        }
+                    }
+                }
+                Err(err) => {
+                    sender.send_item(Err(err));
+                }
+            },
+        }
+    }
+}
+
+struct Job {
+    options: SearchOptions,
+    reply: Reply,
+}
+
+/// The sender of the searches to the worker thread, see the [module](self) docs.
+///
+/// The worker takes the global lock for each query, so the others can still use the lock in
+/// between. It stops when all the clones of the broker are dropped.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::*;
+///
+/// let broker = EverythingBroker::spawn()?;
+/// let handles: Vec<_> = ["*.rs", "*.toml"]
+///     .into_iter()
+///     .map(|search| {
+///         let broker = broker.clone();
+///         std::thread::spawn(move || broker.search(SearchOptions::new(search)))
+///     })
+///     .collect();
+/// for handle in handles {
+///     println!("{} results", handle.join().unwrap()?.len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct EverythingBroker {
+    sender: Sender<Job>,
+}
+
+impl EverythingBroker {
+    /// Start the worker thread.
+    pub fn spawn() -> std::io::Result<Self> {
+        Self::spawn_with(None)
+    }
+
+    /// Start the worker thread, which takes a permit of the `limiter` for each query.
+    ///
+    /// Share the `limiter` with the other brokers (or your own queries) to limit them all.
+    pub fn spawn_limited(limiter: RateLimiter) -> std::io::Result<Self> {
+        Self::spawn_with(Some(limiter))
+    }
+
+    fn spawn_with(limiter: Option<RateLimiter>) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("everything-sdk-broker".to_string())
+            .spawn(move || work(receiver, limiter))?;
+        Ok(EverythingBroker { sender })
+    }
+
+    fn send(&self, options: SearchOptions, reply: Reply) -> Result<()> {
+        self.sender
+            .send(Job { options, reply })
+            .map_err(|_| EverythingError::Disconnected)
+    }
+
+    /// Send the search, and get the results later by [`Pending::wait`].
+    pub fn submit(&self, options: SearchOptions) -> Result<Pending> {
+        let (reply, receiver) = mpsc::sync_channel(1);
+        self.send(options, Reply::Blocking(reply))?;
+        Ok(Pending { receiver })
+    }
+
+    /// Search and block until the results.
+    pub fn search(&self, options: SearchOptions) -> Result<OwnedResults> {
+        self.submit(options)?.wait()
+    }
+
+    /// Search and wait for the results, without blocking the executor.
+    #[cfg(feature = "async")]
+    pub async fn search_async(&self, options: SearchOptions) -> Result<OwnedResults> {
+        let (reply, receiver) = futures::channel::oneshot::channel();
+        self.send(options, Reply::Async(reply))?;
+        receiver.await.map_err(|_| EverythingError::Disconnected)?
+    }
+
+    /// Send the search, whose items are sent to the `sender` one by one while being copied
+    /// out, and the `sender` is dropped after the last one, which ends the channel. The error
+    /// of the query (if any) is sent instead.
+    ///
+    /// See the [`channel`](super::channel) module docs for an example.
+    pub fn query_to_channel(&self, options: SearchOptions, sender: impl ItemSender) -> Result<()> {
+        self.send(options, Reply::Stream(Box::new(sender)))
+    }
+}
+
+/// The results to come, from [`EverythingBroker::submit`].
+#[derive(Debug)]
+pub struct Pending {
+    receiver: Receiver<Result<OwnedResults>>,
+}
+
+impl Pending {
+    /// Block until the results.
+    pub fn wait(self) -> Result<OwnedResults> {
+        self.receiver
+            .recv()
+            .map_err(|_| EverythingError::Disconnected)?
+    }
+
+    /// The results if done, or `None` if not yet.
+    pub fn try_get(&self) -> Option<Result<OwnedResults>> {
+        match self.receiver.try_recv() {
+            Ok(results) => Some(results),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(EverythingError::Disconnected)),
+        }
+    }
+}
+
+fn work(receiver: Receiver<Job>, limiter: Option<RateLimiter>) {
+    let mut backlog = VecDeque::new();
+    loop {
+        let job = match backlog.pop_front() {
+            Some(job) => job,
+            None => match receiver.recv() {
+                Ok(job) => job,
+                Err(_) => return,
+            },
+        };
+        let _permit = limiter.as_ref().map(RateLimiter::acquire);
+        if let Reply::Stream(sender) = &job.reply {
+            channel::query_options_to(&job.options, sender.as_ref());
+            continue;
+        }
+        // The same searches sent in the meantime are answered by this query too.
+        backlog.extend(receiver.try_iter());
+        let (same, rest): (Vec<Job>, VecDeque<Job>) = backlog.drain(..).partition(|other| {
+            other.options == job.options && !matches!(other.reply, Reply::Stream(_))
+        });
+        backlog = rest;
+
+        let results = job.options.query_snapshot();
+        for other in same {
+            match &results {
+                Ok(snapshot) => other.reply.send(Ok(snapshot.clone())),
+                // The errors are not `Clone`, and rare, so query again for the others.
+                Err(_) => other.reply.send(job.options.query_snapshot()),
+            }
+        }
+        job.reply.send(results);
+    }
+}
@@ -0,0 +1,101 @@
+//! Stream the owned items of a query into a channel, so the pipeline works on the first items
+//! while the rest are still being copied out.
+//!
+//! The items are copied out with the global lock held, so the receiver should keep up, or
+//! use an unbounded channel. A full bounded channel holds the lock until it has room.
+//!
+//! ```no_run
+//! use std::sync::mpsc;
+//! use everything_sdk::*;
+//!
+//! let broker = EverythingBroker::spawn()?;
+//! let (sender, receiver) = mpsc::channel();
+//! broker.query_to_channel(SearchOptions::new("*.log"), sender)?;
+//! for item in receiver {
+//!     println!("{:?}", item?.filename);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::sync::mpsc::{Sender, SyncSender};
+
+use super::{global, EverythingSearcher, ItemData, Result, SearchOptions};
+
+/// The sending half of a channel of the items, implemented for the senders of
+/// [`std::sync::mpsc`], and of [crossbeam](https://docs.rs/crossbeam-channel) with the
+/// `crossbeam` feature.
+pub trait ItemSender: Send + 'static {
+    /// Send the item, or `false` if the receiver is gone, which stops the query streaming.
+    fn send_item(&self, item: Result<ItemData>) -> bool;
+}
+
+impl ItemSender for Sender<Result<ItemData>> {
+    fn send_item(&self, item: Result<ItemData>) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+impl ItemSender for SyncSender<Result<ItemData>> {
+    fn send_item(&self, item: Result<ItemData>) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+impl ItemSender for crossbeam_channel::Sender<Result<ItemData>> {
+    fn send_item(&self, item: Result<ItemData>) -> bool {
+        self.send(item).is_ok()
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Query, and send each visible item to the `sender` as soon as it is copied out, and
+    /// return the number sent. It stops early if the receiver is gone.
+    #[cfg(not(feature = "async"))]
+    pub fn query_to_channel<S: ItemSender + ?Sized>(&mut self, sender: &S) -> usize {
+        self.query()
+            .iter()
+            .take_while(|item| sender.send_item(Ok(item.to_data())))
+            .count()
+    }
+
+    /// Query, and send each visible item to the `sender` as soon as it is copied out, and
+    /// return the number sent. It stops early if the receiver is gone.
+    #[cfg(feature = "async")]
+    pub async fn query_to_channel<S: ItemSender + ?Sized>(&mut self, sender: &S) -> usize {
+        self.query()
+            .await
+            .iter()
+            .take_while(|item| sender.send_item(Ok(item.to_data())))
+            .count()
+    }
+}
+
+/// Take the [`global`] lock, and query with the `options` into the `sender`, which gets the
+/// error instead if Everything is not running. It blocks on the lock, like
+/// [`SearchOptions::query_snapshot`].
+#[cfg(not(feature = "async"))]
+pub(super) fn query_options_to(options: &SearchOptions, sender: &dyn ItemSender) {
+    let mut everything = global()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Err(err) = everything.is_db_loaded() {
+        sender.send_item(Err(err));
+        return;
+    }
+    let mut searcher = everything.searcher();
+    searcher.set_options(options).query_to_channel(sender);
+}
+
+#[cfg(feature = "async")]
+pub(super) fn query_options_to(options: &SearchOptions, sender: &dyn ItemSender) {
+    futures::executor::block_on(async {
+        let mut everything = global().lock().await;
+        if let Err(err) = everything.is_db_loaded() {
+            sender.send_item(Err(err));
+            return;
+        }
+        let mut searcher = everything.searcher();
+        searcher.set_options(options).query_to_channel(sender).await;
+    })
+}
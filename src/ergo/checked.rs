@@ -0,0 +1,300 @@
+//! The request flags in the type, so the item getters are checked at compile time.
+//!
+//! With the dynamic API, `item.size()` fails with [`InvalidRequestError::RequestFlagsNotSet`]
+//! at runtime if `EVERYTHING_REQUEST_SIZE` was not requested. Here each requested flag is a
+//! marker type (like [`Size`]) added to the type of the searcher, and the getter of a flag not
+//! requested does not compile. The getters do not return `Result`, the flags are checked once
+//! by the query instead.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::checked::{FileName, Size};
+//! use everything_sdk::*;
+//!
+//! let mut everything = global().lock().unwrap();
+//! let mut searcher = everything.searcher();
+//! searcher.set_search("*.iso");
+//! let mut checked = searcher.checked().request::<FileName>().request::<Size>();
+//! let results = checked.query()?;
+//! for item in results.iter() {
+//!     println!("{:?} {}", item.filename(), item.size());
+//!     // item.date_modified(); // <- does not compile, `DateModified` is not requested
+//! }
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::ffi::OsString;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+use super::{
+    EverythingItem, EverythingResults, EverythingSearcher, FileAttributes, InvalidRequestError,
+    RequestFlags, Result,
+};
+
+/// A request flag as a type, see the markers in this module.
+pub trait Request {
+    const FLAG: RequestFlags;
+    type Output;
+
+    /// Read it from the item, the flag must be available in the results.
+    fn get(item: &EverythingItem<'_>) -> Self::Output;
+}
+
+macro_rules! requests {
+    ($($(#[$doc:meta])* $name:ident($flag:ident)
+        -> $output:ty = $getter:ident($($arg:expr)?);)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+            pub struct $name;
+
+            impl Request for $name {
+                const FLAG: RequestFlags = RequestFlags::$flag;
+                type Output = $output;
+
+                fn get(item: &EverythingItem<'_>) -> Self::Output {
+                    item.$getter($($arg)?).expect("the request flag is checked by the query")
+                }
+            }
+
+            impl<'a, L> CheckedItem<'a, L> {
+                $(#[$doc])*
+                pub fn $getter<I>(&self) -> $output
+                where
+                    L: Has<$name, I>,
+                {
+                    self.get::<$name, I>()
+                }
+            }
+        )*
+    };
+}
+
+requests! {
+    /// `EVERYTHING_REQUEST_FILE_NAME`, see [`EverythingItem::filename`].
+    FileName(EVERYTHING_REQUEST_FILE_NAME) -> OsString = filename();
+    /// `EVERYTHING_REQUEST_PATH`, see [`EverythingItem::path`].
+    Path(EVERYTHING_REQUEST_PATH) -> PathBuf = path();
+    /// `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`, see [`EverythingItem::full_path_name`].
+    FullPathAndFileName(EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+        -> PathBuf = full_path_name(None);
+    /// `EVERYTHING_REQUEST_EXTENSION`, see [`EverythingItem::extension`].
+    Extension(EVERYTHING_REQUEST_EXTENSION) -> OsString = extension();
+    /// `EVERYTHING_REQUEST_SIZE`, see [`EverythingItem::size`].
+    Size(EVERYTHING_REQUEST_SIZE) -> u64 = size();
+    /// `EVERYTHING_REQUEST_DATE_CREATED`, see [`EverythingItem::date_created`].
+    DateCreated(EVERYTHING_REQUEST_DATE_CREATED) -> u64 = date_created();
+    /// `EVERYTHING_REQUEST_DATE_MODIFIED`, see [`EverythingItem::date_modified`].
+    DateModified(EVERYTHING_REQUEST_DATE_MODIFIED) -> u64 = date_modified();
+    /// `EVERYTHING_REQUEST_DATE_ACCESSED`, see [`EverythingItem::date_accessed`].
+    DateAccessed(EVERYTHING_REQUEST_DATE_ACCESSED) -> u64 = date_accessed();
+    /// `EVERYTHING_REQUEST_ATTRIBUTES`, see [`EverythingItem::attributes`].
+    Attributes(EVERYTHING_REQUEST_ATTRIBUTES) -> FileAttributes = attributes();
+    /// `EVERYTHING_REQUEST_FILE_LIST_FILE_NAME`, see [`EverythingItem::file_list_filename`].
+    FileListFileName(EVERYTHING_REQUEST_FILE_LIST_FILE_NAME) -> OsString = file_list_filename();
+    /// `EVERYTHING_REQUEST_RUN_COUNT`, see [`EverythingItem::run_count`].
+    RunCount(EVERYTHING_REQUEST_RUN_COUNT) -> u32 = run_count();
+    /// `EVERYTHING_REQUEST_DATE_RUN`, see [`EverythingItem::date_run`].
+    DateRun(EVERYTHING_REQUEST_DATE_RUN) -> u64 = date_run();
+    /// `EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED`, see
+    /// [`EverythingItem::date_recently_changed`].
+    DateRecentlyChanged(EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED) -> u64 = date_recently_changed();
+    /// `EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME`, see
+    /// [`EverythingItem::highlighted_filename`].
+    HighlightedFileName(EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME)
+        -> OsString = highlighted_filename();
+    /// `EVERYTHING_REQUEST_HIGHLIGHTED_PATH`, see [`EverythingItem::highlighted_path`].
+    HighlightedPath(EVERYTHING_REQUEST_HIGHLIGHTED_PATH) -> OsString = highlighted_path();
+    /// `EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME`, see
+    /// [`EverythingItem::highlighted_full_path_and_filename`].
+    HighlightedFullPathAndFileName(EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME)
+        -> OsString = highlighted_full_path_and_filename();
+}
+
+/// The type-level list of the requested flags, built by [`CheckedSearcher::request`].
+pub trait RequestList {
+    const FLAGS: RequestFlags;
+}
+
+/// No flag requested.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Nil;
+
+/// The flag `F` and the flags in `Rest`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct With<F, Rest>(PhantomData<(F, Rest)>);
+
+impl RequestList for Nil {
+    const FLAGS: RequestFlags = RequestFlags::empty();
+}
+
+impl<F: Request, Rest: RequestList> RequestList for With<F, Rest> {
+    const FLAGS: RequestFlags = F::FLAG.union(Rest::FLAGS);
+}
+
+/// The index of `F` found at the head of the list.
+pub struct Here;
+/// The index of `F` found in the rest of the list.
+pub struct There<I>(PhantomData<I>);
+
+/// The list contains the flag `F`, at the index `I` (which is inferred, never named).
+pub trait Has<F, I> {}
+
+impl<F, Rest> Has<F, Here> for With<F, Rest> {}
+
+impl<F, G, Rest, I> Has<F, There<I>> for With<G, Rest> where Rest: Has<F, I> {}
+
+/// The searcher with the requested flags `L` in its type, see [`EverythingSearcher::checked`].
+///
+/// It derefs to the [`EverythingSearcher`] for the other search states. The request flags set
+/// there are replaced by `L` when querying.
+pub struct CheckedSearcher<'s, 'a, L> {
+    searcher: &'s mut EverythingSearcher<'a>,
+    _requests: PhantomData<L>,
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Start the checked API with no flag requested, see the [`checked`](self) module.
+    pub fn checked(&mut self) -> CheckedSearcher<'_, 'a, Nil> {
+        CheckedSearcher {
+            searcher: self,
+            _requests: PhantomData,
+        }
+    }
+}
+
+impl<'s, 'a, L: RequestList> CheckedSearcher<'s, 'a, L> {
+    /// Request the flag `F` too.
+    pub fn request<F: Request>(self) -> CheckedSearcher<'s, 'a, With<F, L>> {
+        CheckedSearcher {
+            searcher: self.searcher,
+            _requests: PhantomData,
+        }
+    }
+
+    /// All the flags requested so far.
+    pub fn request_flags(&self) -> RequestFlags {
+        L::FLAGS
+    }
+
+    /// Fail if Everything did not answer all the requested flags (e.g. the Everything before
+    /// 1.4.1 only answers the file name and the path).
+    fn check<'b>(results: EverythingResults<'b>) -> Result<CheckedResults<'b, L>> {
+        let missing = L::FLAGS - results.request_flags();
+        if missing.is_empty() {
+            Ok(CheckedResults {
+                results,
+                _requests: PhantomData,
+            })
+        } else {
+            Err(InvalidRequestError::RequestFlagsNotSet(missing).into())
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    /// Query with the requested flags, and check that all of them are in the results.
+    pub fn query(&mut self) -> Result<CheckedResults<'_, L>> {
+        self.searcher.set_request_flags(L::FLAGS);
+        Self::check(self.searcher.query())
+    }
+
+    #[cfg(feature = "async")]
+    /// Query with the requested flags, and check that all of them are in the results.
+    pub async fn query(&mut self) -> Result<CheckedResults<'_, L>> {
+        self.searcher.set_request_flags(L::FLAGS);
+        Self::check(self.searcher.query().await)
+    }
+}
+
+impl<'a, L> Deref for CheckedSearcher<'_, 'a, L> {
+    type Target = EverythingSearcher<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.searcher
+    }
+}
+
+impl<'a, L> DerefMut for CheckedSearcher<'_, 'a, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.searcher
+    }
+}
+
+/// The results with all the flags in `L` available.
+pub struct CheckedResults<'a, L> {
+    results: EverythingResults<'a>,
+    _requests: PhantomData<L>,
+}
+
+impl<'a, L> CheckedResults<'a, L> {
+    pub fn iter(&self) -> impl Iterator<Item = CheckedItem<'a, L>> {
+        self.results.iter().map(CheckedItem::new)
+    }
+
+    pub fn at(&self, index: u32) -> Option<CheckedItem<'a, L>> {
+        self.results.at(index).map(CheckedItem::new)
+    }
+
+    /// The dynamic results, for the rest of the API.
+    pub fn as_results(&self) -> &EverythingResults<'a> {
+        &self.results
+    }
+
+    pub fn into_results(self) -> EverythingResults<'a> {
+        self.results
+    }
+}
+
+impl<'a, L> Deref for CheckedResults<'a, L> {
+    type Target = EverythingResults<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.results
+    }
+}
+
+/// An item whose getters of the flags in `L` can not fail.
+pub struct CheckedItem<'a, L> {
+    item: EverythingItem<'a>,
+    _requests: PhantomData<L>,
+}
+
+impl<'a, L> CheckedItem<'a, L> {
+    fn new(item: EverythingItem<'a>) -> Self {
+        CheckedItem {
+            item,
+            _requests: PhantomData,
+        }
+    }
+
+    /// The value of the requested flag `F`, like `item.get::<Size, _>()`.
+    pub fn get<F: Request, I>(&self) -> F::Output
+    where
+        L: Has<F, I>,
+    {
+        F::get(&self.item)
+    }
+
+    pub fn index(&self) -> u32 {
+        self.item.index()
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.item.is_file()
+    }
+
+    pub fn is_folder(&self) -> bool {
+        self.item.is_folder()
+    }
+
+    pub fn is_volume(&self) -> bool {
+        self.item.is_volume()
+    }
+
+    /// The dynamic item, for the rest of the API.
+    pub fn as_item(&self) -> &EverythingItem<'a> {
+        &self.item
+    }
+}
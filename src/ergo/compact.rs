@@ -0,0 +1,420 @@
+//! The compact storage of huge snapshots, where the folders are interned and shared by the
+//! items in them.
+//!
+//! An [`OwnedResults`] keeps the name, the path and the full path of each item as its own
+//! strings, besides all the other columns as [`ItemData`]. The [`CompactResults`] keeps each
+//! distinct folder once, and only the name and the common columns per item (the rare ones
+//! boxed aside), so a snapshot of millions of items (most of them share the folders) takes
+//! several times less memory. The full paths are joined on demand.
+//!
+//! Nothing is lost, [`CompactResults::to_owned_results`] gives back the same items, with
+//! their original indices and all the columns.
+//!
+//! The [`OwnedResults`] is left as is, since its items are exposed as `&[ItemData]`, convert
+//! by [`OwnedResults::into_compact`] to keep the large snapshots around, such as in a cache.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut options = SearchOptions::new("");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+//!     | RequestFlags::EVERYTHING_REQUEST_SIZE;
+//! let compact = spawn_query(options).join().unwrap()?.into_compact();
+//! println!("{} items in {} folders", compact.len(), compact.folder_count());
+//! for item in compact.iter().filter(|item| item.size() > Some(1 << 30)) {
+//!     println!("{}", item.full_path().display());
+//! }
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use super::{ItemData, ItemKind, OwnedResults, RequestFlags, SearchOptions, SortType};
+
+/// The snapshot with the folders interned, see the [module](self) docs.
+#[derive(Clone, Debug, Default)]
+pub struct CompactResults {
+    folders: Vec<Box<Path>>,
+    items: Vec<Entry>,
+    request_flags: RequestFlags,
+    sort_type: SortType,
+    total: u32,
+    options: SearchOptions,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    /// The index in the results when it was copied, see [`ItemData::index`].
+    index: u32,
+    kind: ItemKind,
+    request_flags: RequestFlags,
+    /// Which of the name, the path and the full path the item has, by the `HAS_*` bits.
+    names: u8,
+    /// The index in `folders`.
+    folder: u32,
+    name: Box<OsStr>,
+    size: Option<u64>,
+    date_created: Option<u64>,
+    date_modified: Option<u64>,
+    date_accessed: Option<u64>,
+    attributes: Option<u32>,
+    /// The rare columns, `None` if the item has none of them.
+    rare: Option<Box<Rare>>,
+}
+
+const HAS_FILENAME: u8 = 1;
+const HAS_PATH: u8 = 1 << 1;
+const HAS_FULL_PATH: u8 = 1 << 2;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Rare {
+    /// The full path if not the same as the one joined by the folder and the name.
+    full_path: Option<PathBuf>,
+    extension: Option<OsString>,
+    file_list_filename: Option<OsString>,
+    run_count: Option<u32>,
+    date_run: Option<u64>,
+    date_recently_changed: Option<u64>,
+    highlighted_filename: Option<OsString>,
+    highlighted_path: Option<OsString>,
+    highlighted_full_path_and_filename: Option<OsString>,
+}
+
+/// An item of the [`CompactResults`], borrowing the strings from it.
+#[derive(Clone, Copy, Debug)]
+pub struct CompactItem<'a> {
+    results: &'a CompactResults,
+    at: usize,
+}
+
+impl OwnedResults {
+    /// Convert into the [`CompactResults`] with the folders interned, all the columns are kept.
+    pub fn into_compact(self) -> CompactResults {
+        let request_flags = self.request_flags();
+        let (sort_type, total, options) = (self.sort_type(), self.total(), self.options().clone());
+        let mut folders = Vec::new();
+        let mut interned: HashMap<PathBuf, u32> = HashMap::new();
+        let items = self
+            .into_items()
+            .into_iter()
+            .map(|data| {
+                let names = [
+                    (data.filename.is_some(), HAS_FILENAME),
+                    (data.path.is_some(), HAS_PATH),
+                    (data.full_path.is_some(), HAS_FULL_PATH),
+                ]
+                .into_iter()
+                .filter(|(has, _)| *has)
+                .fold(0, |names, (_, bit)| names | bit);
+                let (folder, name) = split(&data);
+                let full_path = data
+                    .full_path
+                    .filter(|full_path| *full_path != join(&folder, &name));
+                let folder = *interned.entry(folder).or_insert_with_key(|folder| {
+                    folders.push(folder.clone().into_boxed_path());
+                    (folders.len() - 1) as u32
+                });
+                let rare = Rare {
+                    full_path,
+                    extension: data.extension,
+                    file_list_filename: data.file_list_filename,
+                    run_count: data.run_count,
+                    date_run: data.date_run,
+                    date_recently_changed: data.date_recently_changed,
+                    highlighted_filename: data.highlighted_filename,
+                    highlighted_path: data.highlighted_path,
+                    highlighted_full_path_and_filename: data.highlighted_full_path_and_filename,
+                };
+                Entry {
+                    index: data.index,
+                    kind: data.kind,
+                    request_flags: data.request_flags,
+                    names,
+                    folder,
+                    name: name.into_boxed_os_str(),
+                    size: data.size,
+                    date_created: data.date_created,
+                    date_modified: data.date_modified,
+                    date_accessed: data.date_accessed,
+                    attributes: data.attributes,
+                    rare: (rare != Rare::default()).then(|| Box::new(rare)),
+                }
+            })
+            .collect();
+        CompactResults {
+            folders,
+            items,
+            request_flags,
+            sort_type,
+            total,
+            options,
+        }
+    }
+}
+
+/// The folder and the name of the item, from the path and the file name if requested, or
+/// else from the full path. Empty if neither.
+fn split(data: &ItemData) -> (PathBuf, OsString) {
+    let (parent, name) = match &data.full_path {
+        Some(full_path) => match (full_path.parent(), full_path.file_name()) {
+            (Some(parent), Some(name)) => (parent.to_path_buf(), name.to_os_string()),
+            // The volume like `C:`, without the parent.
+            _ => (PathBuf::new(), full_path.as_os_str().to_os_string()),
+        },
+        None => (PathBuf::new(), OsString::new()),
+    };
+    (
+        data.path.clone().unwrap_or(parent),
+        data.filename.clone().unwrap_or(name),
+    )
+}
+
+/// Join the folder and the name, with the `\` after the drive like `C:` too.
+fn join(folder: &Path, name: &OsStr) -> PathBuf {
+    if folder.as_os_str().is_empty() {
+        return PathBuf::from(name);
+    }
+    let mut full_path = folder.as_os_str().to_os_string();
+    if folder.as_os_str().encode_wide().last() != Some(u16::from(b'\\')) {
+        full_path.push("\\");
+    }
+    full_path.push(name);
+    full_path.into()
+}
+
+impl CompactResults {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The number of the distinct folders, which the items share.
+    pub fn folder_count(&self) -> usize {
+        self.folders.len()
+    }
+
+    /// The request flags of the query, see [`OwnedResults::request_flags`].
+    pub fn request_flags(&self) -> RequestFlags {
+        self.request_flags
+    }
+
+    pub fn sort_type(&self) -> SortType {
+        self.sort_type
+    }
+
+    /// The number of all the results of the query, see [`OwnedResults::total`].
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// The item at the position `at`, not by [`CompactItem::index`].
+    pub fn get(&self, at: usize) -> Option<CompactItem<'_>> {
+        (at < self.items.len()).then_some(CompactItem { results: self, at })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CompactItem<'_>> + '_ {
+        (0..self.items.len()).map(move |at| CompactItem { results: self, at })
+    }
+
+    /// Convert back into the [`OwnedResults`], the same as the one converted from.
+    pub fn to_owned_results(&self) -> OwnedResults {
+        OwnedResults::from_parts(
+            self.iter().map(|item| item.to_data()).collect(),
+            self.request_flags,
+            self.sort_type,
+            self.total,
+            self.options.clone(),
+        )
+    }
+}
+
+impl<'a> CompactItem<'a> {
+    fn entry(&self) -> &'a Entry {
+        &self.results.items[self.at]
+    }
+
+    /// The index in the results when it was copied, the same as [`ItemData::index`].
+    pub fn index(&self) -> u32 {
+        self.entry().index
+    }
+
+    pub fn kind(&self) -> ItemKind {
+        self.entry().kind
+    }
+
+    /// The file name, or the volume like `C:`.
+    pub fn name(&self) -> &'a OsStr {
+        &self.entry().name
+    }
+
+    /// The folder containing the item, the same as [`ItemData::path`] (empty for the
+    /// volumes).
+    pub fn parent(&self) -> &'a Path {
+        &self.results.folders[self.entry().folder as usize]
+    }
+
+    /// Join the folder and the name, with the `\` after the drive like `C:` too, or the full
+    /// path as copied if different.
+    pub fn full_path(&self) -> PathBuf {
+        match self.rare().and_then(|rare| rare.full_path.as_ref()) {
+            Some(full_path) => full_path.clone(),
+            None => join(self.parent(), self.name()),
+        }
+    }
+
+    fn rare(&self) -> Option<&'a Rare> {
+        self.entry().rare.as_deref()
+    }
+
+    pub fn size(&self) -> Option<u64> {
+        self.entry().size
+    }
+
+    pub fn date_created(&self) -> Option<u64> {
+        self.entry().date_created
+    }
+
+    pub fn date_modified(&self) -> Option<u64> {
+        self.entry().date_modified
+    }
+
+    pub fn date_accessed(&self) -> Option<u64> {
+        self.entry().date_accessed
+    }
+
+    pub fn attributes(&self) -> Option<u32> {
+        self.entry().attributes
+    }
+
+    /// Copy out as the [`ItemData`], the same as the one converted from.
+    pub fn to_data(&self) -> ItemData {
+        let entry = self.entry();
+        let has = |bit| entry.names & bit != 0;
+        let rare = self.rare().cloned().unwrap_or_default();
+        ItemData {
+            index: entry.index,
+            kind: entry.kind,
+            request_flags: entry.request_flags,
+            filename: has(HAS_FILENAME).then(|| entry.name.to_os_string()),
+            path: has(HAS_PATH).then(|| self.parent().to_path_buf()),
+            full_path: has(HAS_FULL_PATH).then(|| self.full_path()),
+            extension: rare.extension,
+            size: entry.size,
+            date_created: entry.date_created,
+            date_modified: entry.date_modified,
+            date_accessed: entry.date_accessed,
+            attributes: entry.attributes,
+            file_list_filename: rare.file_list_filename,
+            run_count: rare.run_count,
+            date_run: rare.date_run,
+            date_recently_changed: rare.date_recently_changed,
+            highlighted_filename: rare.highlighted_filename,
+            highlighted_path: rare.highlighted_path,
+            highlighted_full_path_and_filename: rare.highlighted_full_path_and_filename,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(index: u32, full_path: &str) -> ItemData {
+        let full_path = PathBuf::from(full_path);
+        ItemData {
+            index,
+            kind: ItemKind::File,
+            request_flags: RequestFlags::all(),
+            filename: full_path.file_name().map(OsStr::to_os_string),
+            path: full_path.parent().map(Path::to_path_buf),
+            full_path: Some(full_path),
+            size: Some(u64::from(index) * 1024),
+            date_modified: Some(133_000_000_000_000_000),
+            attributes: Some(0x20),
+            ..Default::default()
+        }
+    }
+
+    fn round_trip(items: Vec<ItemData>) {
+        let results = OwnedResults::from_parts(
+            items.clone(),
+            RequestFlags::all(),
+            SortType::EVERYTHING_SORT_SIZE_DESCENDING,
+            1000,
+            SearchOptions::new("*.txt"),
+        );
+        let compact = results.into_compact();
+        let owned = compact.to_owned_results();
+        assert_eq!(owned.items(), &items[..]);
+        assert_eq!(owned.request_flags(), RequestFlags::all());
+        assert_eq!(owned.sort_type(), SortType::EVERYTHING_SORT_SIZE_DESCENDING);
+        assert_eq!(owned.total(), 1000);
+        assert_eq!(owned.options(), &SearchOptions::new("*.txt"));
+    }
+
+    #[test]
+    fn round_trip_keeps_the_indices_and_the_folders_shared() {
+        let items = vec![
+            item(100, r"C:\Users\a.txt"),
+            item(7, r"C:\Users\b.txt"),
+            item(42, r"D:\c.txt"),
+        ];
+        let compact = OwnedResults::from_parts(
+            items.clone(),
+            RequestFlags::all(),
+            SortType::default(),
+            3,
+            SearchOptions::new(""),
+        )
+        .into_compact();
+        assert_eq!(compact.folder_count(), 2);
+        let indices: Vec<_> = compact.iter().map(|item| item.index()).collect();
+        assert_eq!(indices, [100, 7, 42]);
+        assert_eq!(compact.get(2).unwrap().parent(), Path::new(r"D:\"));
+        round_trip(items);
+    }
+
+    #[test]
+    fn round_trip_keeps_all_the_columns() {
+        let mut rare = item(1, r"C:\Windows\notepad.exe");
+        rare.extension = Some("exe".into());
+        rare.file_list_filename = Some(r"C:\list.efu".into());
+        rare.run_count = Some(3);
+        rare.date_run = Some(133_000_000_000_000_001);
+        rare.date_recently_changed = Some(133_000_000_000_000_002);
+        rare.highlighted_filename = Some("*note*pad.exe".into());
+        rare.highlighted_path = Some(r"C:\*Win*dows".into());
+        rare.highlighted_full_path_and_filename = Some(r"C:\*Win*dows\notepad.exe".into());
+        round_trip(vec![rare]);
+    }
+
+    #[test]
+    fn round_trip_keeps_the_missing_and_odd_names() {
+        let mut volume = item(0, "C:");
+        volume.kind = ItemKind::Volume;
+        volume.path = Some(PathBuf::new());
+        let mut only_name = item(1, r"C:\a.txt");
+        only_name.path = None;
+        only_name.full_path = None;
+        let mut only_full_path = item(2, r"C:\b.txt");
+        only_full_path.filename = None;
+        only_full_path.path = None;
+        let mut odd = item(3, r"C:\c.txt");
+        odd.full_path = Some(r"\\?\C:\c.txt".into());
+        let none = ItemData {
+            index: 4,
+            ..Default::default()
+        };
+        round_trip(vec![volume, only_name, only_full_path, odd, none]);
+    }
+}
@@ -0,0 +1,386 @@
+//! Owned data copied out of the results, which is free from the global shared states.
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+
+use super::{
+    access, with_full_path, EverythingItem, EverythingResults, FileAttributes, RequestFlags, Result,
+};
+use crate::raw;
+
+/// What a result item is in the Everything index.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ItemKind {
+    #[default]
+    File,
+    Folder,
+    /// The root folder of a volume, such as `C:`.
+    Volume,
+}
+
+impl ItemKind {
+    pub fn is_file(&self) -> bool {
+        *self == ItemKind::File
+    }
+
+    /// Volume is a folder too.
+    pub fn is_folder(&self) -> bool {
+        *self != ItemKind::File
+    }
+
+    pub fn is_volume(&self) -> bool {
+        *self == ItemKind::Volume
+    }
+}
+
+/// The owned copy of the data of an [`EverythingItem`].
+///
+/// Each field is `Some(_)` only if the corresponding [`RequestFlags`] is available in the
+/// results, which is the same as the `Ok(_)` of the getter in [`EverythingItem`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ItemData {
+    /// The index in the results when it was copied.
+    pub index: u32,
+    pub kind: ItemKind,
+    /// The request flags available when it was copied.
+    pub request_flags: RequestFlags,
+    pub filename: Option<OsString>,
+    pub path: Option<PathBuf>,
+    /// From `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`, or joined by the
+    /// `EVERYTHING_REQUEST_PATH` and `EVERYTHING_REQUEST_FILE_NAME`.
+    pub full_path: Option<PathBuf>,
+    pub extension: Option<OsString>,
+    pub size: Option<u64>,
+    pub date_created: Option<u64>,
+    pub date_modified: Option<u64>,
+    pub date_accessed: Option<u64>,
+    pub attributes: Option<u32>,
+    pub file_list_filename: Option<OsString>,
+    pub run_count: Option<u32>,
+    pub date_run: Option<u64>,
+    pub date_recently_changed: Option<u64>,
+    pub highlighted_filename: Option<OsString>,
+    pub highlighted_path: Option<OsString>,
+    pub highlighted_full_path_and_filename: Option<OsString>,
+}
+
+/// The owned item in an [`OwnedResults`](super::OwnedResults), see
+/// [`EverythingResults::into_owned`](super::EverythingResults::into_owned).
+pub type OwnedItem = ItemData;
+
+impl ItemData {
+    pub fn is_file(&self) -> bool {
+        self.kind.is_file()
+    }
+
+    pub fn is_folder(&self) -> bool {
+        self.kind.is_folder()
+    }
+
+    pub fn is_volume(&self) -> bool {
+        self.kind.is_volume()
+    }
+
+    /// The [`ItemData::attributes`] typed.
+    pub fn file_attributes(&self) -> Option<FileAttributes> {
+        self.attributes.map(FileAttributes::from_bits_retain)
+    }
+
+    /// The same as [`EverythingItem::is_reparse_point`], `false` if no attributes.
+    pub fn is_reparse_point(&self) -> bool {
+        self.file_attributes()
+            .is_some_and(|attr| attr.is_reparse_point())
+    }
+}
+
+impl<'a> EverythingItem<'a> {
+    /// Copy all the available data (depends on the request flags) of this item out.
+    pub fn to_data(&self) -> ItemData {
+        let mut data = ItemData::default();
+        data.fill_from(self);
+        data
+    }
+
+    /// Read the full path name into `buf` in UTF-16 (without the null terminator), reusing
+    /// its allocation, and return the length.
+    ///
+    /// It needs `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`, or both of
+    /// `EVERYTHING_REQUEST_PATH` and `EVERYTHING_REQUEST_FILE_NAME`, like
+    /// [`EverythingItem::filepath`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.rs");
+    /// let mut buf = Vec::new();
+    /// for item in searcher.query() {
+    ///     item.full_path_name_into(&mut buf)?;
+    ///     // ... use the buf, no allocation after the longest path so far ...
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn full_path_name_into(&self, buf: &mut Vec<u16>) -> Result<usize> {
+        if !self
+            .request_flags
+            .contains(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+        {
+            self.need_flags_set(
+                RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            )?;
+        }
+        assert!(read_full_path(self.index, buf), "the flags are checked");
+        Ok(buf.len())
+    }
+
+    /// Read the full path name into `out`, reusing both allocations of `out` and `buf`, see
+    /// [`EverythingItem::full_path_name_into`].
+    pub fn full_path_name_into_os_string(
+        &self,
+        out: &mut OsString,
+        buf: &mut Vec<u16>,
+    ) -> Result<()> {
+        self.full_path_name_into(buf)?;
+        assign_wide_os(out, buf);
+        Ok(())
+    }
+}
+
+impl ItemData {
+    /// Refill with the data of `item`, the allocation of `full_path` is reused if possible.
+    fn fill_from(&mut self, item: &EverythingItem<'_>) {
+        let has_full_path = item
+            .request_flags
+            .contains(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+            || item.request_flags.contains(
+                RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            );
+        self.index = item.index;
+        self.kind = if item.is_volume() {
+            ItemKind::Volume
+        } else if item.is_folder() {
+            ItemKind::Folder
+        } else {
+            ItemKind::File
+        };
+        self.request_flags = item.request_flags;
+        let full_path = has_full_path
+            .then(|| {
+                let len = raw::Everything_GetResultFullPathNameSizeHint(&access(), item.index)?;
+                with_full_path(item.scratch, item.index, u32::from(len) as usize, |wide| {
+                    assign_wide(&mut self.full_path, wide)
+                })
+            })
+            .flatten();
+        if full_path.is_none() {
+            self.full_path = None;
+        }
+        self.filename = item.filename().ok();
+        self.path = item.path().ok();
+        self.extension = item.extension().ok();
+        self.size = item.size().ok();
+        self.date_created = item.date_created().ok();
+        self.date_modified = item.date_modified().ok();
+        self.date_accessed = item.date_accessed().ok();
+        self.attributes = item.attributes().ok().map(|attr| attr.bits());
+        self.file_list_filename = item.file_list_filename().ok();
+        self.run_count = item.run_count().ok();
+        self.date_run = item.date_run().ok();
+        self.date_recently_changed = item.date_recently_changed().ok();
+        self.highlighted_filename = item.highlighted_filename().ok();
+        self.highlighted_path = item.highlighted_path().ok();
+        self.highlighted_full_path_and_filename = item.highlighted_full_path_and_filename().ok();
+    }
+}
+
+impl<'a> EverythingResults<'a> {
+    /// Clear the `out` and refill it with all the visible results, reusing the allocations
+    /// of the `out` itself and the items in it.
+    pub(super) fn fill_data(&self, out: &mut Vec<ItemData>) {
+        out.truncate(self.len() as usize);
+        for item in self.iter() {
+            match out.get_mut(item.index as usize) {
+                Some(data) => data.fill_from(&item),
+                None => {
+                    let mut data = ItemData::default();
+                    data.fill_from(&item);
+                    out.push(data);
+                }
+            }
+        }
+    }
+}
+
+/// The record of [`EverythingResults::collect_into`], the same as [`ItemData`].
+pub type ResultRecord = ItemData;
+
+impl<'a> EverythingResults<'a> {
+    /// Append all the visible results to `out`, with only the `fields` (and the ones not
+    /// requested by the query are skipped too), for the large results.
+    ///
+    /// It walks the results once and reads each field by the SDK directly, without the checks
+    /// and the `Result`s of the getters on [`EverythingItem`], and the buffer of the full path
+    /// is reused for all the items.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.dll").set_request_flags(
+    ///     RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+    ///         | RequestFlags::EVERYTHING_REQUEST_SIZE,
+    /// );
+    /// let mut records = Vec::new();
+    /// searcher
+    ///     .query()
+    ///     .collect_into(&mut records, RequestFlags::EVERYTHING_REQUEST_SIZE);
+    /// let total: u64 = records.iter().filter_map(|r| r.size).sum();
+    /// ```
+    pub fn collect_into(&self, out: &mut Vec<ResultRecord>, fields: RequestFlags) {
+        let access = access();
+        let mut flags = self.request_flags() & fields;
+        // The full path can be read by the path and the file name too, like `fill_from`.
+        if fields.contains(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME)
+            && self.request_flags().contains(
+                RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            )
+        {
+            flags |= RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME;
+        }
+        let has = |flag| flags.contains(flag);
+        out.reserve(self.len() as usize);
+        for index in 0..self.len() {
+            let mut data = ItemData {
+                index,
+                request_flags: flags,
+                ..Default::default()
+            };
+            data.kind = if raw::Everything_IsVolumeResult(&access, index) {
+                ItemKind::Volume
+            } else if raw::Everything_IsFolderResult(&access, index) {
+                ItemKind::Folder
+            } else {
+                ItemKind::File
+            };
+            if has(RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME) {
+                if let Some(len) = raw::Everything_GetResultFullPathNameSizeHint(&access, index) {
+                    with_full_path(self.scratch, index, u32::from(len) as usize, |wide| {
+                        assign_wide(&mut data.full_path, wide)
+                    });
+                }
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_FILE_NAME) {
+                data.filename = raw::Everything_GetResultFileName(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_PATH) {
+                data.path = raw::Everything_GetResultPath(&access, index).map(PathBuf::from);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_EXTENSION) {
+                data.extension = raw::Everything_GetResultExtension(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_SIZE) {
+                // -1 for the folders without the size, see `EverythingItem::size`.
+                data.size = raw::Everything_GetResultSize(&access, index)
+                    .map(|size| u64::try_from(size).unwrap_or(0));
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED) {
+                data.date_created = raw::Everything_GetResultDateCreated(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED) {
+                data.date_modified = raw::Everything_GetResultDateModified(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED) {
+                data.date_accessed = raw::Everything_GetResultDateAccessed(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES) {
+                data.attributes =
+                    raw::Everything_GetResultAttributes(&access, index).map(|attr| attr.bits());
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME) {
+                data.file_list_filename = raw::Everything_GetResultFileListFileName(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT) {
+                data.run_count = Some(raw::Everything_GetResultRunCount(&access, index));
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_DATE_RUN) {
+                data.date_run = raw::Everything_GetResultDateRun(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED) {
+                data.date_recently_changed =
+                    raw::Everything_GetResultDateRecentlyChanged(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME) {
+                data.highlighted_filename =
+                    raw::Everything_GetResultHighlightedFileName(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_PATH) {
+                data.highlighted_path = raw::Everything_GetResultHighlightedPath(&access, index);
+            }
+            if has(RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME) {
+                data.highlighted_full_path_and_filename =
+                    raw::Everything_GetResultHighlightedFullPathAndFileName(&access, index);
+            }
+            out.push(data);
+        }
+    }
+}
+
+/// Read the full path name (without the null terminator) into `buf`, return `false` if failed.
+fn read_full_path(index: u32, buf: &mut Vec<u16>) -> bool {
+    let access = access();
+    buf.clear();
+    let Some(size_hint) = raw::Everything_GetResultFullPathNameSizeHint(&access, index) else {
+        return false;
+    };
+    buf.resize(u32::from(size_hint) as usize, 0);
+    match raw::Everything_GetResultFullPathName(&access, index, buf) {
+        Some(n_wchar) => {
+            buf.truncate(u32::from(n_wchar) as usize);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Decode the UTF-16 `wide` into the path in `slot`, without re-allocation if its capacity is
+/// enough. (Fall back to a new allocation if there is any unpaired surrogate in `wide`)
+fn assign_wide(slot: &mut Option<PathBuf>, wide: &[u16]) {
+    assign_wide_os(
+        slot.get_or_insert_with(PathBuf::new).as_mut_os_string(),
+        wide,
+    );
+}
+
+/// The same as [`assign_wide`], into the `OsString`.
+fn assign_wide_os(path: &mut OsString, wide: &[u16]) {
+    path.clear();
+    let mut utf8 = [0; 4];
+    for c in char::decode_utf16(wide.iter().copied()) {
+        match c {
+            Ok(c) => path.push(c.encode_utf8(&mut utf8)),
+            Err(_) => {
+                *path = OsString::from_wide(wide);
+                break;
+            }
+        }
+    }
+}
+
+impl<'a> From<&EverythingItem<'a>> for ItemData {
+    fn from(item: &EverythingItem<'a>) -> Self {
+        item.to_data()
+    }
+}
+
+impl<'a> From<EverythingItem<'a>> for ItemData {
+    fn from(item: EverythingItem<'a>) -> Self {
+        item.to_data()
+    }
+}
@@ -0,0 +1,118 @@
+//! Remove the same items seen twice, and find the duplicate files by their sizes and contents.
+//!
+//! The same item comes back more than once when the results of several queries are merged,
+//! or from the queries of the different request flags.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::Path;
+
+use super::{ItemData, OwnedResults};
+
+/// The identity of the item, the full path in lowercase, as the paths are case-insensitive
+/// on Windows. `None` if neither the full path nor the path and the file name are requested.
+fn identity(data: &ItemData) -> Option<String> {
+    let full_path = match (&data.full_path, &data.path, &data.filename) {
+        (Some(full_path), _, _) => full_path.clone(),
+        (None, Some(path), Some(filename)) => path.join(filename),
+        _ => return None,
+    };
+    Some(full_path.to_string_lossy().to_lowercase())
+}
+
+impl OwnedResults {
+    /// Remove the items of the same path (case-insensitively), keeping the first one, and
+    /// return the number removed. The items without a path are all kept.
+    pub fn dedup_by_path(&mut self) -> usize {
+        let before = self.items.len();
+        let mut seen = HashSet::new();
+        self.items.retain(|data| match identity(data) {
+            Some(identity) => seen.insert(identity),
+            None => true,
+        });
+        before - self.items.len()
+    }
+
+    /// Append the items of `other` not in this one, by [`OwnedResults::dedup_by_path`].
+    pub fn merge(&mut self, other: OwnedResults) {
+        self.items.extend(other.into_items());
+        self.dedup_by_path();
+    }
+
+    /// Group the non-empty files of the same size, the groups of one file are left out, and
+    /// the largest groups (by the size) come first.
+    ///
+    /// They are only the candidates, confirm them by [`DuplicateGroup::split_by_content`]. It
+    /// needs `EVERYTHING_REQUEST_SIZE`, and the same paths should be removed first by
+    /// [`OwnedResults::dedup_by_path`].
+    pub fn dedup_candidates(&self) -> Vec<DuplicateGroup> {
+        let mut by_size: HashMap<u64, Vec<ItemData>> = HashMap::new();
+        for data in self.items.iter().filter(|data| data.is_file()) {
+            match data.size {
+                Some(size) if size > 0 => by_size.entry(size).or_default().push(data.clone()),
+                _ => {}
+            }
+        }
+        let mut groups: Vec<_> = by_size
+            .into_iter()
+            .filter(|(_, items)| items.len() > 1)
+            .map(|(size, items)| DuplicateGroup { size, items })
+            .collect();
+        groups.sort_by(|a, b| b.size.cmp(&a.size));
+        groups
+    }
+}
+
+/// The files of the same size, see [`OwnedResults::dedup_candidates`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub items: Vec<ItemData>,
+}
+
+impl DuplicateGroup {
+    /// The space wasted by the copies, all but one of the files.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.items.len() as u64).saturating_sub(1)
+    }
+
+    /// Read the files and split the group by the hash of their contents, the groups of one
+    /// file are left out. It needs the full paths.
+    ///
+    /// The hash is NOT cryptographic, compare the bytes before deleting anything. The files
+    /// failed to read (gone, or locked) are skipped.
+    pub fn split_by_content(self) -> Vec<DuplicateGroup> {
+        let mut by_hash: HashMap<u64, Vec<ItemData>> = HashMap::new();
+        for data in self.items {
+            let Some(path) = &data.full_path else {
+                continue;
+            };
+            if let Ok(hash) = hash_file(path) {
+                by_hash.entry(hash).or_default().push(data);
+            }
+        }
+        by_hash
+            .into_values()
+            .filter(|items| items.len() > 1)
+            .map(|items| DuplicateGroup {
+                size: self.size,
+                items,
+            })
+            .collect()
+    }
+}
+
+fn hash_file(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        match file.read(&mut buf)? {
+            0 => return Ok(hasher.finish()),
+            n => hasher.write(&buf[..n]),
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! The Everything File List (`.efu`), to re-import the results into Everything (File > Open
+//! File List, or `filelist:`) or to share them with other machines.
+//!
+//! An EFU is a CSV in UTF-8, with the columns of full path, size, date modified, date created
+//! and attributes, where the dates are the raw FILETIME numbers and the attributes are the raw
+//! `FILE_ATTRIBUTE_*` number.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut options = SearchOptions::new("*.iso");
+//! options.request_flags = efu::REQUEST_FLAGS;
+//! let results = spawn_query(options).join().unwrap().unwrap();
+//! let file = std::fs::File::create("isos.efu").unwrap();
+//! efu::write_efu(&results, std::io::BufWriter::new(file)).unwrap();
+//! ```
+//!
+//! And read back by [`read_efu`] or [`load_efu`], without Everything.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error as ThisError;
+use windows::Win32::Storage::FileSystem::FILE_ATTRIBUTE_DIRECTORY;
+
+use super::{ItemData, ItemKind, OwnedResults, RequestFlags, SearchOptions, SortType};
+
+/// The header line, the same as Everything writes.
+pub const HEADER: &str = "Filename,Size,Date Modified,Date Created,Attributes";
+
+/// The request flags of all the columns in an EFU.
+pub const REQUEST_FLAGS: RequestFlags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+    .union(RequestFlags::EVERYTHING_REQUEST_SIZE)
+    .union(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED)
+    .union(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED)
+    .union(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES);
+
+/// The line ending of the EFU written by Everything.
+const CRLF: &str = "\r\n";
+
+/// Write the results as an EFU into `writer`, see [`REQUEST_FLAGS`] to query all the columns.
+///
+/// The columns not requested are left empty, which Everything reads as unknown. The folders
+/// without the attributes requested get `FILE_ATTRIBUTE_DIRECTORY`, so they are still folders
+/// after importing. The items without the full path (neither the full path nor the path and
+/// the file name requested) are skipped.
+///
+/// Fail with [`io::ErrorKind::InvalidData`] if a full path is not valid Unicode (an unpaired
+/// surrogate), which can not be written in UTF-8.
+pub fn write_efu<W: Write>(results: &OwnedResults, mut writer: W) -> io::Result<()> {
+    write!(writer, "{HEADER}{CRLF}")?;
+    for data in results {
+        write_row(data, &mut writer)?;
+    }
+    writer.flush()
+}
+
+fn write_row(data: &ItemData, writer: &mut impl Write) -> io::Result<()> {
+    let Some(full_path) = &data.full_path else {
+        return Ok(());
+    };
+    let full_path = full_path.to_str().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("the path {full_path:?} is not valid Unicode"),
+        )
+    })?;
+    let number = |n: Option<u64>| n.map(|n| n.to_string()).unwrap_or_default();
+    let attributes = match data.attributes {
+        Some(attributes) => Some(attributes),
+        None if data.is_folder() => Some(FILE_ATTRIBUTE_DIRECTORY.0),
+        None => None,
+    };
+    write!(
+        writer,
+        "\"{}\",{},{},{},{}{CRLF}",
+        full_path.replace('"', "\"\""),
+        number(data.size),
+        number(data.date_modified),
+        number(data.date_created),
+        number(attributes.map(u64::from)),
+    )
+}
+
+#[derive(ThisError, Debug)]
+pub enum EfuError {
+    #[error("failed to read the file list.")]
+    Io(#[from] io::Error),
+    #[error("no Filename column in the header.")]
+    MissingFilename,
+    #[error("the quote is not closed at line {line}.")]
+    UnclosedQuote { line: usize },
+    #[error("invalid number {value:?} of the column {column} at line {line}.")]
+    InvalidNumber {
+        line: usize,
+        column: &'static str,
+        value: String,
+    },
+}
+
+/// A column known in the header, the others are ignored.
+#[derive(Clone, Copy)]
+enum Column {
+    Filename,
+    Size,
+    DateModified,
+    DateCreated,
+    DateAccessed,
+    Attributes,
+}
+
+impl Column {
+    fn of_header(header: &str) -> Option<Self> {
+        let column = match header.trim() {
+            "Filename" => Column::Filename,
+            "Size" => Column::Size,
+            "Date Modified" => Column::DateModified,
+            "Date Created" => Column::DateCreated,
+            "Date Accessed" => Column::DateAccessed,
+            "Attributes" => Column::Attributes,
+            _ => return None,
+        };
+        Some(column)
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Column::Filename => "Filename",
+            Column::Size => "Size",
+            Column::DateModified => "Date Modified",
+            Column::DateCreated => "Date Created",
+            Column::DateAccessed => "Date Accessed",
+            Column::Attributes => "Attributes",
+        }
+    }
+
+    fn request_flag(&self) -> RequestFlags {
+        match self {
+            Column::Filename => {
+                RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+                    | RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+                    | RequestFlags::EVERYTHING_REQUEST_PATH
+                    | RequestFlags::EVERYTHING_REQUEST_EXTENSION
+            }
+            Column::Size => RequestFlags::EVERYTHING_REQUEST_SIZE,
+            Column::DateModified => RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED,
+            Column::DateCreated => RequestFlags::EVERYTHING_REQUEST_DATE_CREATED,
+            Column::DateAccessed => RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED,
+            Column::Attributes => RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+        }
+    }
+}
+
+/// Read an EFU into the results, as if they were queried with the request flags of the
+/// columns in the file. The items are in the order of the file.
+///
+/// The folders are told by `FILE_ATTRIBUTE_DIRECTORY`, so without the attributes column all
+/// the items are files. The empty cells are unknown, that is `None`.
+pub fn read_efu<R: BufRead>(reader: R) -> Result<OwnedResults, EfuError> {
+    read_rows(reader, None)
+}
+
+/// Read the EFU file at `path`, see [`read_efu`].
+///
+/// The [`ItemData::file_list_filename`] of the items is the `path`, like the results of the
+/// file lists queried from Everything.
+pub fn load_efu(path: impl AsRef<Path>) -> Result<OwnedResults, EfuError> {
+    let path = path.as_ref();
+    read_rows(BufReader::new(File::open(path)?), Some(path))
+}
+
+/// Read the rows of an EFU, with the file list filename of every item if read from a file.
+fn read_rows<R: BufRead>(reader: R, file_list: Option<&Path>) -> Result<OwnedResults, EfuError> {
+    let mut lines = reader.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, header)) => header?,
+        None => return Err(EfuError::MissingFilename),
+    };
+    // Everything writes no BOM, but the other tools may.
+    let header = header.trim_start_matches('\u{feff}');
+    let columns: Vec<_> = split_row(header)
+        .ok_or(EfuError::UnclosedQuote { line: 1 })?
+        .iter()
+        .map(|header| Column::of_header(header))
+        .collect();
+    if !columns.iter().any(|c| matches!(c, Some(Column::Filename))) {
+        return Err(EfuError::MissingFilename);
+    }
+    let mut request_flags = columns
+        .iter()
+        .flatten()
+        .fold(RequestFlags::empty(), |flags, column| {
+            flags | column.request_flag()
+        });
+    if file_list.is_some() {
+        request_flags |= RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME;
+    }
+
+    let mut items = Vec::new();
+    for (i, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = split_row(&line).ok_or(EfuError::UnclosedQuote { line: i + 1 })?;
+        let mut data = ItemData {
+            index: items.len() as u32,
+            request_flags,
+            file_list_filename: file_list.map(|path| path.as_os_str().to_owned()),
+            ..Default::default()
+        };
+        for (column, cell) in columns.iter().zip(cells) {
+            let Some(column) = column else {
+                continue;
+            };
+            let number = || -> Result<Option<u64>, EfuError> {
+                match cell.trim() {
+                    "" => Ok(None),
+                    n => n.parse().map(Some).map_err(|_| EfuError::InvalidNumber {
+                        line: i + 1,
+                        column: column.header(),
+                        value: cell.clone(),
+                    }),
+                }
+            };
+            match column {
+                Column::Filename => data.full_path = Some(PathBuf::from(&cell)),
+                Column::Size => data.size = number()?,
+                Column::DateModified => data.date_modified = number()?,
+                Column::DateCreated => data.date_created = number()?,
+                Column::DateAccessed => data.date_accessed = number()?,
+                Column::Attributes => {
+                    // Some tools write the attributes beyond u32, keep the low bits.
+                    data.attributes = number()?.map(|n| n as u32);
+                }
+            }
+        }
+        let Some(full_path) = &data.full_path else {
+            continue;
+        };
+        let is_folder = data
+            .attributes
+            .is_some_and(|attributes| attributes & FILE_ATTRIBUTE_DIRECTORY.0 != 0);
+        data.kind = match (is_folder, full_path.parent()) {
+            (true, None) => ItemKind::Volume,
+            (true, Some(_)) => ItemKind::Folder,
+            (false, _) => ItemKind::File,
+        };
+        data.filename = Some(
+            full_path
+                .file_name()
+                .map_or_else(|| full_path.as_os_str().to_owned(), OsString::from),
+        );
+        data.path = Some(
+            full_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        );
+        data.extension = Some(
+            full_path
+                .extension()
+                .map(OsString::from)
+                .unwrap_or_default(),
+        );
+        items.push(data);
+    }
+    let total = items.len() as u32;
+    Ok(OwnedResults::from_parts(
+        items,
+        request_flags,
+        SortType::default(),
+        total,
+        SearchOptions::default(),
+    ))
+}
+
+/// Split a CSV row, `None` if a quote is not closed.
+pub(super) fn split_row(line: &str) -> Option<Vec<String>> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    cells.push(cell);
+    Some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_requests_the_file_list_filename() {
+        let path = std::env::temp_dir().join("everything-sdk-load-efu.efu");
+        std::fs::write(&path, format!("{HEADER}{CRLF}\"C:\\a.txt\",1,2,3,32{CRLF}")).unwrap();
+        let results = load_efu(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let flags = RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME;
+        assert!(results.request_flags().contains(flags));
+        let data = &results.items()[0];
+        assert!(data.request_flags.contains(flags));
+        assert_eq!(data.file_list_filename.as_deref(), Some(path.as_os_str()));
+    }
+
+    #[test]
+    fn read_has_no_file_list_filename() {
+        let efu = format!("{HEADER}{CRLF}\"C:\\a.txt\",1,2,3,32{CRLF}");
+        let results = read_efu(efu.as_bytes()).unwrap();
+        let flags = RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME;
+        assert!(!results.request_flags().contains(flags));
+        assert_eq!(results.items()[0].file_list_filename, None);
+    }
+
+    #[test]
+    fn write_rejects_the_unpaired_surrogate() {
+        use std::os::windows::ffi::OsStringExt;
+
+        let mut full_path = OsString::from_wide(&[u16::from(b'a'), 0xD800]);
+        full_path.push(".txt");
+        let data = ItemData {
+            full_path: Some(PathBuf::from(full_path)),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        let err = write_row(&data, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(buf.is_empty());
+    }
+}
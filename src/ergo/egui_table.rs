@@ -0,0 +1,326 @@
+//! The results grid for [egui](https://docs.rs/egui) apps. (feature `egui`)
+//!
+//! There is no virtualized cache over the live results in this crate, the [`ResultsTableModel`]
+//! is over an [`OwnedResults`] snapshot instead, and only the visible rows are laid out by the
+//! [`egui_extras::TableBuilder`], so a snapshot of many thousands of rows is fine.
+
+use egui_extras::{Column as TableColumn, TableBuilder};
+
+use super::snapshot::Key;
+use super::{FileTime, ItemData, OwnedResults, RequestFlags};
+
+/// A column of the results table.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Column {
+    Name,
+    Path,
+    Extension,
+    Size,
+    DateCreated,
+    DateModified,
+    DateAccessed,
+    Attributes,
+    FileListFilename,
+    RunCount,
+    DateRun,
+    DateRecentlyChanged,
+}
+
+impl Column {
+    /// The columns of the data available in the results, in the order of Everything GUI.
+    pub fn from_request_flags(flags: RequestFlags) -> Vec<Column> {
+        let has_name = flags.intersects(
+            RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+                | RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+        );
+        let has_path = flags.intersects(
+            RequestFlags::EVERYTHING_REQUEST_PATH
+                | RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+        );
+        [
+            (Column::Name, has_name),
+            (Column::Path, has_path),
+            (
+                Column::Extension,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_EXTENSION),
+            ),
+            (
+                Column::Size,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_SIZE),
+            ),
+            (
+                Column::DateCreated,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_CREATED),
+            ),
+            (
+                Column::DateModified,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED),
+            ),
+            (
+                Column::DateAccessed,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED),
+            ),
+            (
+                Column::Attributes,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES),
+            ),
+            (
+                Column::FileListFilename,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME),
+            ),
+            (
+                Column::RunCount,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_RUN_COUNT),
+            ),
+            (
+                Column::DateRun,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_RUN),
+            ),
+            (
+                Column::DateRecentlyChanged,
+                flags.contains(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(column, available)| available.then_some(column))
+        .collect()
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Name => "Name",
+            Column::Path => "Path",
+            Column::Extension => "Extension",
+            Column::Size => "Size",
+            Column::DateCreated => "Date Created",
+            Column::DateModified => "Date Modified",
+            Column::DateAccessed => "Date Accessed",
+            Column::Attributes => "Attributes",
+            Column::FileListFilename => "File List Filename",
+            Column::RunCount => "Run Count",
+            Column::DateRun => "Date Run",
+            Column::DateRecentlyChanged => "Date Recently Changed",
+        }
+    }
+
+    /// The client-side sort key of this column.
+    pub fn sort_key(&self, descending: bool) -> Key {
+        let (asc, desc) = match self {
+            Column::Name => (Key::Name, Key::NameDesc),
+            Column::Path => (Key::Path, Key::PathDesc),
+            Column::Extension => (Key::Extension, Key::ExtensionDesc),
+            Column::Size => (Key::Size, Key::SizeDesc),
+            Column::DateCreated => (Key::DateCreated, Key::DateCreatedDesc),
+            Column::DateModified => (Key::DateModified, Key::DateModifiedDesc),
+            Column::DateAccessed => (Key::DateAccessed, Key::DateAccessedDesc),
+            Column::Attributes => (Key::Attributes, Key::AttributesDesc),
+            Column::FileListFilename => (Key::FileListFilename, Key::FileListFilenameDesc),
+            Column::RunCount => (Key::RunCount, Key::RunCountDesc),
+            Column::DateRun => (Key::DateRun, Key::DateRunDesc),
+            Column::DateRecentlyChanged => (Key::DateRecentlyChanged, Key::DateRecentlyChangedDesc),
+        };
+        if descending {
+            desc
+        } else {
+            asc
+        }
+    }
+
+    /// The cell text of the item, empty if the data is not available.
+    pub fn text(&self, data: &ItemData) -> String {
+        let date = |date: Option<u64>| date.map(|t| FileTime::new(t).to_string());
+        let text = match self {
+            Column::Name => data
+                .filename
+                .clone()
+                .or_else(|| Some(data.full_path.as_ref()?.file_name()?.to_os_string()))
+                .map(|name| name.to_string_lossy().into_owned()),
+            Column::Path => data
+                .path
+                .clone()
+                .or_else(|| Some(data.full_path.as_ref()?.parent()?.to_path_buf()))
+                .map(|path| path.display().to_string()),
+            Column::Extension => data
+                .extension
+                .as_ref()
+                .map(|ext| ext.to_string_lossy().into_owned()),
+            Column::Size => data.size.filter(|_| data.is_file()).map(format_size),
+            Column::DateCreated => date(data.date_created),
+            Column::DateModified => date(data.date_modified),
+            Column::DateAccessed => date(data.date_accessed),
+            Column::Attributes => data.attributes.map(format_attributes),
+            Column::FileListFilename => data
+                .file_list_filename
+                .as_ref()
+                .map(|name| name.to_string_lossy().into_owned()),
+            Column::RunCount => data.run_count.map(|n| n.to_string()),
+            Column::DateRun => date(data.date_run),
+            Column::DateRecentlyChanged => date(data.date_recently_changed),
+        };
+        text.unwrap_or_default()
+    }
+}
+
+/// What happened in the table in this frame, see [`ResultsTableModel::show`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct TableResponse {
+    /// The header clicked to sort, with the new direction (`true` for descending).
+    ///
+    /// It is already sorted on the client side if [`ResultsTableModel::set_client_sort`] is on
+    /// (by default). Otherwise it is the hook to send a new query with the native sort.
+    pub sort_requested: Option<(Column, bool)>,
+}
+
+/// The table model of the results, with the columns and the sort state.
+#[derive(Clone, Debug)]
+pub struct ResultsTableModel {
+    results: OwnedResults,
+    columns: Vec<Column>,
+    sorted_by: Option<(Column, bool)>,
+    client_sort: bool,
+    row_height: f32,
+}
+
+impl ResultsTableModel {
+    /// The columns are decided by the request flags of the results.
+    pub fn new(results: OwnedResults) -> Self {
+        let columns = Column::from_request_flags(results.request_flags());
+        ResultsTableModel {
+            results,
+            columns,
+            sorted_by: None,
+            client_sort: true,
+            row_height: 18.0,
+        }
+    }
+
+    pub fn results(&self) -> &OwnedResults {
+        &self.results
+    }
+
+    /// Replace the results (e.g. from a new query), the current client-side sort is re-applied.
+    pub fn set_results(&mut self, results: OwnedResults) {
+        self.results = results;
+        if let (Some((column, descending)), true) = (self.sorted_by, self.client_sort) {
+            self.results.sort_by_keys(&[column.sort_key(descending)]);
+        }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<Column>) -> &mut Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sort the rows on the client side when the header is clicked, default is `true`.
+    ///
+    /// Turn it off to sort by Everything instead, see [`TableResponse::sort_requested`].
+    pub fn set_client_sort(&mut self, enable: bool) -> &mut Self {
+        self.client_sort = enable;
+        self
+    }
+
+    pub fn set_row_height(&mut self, row_height: f32) -> &mut Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// The column and direction (`true` for descending) sorted by.
+    pub fn sorted_by(&self) -> Option<(Column, bool)> {
+        self.sorted_by
+    }
+
+    /// Sort the rows by the column on the client side.
+    pub fn sort_by(&mut self, column: Column, descending: bool) {
+        self.results.sort_by_keys(&[column.sort_key(descending)]);
+        self.sorted_by = Some((column, descending));
+    }
+
+    /// Show the table in `ui`, only the visible rows are laid out.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> TableResponse {
+        let mut response = TableResponse::default();
+        let mut table = TableBuilder::new(ui).striped(true).resizable(true);
+        for _ in &self.columns {
+            table = table.column(TableColumn::auto().clip(true));
+        }
+        let (columns, sorted_by, row_height) = (&self.columns, self.sorted_by, self.row_height);
+        let items = self.results.items();
+        table
+            .header(row_height + 2.0, |mut header| {
+                for &column in columns {
+                    header.col(|ui| {
+                        let (title, descending) = match sorted_by {
+                            Some((c, true)) if c == column => {
+                                (format!("{} ▼", column.title()), false)
+                            }
+                            Some((c, false)) if c == column => {
+                                (format!("{} ▲", column.title()), true)
+                            }
+                            _ => (column.title().to_string(), false),
+                        };
+                        if ui.button(title).clicked() {
+                            response.sort_requested = Some((column, descending));
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, items.len(), |index, mut row| {
+                    let data = &items[index];
+                    for column in columns {
+                        row.col(|ui| {
+                            ui.label(column.text(data));
+                        });
+                    }
+                });
+            });
+        if let Some((column, descending)) = response.sort_requested {
+            if self.client_sort {
+                self.sort_by(column, descending);
+            } else {
+                self.sorted_by = Some((column, descending));
+            }
+        }
+        response
+    }
+}
+
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{size} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// The letters like the attributes column in Explorer, e.g. `RHA`.
+fn format_attributes(attributes: u32) -> String {
+    use windows::Win32::Storage::FileSystem::{
+        FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_COMPRESSED, FILE_ATTRIBUTE_DIRECTORY,
+        FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM,
+    };
+    [
+        (FILE_ATTRIBUTE_READONLY, 'R'),
+        (FILE_ATTRIBUTE_HIDDEN, 'H'),
+        (FILE_ATTRIBUTE_SYSTEM, 'S'),
+        (FILE_ATTRIBUTE_DIRECTORY, 'D'),
+        (FILE_ATTRIBUTE_ARCHIVE, 'A'),
+        (FILE_ATTRIBUTE_COMPRESSED, 'C'),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| attributes & flag.0 != 0)
+    .map(|(_, letter)| letter)
+    .collect()
+}
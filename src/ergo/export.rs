@@ -0,0 +1,281 @@
+//! Export the results as CSV, JSON or NDJSON (newline-delimited JSON), for the other tools
+//! and the scripts.
+//!
+//! Different from [`EsFormat`](super::format::EsFormat) which renders for the humans like
+//! `es` does, the values here are for the machines: the sizes and the attributes are the raw
+//! numbers, and the dates are in the [`TimeFormat`] chosen. The data not available (or the
+//! unknown dates) are empty in CSV and `null` in JSON.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//! use everything_sdk::export::{self, ExportOptions, TimeFormat};
+//! use everything_sdk::format::Column;
+//!
+//! let mut options = SearchOptions::new("*.iso");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+//!     | RequestFlags::EVERYTHING_REQUEST_SIZE
+//!     | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED;
+//! let results = spawn_query(options).join().unwrap().unwrap();
+//!
+//! let mut export = ExportOptions::new();
+//! export.columns = vec![Column::FullPath, Column::Size, Column::DateModified];
+//! export.time_format = TimeFormat::UnixMillis;
+//! let file = std::fs::File::create("isos.ndjson").unwrap();
+//! export::write_ndjson(&results, &export, std::io::BufWriter::new(file)).unwrap();
+//! ```
+
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::format::Column;
+use super::{FileTime, ItemData, OwnedResults, RequestFlags};
+
+/// All the columns, in the order of Everything GUI.
+const ALL_COLUMNS: [Column; 13] = [
+    Column::Name,
+    Column::Path,
+    Column::FullPath,
+    Column::Extension,
+    Column::Size,
+    Column::DateCreated,
+    Column::DateModified,
+    Column::DateAccessed,
+    Column::Attributes,
+    Column::FileListFilename,
+    Column::RunCount,
+    Column::DateRun,
+    Column::DateRecentlyChanged,
+];
+
+/// How the dates are written.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TimeFormat {
+    /// ISO-8601 in UTC, like `2024-01-02T03:04:05.5Z`, the same as [`FileTime`] displays.
+    #[default]
+    Iso8601Utc,
+    /// The raw FILETIME number, in 100-nanosecond intervals since 1601-01-01 UTC.
+    FileTime,
+    /// The seconds since 1970-01-01 UTC, rounded down.
+    UnixSecs,
+    /// The milliseconds since 1970-01-01 UTC, rounded down, like the `Date` of JavaScript.
+    UnixMillis,
+}
+
+/// What to export, see [`write_csv`], [`write_json`] and [`write_ndjson`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ExportOptions {
+    /// The columns in order, or all the columns available in the results if empty.
+    pub columns: Vec<Column>,
+    pub time_format: TimeFormat,
+    /// Write the header line of the CSV, on by default.
+    pub header: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportOptions {
+    /// All the columns available, the dates in ISO-8601 and the CSV with the header.
+    pub fn new() -> Self {
+        ExportOptions {
+            columns: Vec::new(),
+            time_format: TimeFormat::default(),
+            header: true,
+        }
+    }
+
+    /// The columns to export for the results of the `flags`.
+    pub fn effective_columns(&self, flags: RequestFlags) -> Vec<Column> {
+        if !self.columns.is_empty() {
+            return self.columns.clone();
+        }
+        let has_full_path = flags.contains(
+            RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+        );
+        ALL_COLUMNS
+            .into_iter()
+            .filter(|column| {
+                flags.contains(column.request_flag())
+                    || (*column == Column::FullPath && has_full_path)
+            })
+            .collect()
+    }
+
+    fn value(&self, column: Column, data: &ItemData) -> Value {
+        let date = |filetime: Option<u64>| match filetime {
+            // `u64::MAX` is the unknown date.
+            Some(filetime) if filetime != u64::MAX => self.date(FileTime::new(filetime)),
+            _ => Value::Null,
+        };
+        match column {
+            Column::Name => text(data.filename.as_deref()),
+            Column::Path => text(data.path.as_deref().map(Path::as_os_str)),
+            Column::FullPath => text(data.full_path.as_deref().map(Path::as_os_str)),
+            Column::Extension => text(data.extension.as_deref()),
+            Column::Size => data.size.map_or(Value::Null, Value::Unsigned),
+            Column::DateCreated => date(data.date_created),
+            Column::DateModified => date(data.date_modified),
+            Column::DateAccessed => date(data.date_accessed),
+            Column::Attributes => data
+                .attributes
+                .map_or(Value::Null, |attributes| Value::Unsigned(attributes.into())),
+            Column::FileListFilename => text(data.file_list_filename.as_deref()),
+            Column::RunCount => data
+                .run_count
+                .map_or(Value::Null, |count| Value::Unsigned(count.into())),
+            Column::DateRun => date(data.date_run),
+            Column::DateRecentlyChanged => date(data.date_recently_changed),
+        }
+    }
+
+    fn date(&self, filetime: FileTime) -> Value {
+        match self.time_format {
+            TimeFormat::Iso8601Utc => Value::Text(filetime.to_string()),
+            TimeFormat::FileTime => Value::Unsigned(filetime.get()),
+            TimeFormat::UnixSecs => Value::Signed(filetime.to_unix_secs()),
+            // never overflow, the u64::MAX intervals is less than 2^51 milliseconds.
+            TimeFormat::UnixMillis => {
+                Value::Signed(filetime.to_unix_nanos().div_euclid(1_000_000) as i64)
+            }
+        }
+    }
+}
+
+/// A cell of the export.
+enum Value {
+    Null,
+    Text(String),
+    Unsigned(u64),
+    Signed(i64),
+}
+
+fn text(s: Option<&OsStr>) -> Value {
+    s.map_or(Value::Null, |s| {
+        Value::Text(s.to_string_lossy().into_owned())
+    })
+}
+
+/// The key in JSON, the same as the field of [`ItemData`].
+fn json_key(column: Column) -> &'static str {
+    match column {
+        Column::Name => "filename",
+        Column::Path => "path",
+        Column::FullPath => "full_path",
+        Column::Extension => "extension",
+        Column::Size => "size",
+        Column::DateCreated => "date_created",
+        Column::DateModified => "date_modified",
+        Column::DateAccessed => "date_accessed",
+        Column::Attributes => "attributes",
+        Column::FileListFilename => "file_list_filename",
+        Column::RunCount => "run_count",
+        Column::DateRun => "date_run",
+        Column::DateRecentlyChanged => "date_recently_changed",
+    }
+}
+
+/// Write the results as CSV (RFC 4180, with CRLF) into `writer`, with the
+/// [`Column::header`]s if [`ExportOptions::header`].
+pub fn write_csv<W: Write>(
+    results: &OwnedResults,
+    options: &ExportOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let columns = options.effective_columns(results.request_flags());
+    if options.header {
+        let headers: Vec<_> = columns.iter().map(Column::header).collect();
+        write!(writer, "{}\r\n", headers.join(","))?;
+    }
+    let mut line = String::new();
+    for data in results {
+        line.clear();
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                line.push(',');
+            }
+            match options.value(*column, data) {
+                Value::Null => {}
+                Value::Text(text) => write!(line, "\"{}\"", text.replace('"', "\"\"")).unwrap(),
+                Value::Unsigned(n) => write!(line, "{n}").unwrap(),
+                Value::Signed(n) => write!(line, "{n}").unwrap(),
+            }
+        }
+        write!(writer, "{line}\r\n")?;
+    }
+    writer.flush()
+}
+
+/// Write the results as a JSON array of objects into `writer`, one object per line.
+pub fn write_json<W: Write>(
+    results: &OwnedResults,
+    options: &ExportOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let columns = options.effective_columns(results.request_flags());
+    let mut line = String::new();
+    writer.write_all(b"[")?;
+    for (i, data) in results.iter().enumerate() {
+        line.clear();
+        json_object(&mut line, &columns, options, data);
+        let separator = if i > 0 { ",\n" } else { "\n" };
+        write!(writer, "{separator}{line}")?;
+    }
+    writer.write_all(b"\n]\n")?;
+    writer.flush()
+}
+
+/// Write the results as NDJSON into `writer`, that is one JSON object per line, which can be
+/// streamed and appended.
+pub fn write_ndjson<W: Write>(
+    results: &OwnedResults,
+    options: &ExportOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let columns = options.effective_columns(results.request_flags());
+    let mut line = String::new();
+    for data in results {
+        line.clear();
+        json_object(&mut line, &columns, options, data);
+        writeln!(writer, "{line}")?;
+    }
+    writer.flush()
+}
+
+fn json_object(out: &mut String, columns: &[Column], options: &ExportOptions, data: &ItemData) {
+    out.push('{');
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write!(out, "\"{}\":", json_key(*column)).unwrap();
+        match options.value(*column, data) {
+            Value::Null => out.push_str("null"),
+            Value::Text(text) => json_string(out, &text),
+            Value::Unsigned(n) => write!(out, "{n}").unwrap(),
+            Value::Signed(n) => write!(out, "{n}").unwrap(),
+        }
+    }
+    out.push('}');
+}
+
+fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
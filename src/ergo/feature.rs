@@ -0,0 +1,93 @@
+//! What the running Everything supports, by its version.
+
+use std::fmt;
+
+use super::{access, EverythingError, EverythingGlobal, Result};
+use crate::raw;
+
+/// The features needing a newer Everything, see [`EverythingGlobal::supports`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Feature {
+    /// The query version 2, needed by the request flags other than the file name and path,
+    /// and by the sorts other than by name.
+    QueryVersion2,
+    /// [`EverythingGlobal::is_fast_sort`].
+    FastSort,
+    /// [`EverythingGlobal::is_file_info_indexed`].
+    FileInfoIndexed,
+    /// The run count and the run history, like [`EverythingGlobal::get_run_count`].
+    RunHistory,
+    /// The properties of Everything 1.5, like the custom columns and the content indexing.
+    Properties,
+}
+
+impl Feature {
+    /// The first version supporting it, as `(major, minor, revision, build)`.
+    pub fn min_version(self) -> (u32, u32, u32, u32) {
+        match self {
+            Feature::QueryVersion2 | Feature::RunHistory => (1, 4, 1, 0),
+            Feature::FastSort | Feature::FileInfoIndexed => (1, 4, 1, 859),
+            Feature::Properties => (1, 5, 0, 0),
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor, revision, build) = self.min_version();
+        write!(
+            f,
+            "{self:?} (Everything {major}.{minor}.{revision}.{build} or later)"
+        )
+    }
+}
+
+impl EverythingGlobal {
+    /// Whether the running Everything supports the `feature`, by its version.
+    ///
+    /// It asks Everything for the version each time, and fails with [`EverythingError::Ipc`]
+    /// if it is not running.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let everything = global().lock().unwrap();
+    /// if everything.supports(Feature::FastSort)? {
+    ///     let fast = everything.is_fast_sort(SortType::EVERYTHING_SORT_SIZE_ASCENDING)?;
+    ///     println!("fast sort by size: {fast}");
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn supports(&self, feature: Feature) -> Result<bool> {
+        supports(feature)
+    }
+
+    /// Fail with [`EverythingError::Unsupported`] if the `feature` is not supported.
+    pub(super) fn require(&self, feature: Feature) -> Result<()> {
+        require(feature)
+    }
+}
+
+/// The same as [`EverythingGlobal::supports`], for the ones borrowing the global (like the
+/// items) which can not reach it. The caller must hold the global lock.
+fn supports(feature: Feature) -> Result<bool> {
+    let access = access();
+    let version = (
+        raw::Everything_GetMajorVersion(&access)?,
+        raw::Everything_GetMinorVersion(&access)?,
+        raw::Everything_GetRevision(&access)?,
+        raw::Everything_GetBuildNumber(&access)?,
+    );
+    Ok(version >= feature.min_version())
+}
+
+/// The same as [`EverythingGlobal::require`], see [`supports`].
+pub(super) fn require(feature: Feature) -> Result<()> {
+    if supports(feature)? {
+        Ok(())
+    } else {
+        Err(EverythingError::Unsupported(feature))
+    }
+}
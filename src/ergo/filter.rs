@@ -0,0 +1,243 @@
+//! Filter the results client-side after the reply, for what the search syntax can not say or
+//! what is only known at run time.
+//!
+//! The filters run over the visible results only, so the [`OwnedResults::total`] is still the
+//! total of the query, and a filtered page may be shorter than the `max`.
+
+use std::ops::{Bound, RangeBounds};
+
+use super::{
+    EverythingItem, EverythingResults, EverythingSearcher, FileAttributes, ItemData, OwnedResults,
+    RequestFlags, SearchOptions,
+};
+
+/// The post-query filter, all of its conditions must match.
+///
+/// An item without the data of a condition (not requested) never matches it, so request
+/// [`Filter::request_flags`] too, which [`EverythingSearcher::query_filtered`] does for you.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::*;
+/// use everything_sdk::filter::Filter;
+///
+/// let filter = Filter::new()
+///     .size(1 << 20..16 << 20)
+///     .without_attributes(FileAttributes::HIDDEN | FileAttributes::SYSTEM)
+///     .path_glob(r"C:\Users\*\Documents\*")
+///     .exclude_path_glob(r"*\~$*");
+/// let mut everything = global().lock().unwrap();
+/// let mut searcher = everything.searcher();
+/// let results = searcher.set_search("ext:docx").query_filtered(&filter);
+/// println!("{} documents of 1 to 16 MB", results.len());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Filter {
+    size: (Bound<u64>, Bound<u64>),
+    with_attributes: u32,
+    without_attributes: u32,
+    path_globs: Vec<Glob>,
+    excluded_globs: Vec<Glob>,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter {
+            size: (Bound::Unbounded, Bound::Unbounded),
+            with_attributes: 0,
+            without_attributes: 0,
+            path_globs: Vec::new(),
+            excluded_globs: Vec::new(),
+        }
+    }
+}
+
+impl Filter {
+    /// Match all the items.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The size in the `range`, like `1024..` or `..=4096`.
+    pub fn size(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.size = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// All of the `attributes` are set.
+    pub fn with_attributes(mut self, attributes: FileAttributes) -> Self {
+        self.with_attributes |= attributes.bits();
+        self
+    }
+
+    /// None of the `attributes` is set.
+    pub fn without_attributes(mut self, attributes: FileAttributes) -> Self {
+        self.without_attributes |= attributes.bits();
+        self
+    }
+
+    /// The full path matches the `pattern` (or any of them if called more than once), where
+    /// `*` is any characters and `?` is one, case-insensitively.
+    pub fn path_glob(mut self, pattern: &str) -> Self {
+        self.path_globs.push(Glob::new(pattern));
+        self
+    }
+
+    /// The full path matches none of the excluded `pattern`s, see [`Filter::path_glob`].
+    pub fn exclude_path_glob(mut self, pattern: &str) -> Self {
+        self.excluded_globs.push(Glob::new(pattern));
+        self
+    }
+
+    /// The request flags of the data the conditions need.
+    pub fn request_flags(&self) -> RequestFlags {
+        let mut flags = RequestFlags::empty();
+        if self.size != (Bound::Unbounded, Bound::Unbounded) {
+            flags |= RequestFlags::EVERYTHING_REQUEST_SIZE;
+        }
+        if self.with_attributes != 0 || self.without_attributes != 0 {
+            flags |= RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES;
+        }
+        if !self.path_globs.is_empty() || !self.excluded_globs.is_empty() {
+            flags |= RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME;
+        }
+        flags
+    }
+
+    pub fn matches(&self, data: &ItemData) -> bool {
+        if self.size != (Bound::Unbounded, Bound::Unbounded)
+            && !data.size.is_some_and(|size| self.size.contains(&size))
+        {
+            return false;
+        }
+        if self.with_attributes != 0 || self.without_attributes != 0 {
+            let Some(attributes) = data.attributes else {
+                return false;
+            };
+            if attributes & self.with_attributes != self.with_attributes
+                || attributes & self.without_attributes != 0
+            {
+                return false;
+            }
+        }
+        if self.path_globs.is_empty() && self.excluded_globs.is_empty() {
+            return true;
+        }
+        let Some(path) = &data.full_path else {
+            return false;
+        };
+        let path: Vec<char> = path
+            .to_string_lossy()
+            .chars()
+            .flat_map(char::to_lowercase)
+            .collect();
+        (self.path_globs.is_empty() || self.path_globs.iter().any(|glob| glob.matches(&path)))
+            && !self.excluded_globs.iter().any(|glob| glob.matches(&path))
+    }
+}
+
+/// A wildcard pattern in lowercase.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct Glob(Vec<char>);
+
+impl Glob {
+    fn new(pattern: &str) -> Self {
+        Glob(pattern.chars().flat_map(char::to_lowercase).collect())
+    }
+
+    /// Match the `text` in lowercase, by backtracking to the last `*` only.
+    fn matches(&self, text: &[char]) -> bool {
+        let pattern = &self.0;
+        let (mut p, mut t) = (0, 0);
+        let mut star: Option<(usize, usize)> = None;
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    star = Some((p, t));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || c == text[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match star {
+                    // Let the last `*` eat one more character.
+                    Some((star_p, star_t)) => {
+                        star = Some((star_p, star_t + 1));
+                        p = star_p + 1;
+                        t = star_t + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+impl OwnedResults {
+    /// Keep only the items matching the `predicate`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut results = spawn_query(SearchOptions::new("*.log")).join().unwrap()?;
+    /// results.filter_items(|data| data.filename.as_ref().is_some_and(|name| name.len() > 8));
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn filter_items(&mut self, predicate: impl FnMut(&ItemData) -> bool) {
+        self.items.retain(predicate);
+    }
+
+    /// Keep only the items matching the `filter`.
+    pub fn apply_filter(&mut self, filter: &Filter) {
+        self.filter_items(|data| filter.matches(data));
+    }
+}
+
+impl<'a> EverythingResults<'a> {
+    /// Snapshot only the items matching the `predicate`, the others are never copied out.
+    pub fn filter_items(
+        &self,
+        mut predicate: impl FnMut(&EverythingItem<'_>) -> bool,
+    ) -> OwnedResults {
+        let items = self
+            .iter()
+            .filter(|item| predicate(item))
+            .map(|item| item.to_data())
+            .collect();
+        OwnedResults::from_parts(
+            items,
+            self.request_flags(),
+            self.sort_type(),
+            self.total(),
+            SearchOptions::current(),
+        )
+    }
+
+    /// Snapshot the results, and keep only the items matching the `filter`.
+    pub fn filter_snapshot(&self, filter: &Filter) -> OwnedResults {
+        let mut results = self.snapshot();
+        results.apply_filter(filter);
+        results
+    }
+}
+
+impl EverythingSearcher<'_> {
+    /// Query with the request flags of the `filter` added, and snapshot the items matching it.
+    #[cfg(not(feature = "async"))]
+    pub fn query_filtered(&mut self, filter: &Filter) -> OwnedResults {
+        let flags = self.get_request_flags() | filter.request_flags();
+        self.set_request_flags(flags);
+        self.query().filter_snapshot(filter)
+    }
+
+    /// Query with the request flags of the `filter` added, and snapshot the items matching it.
+    #[cfg(feature = "async")]
+    pub async fn query_filtered(&mut self, filter: &Filter) -> OwnedResults {
+        let flags = self.get_request_flags() | filter.request_flags();
+        self.set_request_flags(flags);
+        self.query().await.filter_snapshot(filter)
+    }
+}
@@ -0,0 +1,163 @@
+//! The folder size by summing up the files in it, for Everything 1.4.
+//!
+//! Everything 1.4 gives no folder size over IPC (it is 0 for the folders, see
+//! [`EverythingItem::size`](super::EverythingItem::size)), so here all the files under the
+//! folder are queried page by page and their sizes are summed. It is exact, but not free for
+//! the huge trees, hence the progress and the cancellation of
+//! [`EverythingGlobal::folder_size_with`].
+
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use super::query::Scope;
+use super::{EverythingError, EverythingGlobal, EverythingResults, RequestFlags, Result};
+
+/// The number of the files in each query.
+const PAGE_SIZE: u32 = 10_000;
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FolderSize {
+    /// The sum of the sizes of all the files in the folder, recursively.
+    pub size: u64,
+    /// The number of the files.
+    pub files: u32,
+}
+
+/// The progress reported after each page of the files.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FolderSizeProgress {
+    /// The size summed so far.
+    pub size: u64,
+    /// The number of the files summed so far.
+    pub files_done: u32,
+    /// The number of all the files, known after the first page.
+    pub files_total: u32,
+}
+
+impl FolderSizeProgress {
+    /// Sum up one page, return `false` if it is empty (no more files).
+    fn add(&mut self, results: &EverythingResults<'_>) -> Result<bool> {
+        self.files_total = results.total();
+        for item in results.iter() {
+            self.size += item.size()?;
+        }
+        self.files_done += results.len();
+        Ok(!results.is_empty())
+    }
+
+    fn is_done(&self) -> bool {
+        self.files_done >= self.files_total
+    }
+}
+
+impl From<FolderSizeProgress> for FolderSize {
+    fn from(progress: FolderSizeProgress) -> Self {
+        FolderSize {
+            size: progress.size,
+            files: progress.files_done,
+        }
+    }
+}
+
+/// The search of all the files under `path`.
+fn search_of(path: &Path) -> Result<String> {
+    let scope = Scope::folder(path);
+    if scope.is_empty() {
+        return Err(EverythingError::InvalidParameter);
+    }
+    Ok(scope.search("file:"))
+}
+
+impl EverythingGlobal {
+    /// The size of the folder at `path`, see [`EverythingGlobal::folder_size_with`].
+    #[cfg(not(feature = "async"))]
+    pub fn folder_size(&mut self, path: impl AsRef<Path>) -> Result<FolderSize> {
+        self.folder_size_with(path, |_| ControlFlow::Continue(()))
+            .map(|size| size.expect("never cancelled"))
+    }
+
+    /// The size of the folder at `path`, by summing up all the files in it recursively.
+    ///
+    /// The `on_progress` is called after each page of the files, return `ControlFlow::Break`
+    /// to cancel it, and then `Ok(None)` is returned. The files changed between the pages may
+    /// be missed or counted twice, as each page is a new query.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// let size = everything.folder_size_with("C:\\Windows", |progress| {
+    ///     println!("{}/{} files", progress.files_done, progress.files_total);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// println!("{:?}", size);
+    /// ```
+    #[cfg(not(feature = "async"))]
+    pub fn folder_size_with(
+        &mut self,
+        path: impl AsRef<Path>,
+        mut on_progress: impl FnMut(&FolderSizeProgress) -> ControlFlow<()>,
+    ) -> Result<Option<FolderSize>> {
+        let search = search_of(path.as_ref())?;
+        self.is_db_loaded()?; // fail fast if IPC is not available
+        let mut progress = FolderSizeProgress::default();
+        loop {
+            let mut searcher = self.searcher();
+            searcher
+                .set_search(&search)
+                .set_request_flags(RequestFlags::EVERYTHING_REQUEST_SIZE)
+                .set_offset(progress.files_done)
+                .set_max(PAGE_SIZE);
+            let more = progress.add(&searcher.query())?;
+            if on_progress(&progress).is_break() {
+                return Ok(None);
+            }
+            if !more || progress.is_done() {
+                return Ok(Some(progress.into()));
+            }
+        }
+    }
+
+    /// The size of the folder at `path`, see [`EverythingGlobal::folder_size_with`].
+    #[cfg(feature = "async")]
+    pub async fn folder_size(&mut self, path: impl AsRef<Path>) -> Result<FolderSize> {
+        self.folder_size_with(path, |_| ControlFlow::Continue(()))
+            .await
+            .map(|size| size.expect("never cancelled"))
+    }
+
+    /// The size of the folder at `path`, by summing up all the files in it recursively.
+    ///
+    /// The `on_progress` is called after each page of the files, return `ControlFlow::Break`
+    /// to cancel it, and then `Ok(None)` is returned. The files changed between the pages may
+    /// be missed or counted twice, as each page is a new query.
+    #[cfg(feature = "async")]
+    pub async fn folder_size_with(
+        &mut self,
+        path: impl AsRef<Path>,
+        mut on_progress: impl FnMut(&FolderSizeProgress) -> ControlFlow<()>,
+    ) -> Result<Option<FolderSize>> {
+        let search = search_of(path.as_ref())?;
+        self.is_db_loaded()?; // fail fast if IPC is not available
+        let mut progress = FolderSizeProgress::default();
+        loop {
+            let mut searcher = self.searcher();
+            searcher
+                .set_search(&search)
+                .set_request_flags(RequestFlags::EVERYTHING_REQUEST_SIZE)
+                .set_offset(progress.files_done)
+                .set_max(PAGE_SIZE);
+            let more = progress.add(&searcher.query().await)?;
+            if on_progress(&progress).is_break() {
+                return Ok(None);
+            }
+            if !more || progress.is_done() {
+                return Ok(Some(progress.into()));
+            }
+        }
+    }
+}
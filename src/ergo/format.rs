@@ -0,0 +1,694 @@
+//! Render the snapshots in the output formats of [es.exe](https://www.voidtools.com/support/everything/command_line_interface/),
+//! the official command line interface of Everything.
+//!
+//! The Rust replacements of `es` can be drop-in compatible, by taking the same switches (see
+//! [`EsFormat::from_args`]) and printing the same columns.
+//!
+//! ```no_run
+//! use everything_sdk::{format::EsFormat, *};
+//!
+//! let (format, search) = EsFormat::from_args(["-size", "-dm", "-csv", "*.rs"]).unwrap();
+//! let mut options = SearchOptions::new(search.join(" "));
+//! options.request_flags = format.request_flags();
+//! let results = spawn_query(options).join().unwrap().unwrap();
+//! format.write(&results, &mut std::io::stdout().lock()).unwrap();
+//! ```
+
+use std::ffi::OsStr;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error as ThisError;
+
+use super::{FileTime, ItemData, OwnedResults, RequestFlags};
+
+/// The line ending of `es`.
+const CRLF: &str = "\r\n";
+
+/// A column of the output, in the order of the switches.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Column {
+    Name,
+    Path,
+    /// The full path and file name, which is the only column by default.
+    FullPath,
+    Extension,
+    Size,
+    DateCreated,
+    DateModified,
+    DateAccessed,
+    Attributes,
+    FileListFilename,
+    RunCount,
+    DateRun,
+    DateRecentlyChanged,
+}
+
+impl Column {
+    /// The column of the `es` switch, like `-size` or `-dm`.
+    pub fn from_switch(switch: &str) -> Option<Self> {
+        let column = match switch.to_ascii_lowercase().as_str() {
+            "-name" => Column::Name,
+            "-path-column" => Column::Path,
+            "-full-path-and-name" | "-filename-column" => Column::FullPath,
+            "-extension" | "-ext" => Column::Extension,
+            "-size" => Column::Size,
+            "-date-created" | "-dc" => Column::DateCreated,
+            "-date-modified" | "-dm" => Column::DateModified,
+            "-date-accessed" | "-da" => Column::DateAccessed,
+            "-attributes" | "-attribs" | "-attrib" => Column::Attributes,
+            "-file-list-file-name" => Column::FileListFilename,
+            "-run-count" => Column::RunCount,
+            "-date-run" => Column::DateRun,
+            "-date-recently-changed" | "-rc" => Column::DateRecentlyChanged,
+            _ => return None,
+        };
+        Some(column)
+    }
+
+    /// The header in the CSV and TSV exports.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Name => "Name",
+            Column::Path => "Path",
+            Column::FullPath => "Filename",
+            Column::Extension => "Extension",
+            Column::Size => "Size",
+            Column::DateCreated => "Date Created",
+            Column::DateModified => "Date Modified",
+            Column::DateAccessed => "Date Accessed",
+            Column::Attributes => "Attributes",
+            Column::FileListFilename => "File List Filename",
+            Column::RunCount => "Run Count",
+            Column::DateRun => "Date Run",
+            Column::DateRecentlyChanged => "Date Recently Changed",
+        }
+    }
+
+    /// The request flag to query the data of this column.
+    pub fn request_flag(&self) -> RequestFlags {
+        match self {
+            Column::Name => RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            Column::Path => RequestFlags::EVERYTHING_REQUEST_PATH,
+            Column::FullPath => RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+            Column::Extension => RequestFlags::EVERYTHING_REQUEST_EXTENSION,
+            Column::Size => RequestFlags::EVERYTHING_REQUEST_SIZE,
+            Column::DateCreated => RequestFlags::EVERYTHING_REQUEST_DATE_CREATED,
+            Column::DateModified => RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED,
+            Column::DateAccessed => RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED,
+            Column::Attributes => RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+            Column::FileListFilename => RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME,
+            Column::RunCount => RequestFlags::EVERYTHING_REQUEST_RUN_COUNT,
+            Column::DateRun => RequestFlags::EVERYTHING_REQUEST_DATE_RUN,
+            Column::DateRecentlyChanged => RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED,
+        }
+    }
+
+    fn is_path_like(&self) -> bool {
+        matches!(self, Column::Name | Column::Path | Column::FullPath)
+    }
+}
+
+/// The `-size-format` of `es`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SizeFormat {
+    /// `0`, in the best unit like `12 KB`.
+    Auto,
+    /// `1`, in bytes.
+    #[default]
+    Bytes,
+    /// `2`, in KB (rounded up, like Everything).
+    KB,
+    /// `3`, in MB (rounded up).
+    MB,
+}
+
+/// The `-date-format` of `es`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum DateFormat {
+    /// `0`, in local time like `2024-01-02 03:04`. (`es` follows the system settings)
+    #[default]
+    System,
+    /// `1`, ISO-8601 in local time, like `2024-01-02T03:04:05`.
+    Iso8601,
+    /// `2`, the raw FILETIME number.
+    FileTime,
+    /// `3`, ISO-8601 in UTC, like `2024-01-02T03:04:05Z`.
+    Iso8601Utc,
+}
+
+/// The output format, the console text or one of the `-export-*` formats of `es`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Output {
+    /// The columns separated by spaces, the size is right-aligned.
+    #[default]
+    Text,
+    Csv,
+    Tsv,
+    /// The file list of Everything, always in the columns of full path, size, date modified,
+    /// date created and attributes, in raw numbers.
+    Efu,
+    /// Only the full paths, one per line.
+    Txt,
+    /// The playlist of the full paths.
+    M3u,
+    /// The same as [`Output::M3u`], `es` writes M3U in the ANSI code page but here both of
+    /// them are in UTF-8.
+    M3u8,
+}
+
+#[derive(ThisError, Debug, Clone, Eq, PartialEq)]
+pub enum ArgsError {
+    #[error("the switch {0} needs a value.")]
+    MissingValue(String),
+    #[error("invalid value {value:?} for the switch {switch}.")]
+    InvalidValue { switch: String, value: String },
+}
+
+/// How to render the results, like `es` does with the same switches.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EsFormat {
+    /// The columns in order, the full path is added at the end if there is no path column.
+    pub columns: Vec<Column>,
+    pub size_format: SizeFormat,
+    pub date_format: DateFormat,
+    /// `-no-digit-grouping` turns it off.
+    pub digit_grouping: bool,
+    /// The width of the size column in [`Output::Text`], `-size-width`.
+    pub size_width: usize,
+    pub output: Output,
+    /// `-no-header` turns it off, for the CSV, TSV and EFU.
+    pub header: bool,
+    /// The file of `-export-*`, `None` for the stdout.
+    pub export: Option<PathBuf>,
+}
+
+impl Default for EsFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EsFormat {
+    /// The same defaults as `es`, that is only the full paths.
+    pub fn new() -> Self {
+        EsFormat {
+            columns: Vec::new(),
+            size_format: SizeFormat::default(),
+            date_format: DateFormat::default(),
+            digit_grouping: true,
+            size_width: 15,
+            output: Output::default(),
+            header: true,
+            export: None,
+        }
+    }
+
+    /// Take the formatting switches of `es` from `args`, and return the rest (such as the
+    /// search text and the other switches) in order.
+    ///
+    /// The column switches (like `-name`, `-size`, `-dm`), `-size-format <n>`,
+    /// `-date-format <n>`, `-no-digit-grouping`, `-size-width <n>`, `-no-header`, the output
+    /// switches (`-csv`, `-tsv`, `-efu`, `-txt`, `-m3u`, `-m3u8`) and the export switches
+    /// (like `-export-csv <file>`) are supported, case-insensitively.
+    pub fn from_args<I, S>(args: I) -> Result<(Self, Vec<String>), ArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut format = EsFormat::new();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let switch = arg.to_ascii_lowercase();
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| ArgsError::MissingValue(arg.clone()))
+            };
+            if let Some(column) = Column::from_switch(&switch) {
+                format.columns.push(column);
+            } else if let Some(output) = output_of(&switch) {
+                format.output = output;
+            } else if let Some(output) = switch.strip_prefix("-export").and_then(output_of) {
+                format.output = output;
+                format.export = Some(value()?.into());
+            } else {
+                match switch.as_str() {
+                    "-size-format" => {
+                        format.size_format = match value()?.as_str() {
+                            "0" => SizeFormat::Auto,
+                            "1" => SizeFormat::Bytes,
+                            "2" => SizeFormat::KB,
+                            "3" => SizeFormat::MB,
+                            other => return Err(invalid(&arg, other)),
+                        }
+                    }
+                    "-date-format" => {
+                        format.date_format = match value()?.as_str() {
+                            "0" => DateFormat::System,
+                            "1" => DateFormat::Iso8601,
+                            "2" => DateFormat::FileTime,
+                            "3" => DateFormat::Iso8601Utc,
+                            other => return Err(invalid(&arg, other)),
+                        }
+                    }
+                    "-size-width" => {
+                        let width = value()?;
+                        format.size_width = width.parse().map_err(|_| invalid(&arg, &width))?;
+                    }
+                    "-no-digit-grouping" => format.digit_grouping = false,
+                    "-no-header" => format.header = false,
+                    _ => rest.push(arg),
+                }
+            }
+        }
+        Ok((format, rest))
+    }
+
+    /// The columns actually rendered, with the full path added if needed.
+    pub fn effective_columns(&self) -> Vec<Column> {
+        if self.output == Output::Efu {
+            return vec![
+                Column::FullPath,
+                Column::Size,
+                Column::DateModified,
+                Column::DateCreated,
+                Column::Attributes,
+            ];
+        }
+        if matches!(self.output, Output::Txt | Output::M3u | Output::M3u8) {
+            return vec![Column::FullPath];
+        }
+        let mut columns = self.columns.clone();
+        if !columns.iter().any(Column::is_path_like) {
+            columns.push(Column::FullPath);
+        }
+        columns
+    }
+
+    /// The request flags to query all the columns.
+    pub fn request_flags(&self) -> RequestFlags {
+        self.effective_columns()
+            .iter()
+            .fold(RequestFlags::empty(), |flags, column| {
+                flags | column.request_flag()
+            })
+    }
+
+    /// Render the results into `out`, with CRLF line endings like `es`.
+    pub fn write(&self, results: &OwnedResults, out: &mut impl Write) -> io::Result<()> {
+        let columns = self.effective_columns();
+        match self.output {
+            Output::Text => {
+                for data in results {
+                    let cells: Vec<_> = columns
+                        .iter()
+                        .map(|column| match column {
+                            Column::Size => {
+                                format!("{:>1$}", self.cell(*column, data), self.size_width)
+                            }
+                            _ => self.cell(*column, data),
+                        })
+                        .collect();
+                    write!(out, "{}{CRLF}", cells.join(" "))?;
+                }
+            }
+            Output::Csv | Output::Efu => {
+                if self.header {
+                    let headers: Vec<_> = columns.iter().map(Column::header).collect();
+                    write!(out, "{}{CRLF}", headers.join(","))?;
+                }
+                for data in results {
+                    let cells: Vec<_> = columns
+                        .iter()
+                        .map(|column| self.csv_cell(*column, data))
+                        .collect();
+                    write!(out, "{}{CRLF}", cells.join(","))?;
+                }
+            }
+            Output::Tsv => {
+                if self.header {
+                    let headers: Vec<_> = columns.iter().map(Column::header).collect();
+                    write!(out, "{}{CRLF}", headers.join("\t"))?;
+                }
+                for data in results {
+                    let cells: Vec<_> = columns
+                        .iter()
+                        .map(|column| self.cell(*column, data))
+                        .collect();
+                    write!(out, "{}{CRLF}", cells.join("\t"))?;
+                }
+            }
+            Output::Txt => {
+                for data in results {
+                    write!(out, "{}{CRLF}", self.cell(Column::FullPath, data))?;
+                }
+            }
+            Output::M3u | Output::M3u8 => {
+                write!(out, "#EXTM3U{CRLF}")?;
+                for data in results {
+                    write!(out, "{}{CRLF}", self.cell(Column::FullPath, data))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the results into a string.
+    pub fn render(&self, results: &OwnedResults) -> String {
+        let mut out = Vec::new();
+        self.write(results, &mut out)
+            .expect("writing to Vec never fails");
+        String::from_utf8(out).expect("all the cells are UTF-8")
+    }
+
+    /// The text of a column, empty if the data is not available.
+    pub fn cell(&self, column: Column, data: &ItemData) -> String {
+        let date = |d: Option<u64>| d.map(|d| self.date(d)).unwrap_or_default();
+        match column {
+            Column::Name => text(data.filename.as_deref()),
+            Column::Path => text(data.path.as_deref().map(Path::as_os_str)),
+            Column::FullPath => text(data.full_path.as_deref().map(Path::as_os_str)),
+            Column::Extension => text(data.extension.as_deref()),
+            Column::Size => match data.size {
+                Some(_) if data.is_folder() && self.output == Output::Text => String::new(),
+                Some(size) => self.size(size),
+                None => String::new(),
+            },
+            Column::DateCreated => date(data.date_created),
+            Column::DateModified => date(data.date_modified),
+            Column::DateAccessed => date(data.date_accessed),
+            Column::Attributes => match self.output {
+                Output::Efu => data.attributes.map(|a| a.to_string()).unwrap_or_default(),
+                _ => data.attributes.map(attributes).unwrap_or_default(),
+            },
+            Column::FileListFilename => text(data.file_list_filename.as_deref()),
+            Column::RunCount => data.run_count.map(|n| n.to_string()).unwrap_or_default(),
+            Column::DateRun => date(data.date_run),
+            Column::DateRecentlyChanged => date(data.date_recently_changed),
+        }
+    }
+
+    /// The cell quoted for CSV, only the text columns are quoted.
+    fn csv_cell(&self, column: Column, data: &ItemData) -> String {
+        let cell = self.cell(column, data);
+        match column {
+            Column::Name
+            | Column::Path
+            | Column::FullPath
+            | Column::Extension
+            | Column::FileListFilename => format!("\"{}\"", cell.replace('"', "\"\"")),
+            _ => cell,
+        }
+    }
+
+    fn size(&self, size: u64) -> String {
+        // The raw numbers are always in bytes, for the machines.
+        if matches!(self.output, Output::Csv | Output::Efu) {
+            return size.to_string();
+        }
+        let (n, unit) = match self.size_format {
+            SizeFormat::Bytes => (size, ""),
+            SizeFormat::KB => (div_ceil(size, 1 << 10), " KB"),
+            SizeFormat::MB => (div_ceil(size, 1 << 20), " MB"),
+            SizeFormat::Auto => {
+                return match size {
+                    0..=1023 => format!("{size} bytes"),
+                    _ if size < 1 << 20 => format!("{} KB", self.group(div_ceil(size, 1 << 10))),
+                    _ if size < 1 << 30 => format!("{:.1} MB", size as f64 / (1u64 << 20) as f64),
+                    _ if size < 1 << 40 => format!("{:.1} GB", size as f64 / (1u64 << 30) as f64),
+                    _ => format!("{:.1} TB", size as f64 / (1u64 << 40) as f64),
+                }
+            }
+        };
+        format!("{}{unit}", self.group(n))
+    }
+
+    /// The number with the `,` digit grouping if enabled.
+    fn group(&self, n: u64) -> String {
+        let digits = n.to_string();
+        if !self.digit_grouping {
+            return digits;
+        }
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+
+    fn date(&self, filetime: u64) -> String {
+        if self.output == Output::Efu || self.date_format == DateFormat::FileTime {
+            return filetime.to_string();
+        }
+        let filetime = FileTime::new(filetime);
+        let Some(time) = filetime.to_system_time() else {
+            return String::new();
+        };
+        let local = chrono::DateTime::<chrono::Local>::from(time);
+        match self.date_format {
+            DateFormat::System => local.format("%Y-%m-%d %H:%M").to_string(),
+            DateFormat::Iso8601 => local.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            DateFormat::Iso8601Utc => {
+                let utc = chrono::DateTime::<chrono::Utc>::from(time);
+                utc.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+            }
+            DateFormat::FileTime => unreachable!("returned above"),
+        }
+    }
+}
+
+/// The output of `-csv`, `-tsv` and so on, also the suffix of `-export-*`.
+fn output_of(switch: &str) -> Option<Output> {
+    let output = match switch.strip_prefix('-')? {
+        "csv" => Output::Csv,
+        "tsv" => Output::Tsv,
+        "efu" => Output::Efu,
+        "txt" => Output::Txt,
+        "m3u" => Output::M3u,
+        "m3u8" => Output::M3u8,
+        _ => return None,
+    };
+    Some(output)
+}
+
+fn text(s: Option<&OsStr>) -> String {
+    s.map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// `u64::div_ceil` is stable only since Rust 1.73.
+fn div_ceil(n: u64, d: u64) -> u64 {
+    n / d + u64::from(n % d != 0)
+}
+
+fn invalid(switch: &str, value: &str) -> ArgsError {
+    ArgsError::InvalidValue {
+        switch: switch.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// The attribute letters like Everything shows, such as `HSA`.
+fn attributes(attributes: u32) -> String {
+    use windows::Win32::Storage::FileSystem::{
+        FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_COMPRESSED, FILE_ATTRIBUTE_DIRECTORY,
+        FILE_ATTRIBUTE_ENCRYPTED, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_NOT_CONTENT_INDEXED,
+        FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM,
+        FILE_ATTRIBUTE_TEMPORARY,
+    };
+    [
+        (FILE_ATTRIBUTE_READONLY, 'R'),
+        (FILE_ATTRIBUTE_HIDDEN, 'H'),
+        (FILE_ATTRIBUTE_SYSTEM, 'S'),
+        (FILE_ATTRIBUTE_DIRECTORY, 'D'),
+        (FILE_ATTRIBUTE_ARCHIVE, 'A'),
+        (FILE_ATTRIBUTE_TEMPORARY, 'T'),
+        (FILE_ATTRIBUTE_COMPRESSED, 'C'),
+        (FILE_ATTRIBUTE_OFFLINE, 'O'),
+        (FILE_ATTRIBUTE_NOT_CONTENT_INDEXED, 'I'),
+        (FILE_ATTRIBUTE_ENCRYPTED, 'E'),
+    ]
+    .into_iter()
+    .filter(|(attr, _)| attributes & attr.0 != 0)
+    .map(|(_, letter)| letter)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ItemKind, SearchOptions, SortType};
+
+    /// 2024-01-02T03:04:05Z
+    const DATE: u64 = 133_486_382_450_000_000;
+
+    fn results() -> OwnedResults {
+        let file = ItemData {
+            kind: ItemKind::File,
+            filename: Some("a \"b\".txt".into()),
+            path: Some(PathBuf::from("C:\\dir")),
+            full_path: Some(PathBuf::from("C:\\dir\\a \"b\".txt")),
+            extension: Some("txt".into()),
+            size: Some(1_234_567),
+            date_modified: Some(DATE),
+            attributes: Some(0x21), // readonly and archive
+            ..Default::default()
+        };
+        let folder = ItemData {
+            index: 1,
+            kind: ItemKind::Folder,
+            filename: Some("dir".into()),
+            path: Some(PathBuf::from("C:")),
+            full_path: Some(PathBuf::from("C:\\dir")),
+            size: Some(4096),
+            date_modified: Some(DATE),
+            attributes: Some(0x10),
+            ..Default::default()
+        };
+        OwnedResults::from_parts(
+            vec![file, folder],
+            RequestFlags::all(),
+            SortType::default(),
+            2,
+            SearchOptions::default(),
+        )
+    }
+
+    fn format(args: &[&str]) -> EsFormat {
+        let (format, rest) = EsFormat::from_args(args.iter().copied()).unwrap();
+        assert!(rest.is_empty());
+        format
+    }
+
+    #[test]
+    fn args() {
+        let (format, rest) =
+            EsFormat::from_args(["-NAME", "-dm", "-size-format", "2", "*.rs", "-csv"]).unwrap();
+        assert_eq!(format.columns, [Column::Name, Column::DateModified]);
+        assert_eq!(format.size_format, SizeFormat::KB);
+        assert_eq!(format.output, Output::Csv);
+        assert_eq!(rest, ["*.rs"]);
+        assert_eq!(
+            EsFormat::from_args(["-size-format"]),
+            Err(ArgsError::MissingValue("-size-format".to_string()))
+        );
+        assert!(matches!(
+            EsFormat::from_args(["-date-format", "9"]),
+            Err(ArgsError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn only_the_full_paths_by_default() {
+        assert_eq!(
+            EsFormat::new().render(&results()),
+            "C:\\dir\\a \"b\".txt\r\nC:\\dir\r\n"
+        );
+    }
+
+    #[test]
+    fn text_columns() {
+        let format = format(&["-name", "-size", "-dm", "-attrib", "-date-format", "3"]);
+        assert_eq!(
+            format.render(&results()),
+            concat!(
+                "a \"b\".txt       1,234,567 2024-01-02T03:04:05Z RA\r\n",
+                "dir                 2024-01-02T03:04:05Z D\r\n",
+            )
+        );
+    }
+
+    #[test]
+    fn sizes() {
+        let size = |args: &[&str], n: u64| format(args).size(n);
+        assert_eq!(size(&[], 1_234_567), "1,234,567");
+        assert_eq!(size(&["-no-digit-grouping"], 1_234_567), "1234567");
+        assert_eq!(size(&["-size-format", "2"], 1025), "2 KB");
+        assert_eq!(size(&["-size-format", "2"], 0), "0 KB");
+        assert_eq!(size(&["-size-format", "3"], 1 << 20), "1 MB");
+        assert_eq!(size(&["-size-format", "3"], (1 << 20) + 1), "2 MB");
+        assert_eq!(size(&["-size-format", "0"], 512), "512 bytes");
+        assert_eq!(size(&["-size-format", "0"], 150_000), "147 KB");
+        assert_eq!(size(&["-size-format", "0"], 1_040_000), "1,016 KB");
+        assert_eq!(size(&["-size-format", "0"], 1_500_000), "1.4 MB");
+        assert_eq!(size(&["-size-format", "0"], 3 << 29), "1.5 GB");
+        // The raw bytes for the machines.
+        assert_eq!(size(&["-csv", "-size-format", "2"], 1_234_567), "1234567");
+    }
+
+    #[test]
+    fn dates() {
+        let date = |args: &[&str]| format(args).date(DATE);
+        assert_eq!(date(&["-date-format", "2"]), DATE.to_string());
+        assert_eq!(date(&["-date-format", "3"]), "2024-01-02T03:04:05Z");
+        assert_eq!(date(&["-efu", "-date-format", "3"]), DATE.to_string());
+        // In the local time zone, so only the shapes.
+        assert_eq!(date(&["-date-format", "0"]).len(), "2024-01-02 03:04".len());
+        assert_eq!(
+            date(&["-date-format", "1"]).len(),
+            "2024-01-02T03:04:05".len()
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let format = format(&["-name", "-size", "-csv"]);
+        assert_eq!(
+            format.render(&results()),
+            "Name,Size\r\n\"a \"\"b\"\".txt\",1234567\r\n\"dir\",4096\r\n"
+        );
+        let format = self::format(&["-name", "-csv", "-no-header"]);
+        assert_eq!(
+            format.render(&results()),
+            "\"a \"\"b\"\".txt\"\r\n\"dir\"\r\n"
+        );
+    }
+
+    #[test]
+    fn tsv() {
+        let format = format(&["-name", "-ext", "-tsv"]);
+        assert_eq!(
+            format.render(&results()),
+            "Name\tExtension\r\na \"b\".txt\ttxt\r\ndir\t\r\n"
+        );
+    }
+
+    #[test]
+    fn efu() {
+        let format = format(&["-name", "-efu"]);
+        assert_eq!(
+            format.request_flags(),
+            RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+                | RequestFlags::EVERYTHING_REQUEST_SIZE
+                | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED
+                | RequestFlags::EVERYTHING_REQUEST_DATE_CREATED
+                | RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES
+        );
+        assert_eq!(
+            format.render(&results()),
+            format!(
+                "Filename,Size,Date Modified,Date Created,Attributes\r\n\
+                 \"C:\\dir\\a \"\"b\"\".txt\",1234567,{DATE},,33\r\n\
+                 \"C:\\dir\",4096,{DATE},,16\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn playlists() {
+        assert_eq!(
+            format(&["-size", "-txt"]).render(&results()),
+            "C:\\dir\\a \"b\".txt\r\nC:\\dir\r\n"
+        );
+        assert_eq!(
+            format(&["-m3u8"]).render(&results()),
+            "#EXTM3U\r\nC:\\dir\\a \"b\".txt\r\nC:\\dir\r\n"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Re-rank the results by fuzzy matching against the raw user input. (feature `fuzzy`)
+//!
+//! Everything only matches the substrings (or wildcards and regex), and the results are sorted
+//! by a column, which is not what a launcher wants for the input like `vsc`. Here the results
+//! are scored by the fzf-style matcher from [nucleo](https://docs.rs/nucleo-matcher), and the
+//! better matches come first.
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+
+use super::query::{self, Ast};
+use super::{ItemData, OwnedResults};
+
+/// The fuzzy re-ranker for one user input, which can be reused for many items.
+pub struct ReRanker {
+    matcher: Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl ReRanker {
+    /// The `input` is parsed in the fzf syntax, e.g. `'exact`, `^prefix`, `suffix$` and `!not`,
+    /// with smart case.
+    pub fn new(input: &str) -> Self {
+        ReRanker {
+            matcher: Matcher::new(Config::DEFAULT.match_paths()),
+            pattern: Pattern::parse(input, CaseMatching::Smart, Normalization::Smart),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Score the text, `None` if not matched. The higher the better.
+    pub fn score_text(&mut self, text: &str) -> Option<u32> {
+        let haystack = Utf32Str::new(text, &mut self.buf);
+        self.pattern.score(haystack, &mut self.matcher)
+    }
+
+    /// Score the item by the better one of its file name and full path, `None` if neither of
+    /// them matched (or not requested).
+    pub fn score(&mut self, data: &ItemData) -> Option<u32> {
+        let by_name = data
+            .filename
+            .as_ref()
+            .and_then(|name| self.score_text(&name.to_string_lossy()));
+        let by_path = data
+            .full_path
+            .as_ref()
+            .and_then(|path| self.score_text(&path.to_string_lossy()));
+        by_name.max(by_path)
+    }
+
+    /// Drop the items not matched and sort the rest by the score, the best first.
+    ///
+    /// The sort is stable, so the items with the same score keep the order from Everything.
+    pub fn rank(&mut self, items: impl IntoIterator<Item = ItemData>) -> Vec<(u32, ItemData)> {
+        let mut ranked: Vec<_> = items
+            .into_iter()
+            .filter_map(|data| self.score(&data).map(|score| (score, data)))
+            .collect();
+        ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+        ranked
+    }
+}
+
+impl OwnedResults {
+    /// Keep only the items fuzzy matched with the `input`, and order them by the score, the
+    /// best first. See [`ReRanker`].
+    pub fn rerank_fuzzy(&mut self, input: &str) {
+        let mut reranker = ReRanker::new(input);
+        let items = std::mem::take(&mut self.items);
+        self.items = reranker
+            .rank(items)
+            .into_iter()
+            .map(|(_, data)| data)
+            .collect();
+    }
+
+    /// Re-rank by the plain terms of the original search, see [`fuzzy_input`].
+    ///
+    /// Nothing is done if the search has no plain terms, like `ext:rs` only.
+    pub fn rerank_fuzzy_by_search(&mut self) {
+        let input = fuzzy_input(&self.options().search);
+        if !input.is_empty() {
+            self.rerank_fuzzy(&input);
+        }
+    }
+}
+
+/// The fuzzy input from the Everything `search`, by its plain terms (without the wildcards),
+/// as Everything already did the rest. The functions, the negated terms and the folders (with
+/// `\`) are dropped, e.g. `vs*code ext:exe !insiders` is `vscode`.
+pub fn fuzzy_input(search: &str) -> String {
+    fn collect<'a>(ast: &'a Ast, terms: &mut Vec<&'a str>) {
+        match ast {
+            Ast::Term { text, .. } if !text.contains('\\') => terms.push(text),
+            Ast::Modifier { expr, .. } | Ast::Group(expr) => collect(expr, terms),
+            Ast::And(exprs) | Ast::Or(exprs) => exprs.iter().for_each(|e| collect(e, terms)),
+            _ => {}
+        }
+    }
+
+    let Ok(ast) = query::parse(search) else {
+        return String::new();
+    };
+    let mut terms = Vec::new();
+    collect(&ast, &mut terms);
+    terms
+        .into_iter()
+        .map(|term| term.replace(['*', '?'], ""))
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
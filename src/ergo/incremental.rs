@@ -0,0 +1,121 @@
+//! Search as you type, for the search boxes.
+//!
+//! Each keystroke is a new search, but querying all of them is a waste, and the results of an
+//! older search arriving after a newer one would flash in the UI. The [`IncrementalSearch`]
+//! waits for the typing to pause, queries only the latest search, and drops the results of a
+//! search superseded while it was querying.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use super::{OwnedResults, Result, SearchOptions};
+
+/// The search box of a UI, see the [module](self) docs.
+///
+/// The queries are done on its own thread, which takes the [`global`](super::global) lock for
+/// each query. The thread stops when it is dropped, but the search being queried (if any) is
+/// still finished and its results dropped.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use everything_sdk::*;
+///
+/// let mut options = SearchOptions::new("");
+/// options.max = 100;
+/// let debounce = Duration::from_millis(150);
+/// let (search, results) = IncrementalSearch::spawn_channel(options, debounce)?;
+/// for text in ["e", "ev", "eve", "ever"] {
+///     search.set_text(text);
+/// }
+/// let results = results.recv().unwrap()?; // only for "ever"
+/// println!("{}: {} results", results.options().search, results.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct IncrementalSearch {
+    options: SearchOptions,
+    sender: Sender<SearchOptions>,
+}
+
+impl IncrementalSearch {
+    /// Start the thread, which calls `on_results` with the results of each search not
+    /// superseded, after no new search for `debounce`.
+    ///
+    /// The `options` are for [`set_text`](Self::set_text), the search text in it is ignored.
+    pub fn spawn<F>(
+        options: SearchOptions,
+        debounce: Duration,
+        on_results: F,
+    ) -> std::io::Result<Self>
+    where
+        F: FnMut(Result<OwnedResults>) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("everything-sdk-incremental".to_string())
+            .spawn(move || work(receiver, debounce, on_results))?;
+        Ok(IncrementalSearch { options, sender })
+    }
+
+    /// The same as [`spawn`](Self::spawn), but the results are sent to the returned channel.
+    pub fn spawn_channel(
+        options: SearchOptions,
+        debounce: Duration,
+    ) -> std::io::Result<(Self, Receiver<Result<OwnedResults>>)> {
+        let (sender, receiver) = mpsc::channel();
+        let search = Self::spawn(options, debounce, move |results| {
+            let _ = sender.send(results);
+        })?;
+        Ok((search, receiver))
+    }
+
+    /// Search the `text`, with the other options given at the start.
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.update(SearchOptions {
+            search: text.into(),
+            ..self.options.clone()
+        });
+    }
+
+    /// Search with all the `options`, such as the sort changed by the UI.
+    pub fn update(&self, options: SearchOptions) {
+        // Never fails, as the thread exits only when this is dropped.
+        let _ = self.sender.send(options);
+    }
+}
+
+fn work(
+    updates: Receiver<SearchOptions>,
+    debounce: Duration,
+    mut on_results: impl FnMut(Result<OwnedResults>),
+) {
+    let Ok(mut latest) = updates.recv() else {
+        return;
+    };
+    loop {
+        // Wait for the typing to pause.
+        loop {
+            match updates.recv_timeout(debounce) {
+                Ok(options) => latest = options,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        let results = latest.query_snapshot();
+        match updates.try_recv() {
+            // Superseded while querying, debounce the newer one instead.
+            Ok(options) => {
+                latest = options;
+                continue;
+            }
+            Err(TryRecvError::Empty) => on_results(results),
+            Err(TryRecvError::Disconnected) => return,
+        }
+        match updates.recv() {
+            Ok(options) => latest = options,
+            Err(_) => return,
+        }
+    }
+}
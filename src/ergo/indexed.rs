@@ -0,0 +1,82 @@
+//! Check the requested columns against the file info indexed by Everything.
+//!
+//! The size, dates and attributes are only answered quickly when they are indexed (Tools >
+//! Options > Indexes), otherwise the columns may come back empty without any error. Check or
+//! prune the request flags before querying to catch it early.
+
+use super::logging::warn;
+use super::{
+    access, EverythingError, EverythingGlobal, EverythingSearcher, FileInfoType, RequestFlags,
+    Result,
+};
+use crate::raw;
+
+/// The request flags backed by the indexed file info.
+const INDEXED_FLAGS: [(RequestFlags, FileInfoType); 5] = [
+    (
+        RequestFlags::EVERYTHING_REQUEST_SIZE,
+        FileInfoType::EVERYTHING_IPC_FILE_INFO_FILE_SIZE,
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_CREATED,
+        FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_CREATED,
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED,
+        FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED,
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED,
+        FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED,
+    ),
+    (
+        RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+        FileInfoType::EVERYTHING_IPC_FILE_INFO_ATTRIBUTES,
+    ),
+];
+
+/// The ones in `flags` whose file info is not indexed.
+fn unindexed(flags: RequestFlags) -> Result<RequestFlags> {
+    let mut missing = RequestFlags::empty();
+    for (flag, file_info_type) in INDEXED_FLAGS {
+        if !flags.contains(flag) {
+            continue;
+        }
+        let indexed = raw::Everything_IsFileInfoIndexed(&access(), file_info_type)?;
+        if !indexed {
+            missing |= flag;
+        }
+    }
+    Ok(missing)
+}
+
+impl EverythingGlobal {
+    /// The request flags in `flags` whose file info is not indexed, empty if all are indexed.
+    pub fn unindexed_flags(&self, flags: RequestFlags) -> Result<RequestFlags> {
+        unindexed(flags)
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Fail with [`EverythingError::NotIndexed`] if any of the current request flags is not
+    /// indexed.
+    pub fn check_indexed(&self) -> Result<()> {
+        let missing = unindexed(self.get_request_flags())?;
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(EverythingError::NotIndexed(missing))
+        }
+    }
+
+    /// Remove the request flags not indexed (with a warning in the log), and return them.
+    pub fn prune_unindexed(&mut self) -> Result<RequestFlags> {
+        let flags = self.get_request_flags();
+        let missing = unindexed(flags)?;
+        if !missing.is_empty() {
+            warn!("pruned the request flags not indexed by Everything: {missing:?}");
+            self.set_request_flags(flags - missing);
+        }
+        Ok(missing)
+    }
+}
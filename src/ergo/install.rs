@@ -0,0 +1,188 @@
+//! Where and how Everything is installed, by the registry and the default install folders.
+//!
+//! None of these talk to Everything, so they work before the first query, or while it is not
+//! running at all, which is what the diagnostics need.
+
+use std::ffi::OsString;
+use std::fs;
+use std::os::windows::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+
+use windows::core::HSTRING;
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegGetValueW, RegOpenKeyExW, HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+    KEY_READ, REG_ROUTINE_FLAGS, RRF_RT_REG_SZ, RRF_SUBKEY_WOW6432KEY, RRF_SUBKEY_WOW6464KEY,
+};
+
+use super::Instance;
+
+/// The uninstall entries of Everything 1.4 and 1.5 alpha.
+const UNINSTALL_KEYS: [&str; 2] = [
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall\Everything",
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall\Everything 1.5a",
+];
+
+/// The names of the Everything service, of Everything 1.4 and 1.5 alpha.
+pub const SERVICE_NAMES: [&str; 2] = ["Everything", "Everything (1.5a)"];
+
+const EXE_NAMES: [&str; 2] = ["Everything.exe", "Everything64.exe"];
+
+/// An installation of Everything found by [`find`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Installation {
+    /// The path of `Everything.exe`.
+    pub exe: PathBuf,
+    /// The version in the uninstall entry, like `1.4.1.1024 (x64)`, `None` if it is found in the
+    /// default install folders only (e.g. the portable one copied there).
+    pub version: Option<String>,
+    /// Whether the Everything service is installed, which does the indexing with the admin
+    /// rights for the client running as the standard user.
+    pub service: bool,
+    /// The instance it runs as by default. Everything 1.5 alpha runs as `1.5a` unless
+    /// `alpha_instance=0` is set in the `Everything.ini` next to the exe.
+    pub instance: Instance,
+}
+
+impl Installation {
+    /// The folder `Everything.exe` is in.
+    pub fn dir(&self) -> &Path {
+        self.exe.parent().unwrap_or(&self.exe)
+    }
+}
+
+/// The installation of Everything, by the uninstall entries in the registry (both the 64-bit
+/// and 32-bit views, for all users and then the current user), and then the default install
+/// folders in `Program Files`.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::install;
+///
+/// match install::find() {
+///     Some(found) => println!("Everything {:?} at {}", found.version, found.exe.display()),
+///     None => println!("Everything is not installed"),
+/// }
+/// ```
+pub fn find() -> Option<Installation> {
+    let from_registry = [HKEY_LOCAL_MACHINE, HKEY_CURRENT_USER]
+        .into_iter()
+        .flat_map(|root| {
+            [RRF_SUBKEY_WOW6464KEY, RRF_SUBKEY_WOW6432KEY]
+                .into_iter()
+                .flat_map(move |view| UNINSTALL_KEYS.map(|key| (root, view, key)))
+        })
+        .filter_map(|(root, view, key)| {
+            let dir = registry_string(root, key, "InstallLocation", view)?;
+            let version = registry_string(root, key, "DisplayVersion", view)
+                .map(|version| version.to_string_lossy().into_owned());
+            Some((PathBuf::from(dir), version))
+        });
+    let known = ["ProgramW6432", "ProgramFiles", "ProgramFiles(x86)"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| (Path::new(&dir).join("Everything"), None));
+    let (exe, version) = from_registry.chain(known).find_map(|(dir, version)| {
+        let exe = EXE_NAMES
+            .map(|exe| dir.join(exe))
+            .into_iter()
+            .find(|exe| exe.is_file())?;
+        Some((exe, version))
+    })?;
+    let instance = default_instance(&exe, version.as_deref());
+    Some(Installation {
+        exe,
+        version,
+        service: is_service_installed(),
+        instance,
+    })
+}
+
+/// The path of `Everything.exe` installed, see [`find`].
+pub fn find_exe() -> Option<PathBuf> {
+    find().map(|found| found.exe)
+}
+
+/// Whether the Everything service (of any version) is installed, running or not.
+pub fn is_service_installed() -> bool {
+    SERVICE_NAMES.into_iter().any(|name| {
+        let key = HSTRING::from(format!(r"SYSTEM\CurrentControlSet\Services\{name}"));
+        let mut hkey = HKEY::default();
+        let status = unsafe { RegOpenKeyExW(HKEY_LOCAL_MACHINE, &key, 0, KEY_READ, &mut hkey) };
+        if status == ERROR_SUCCESS {
+            unsafe {
+                let _ = RegCloseKey(hkey);
+            }
+            true
+        } else {
+            false
+        }
+    })
+}
+
+fn default_instance(exe: &Path, version: Option<&str>) -> Instance {
+    // Like `1.5.0.1383a (x64)`, the alpha has the `a` after the build number.
+    let is_alpha = version.is_some_and(|version| {
+        version.starts_with("1.5.")
+            && version
+                .split_whitespace()
+                .next()
+                .is_some_and(|number| number.ends_with('a'))
+    });
+    if !is_alpha {
+        return Instance::Default;
+    }
+    let ini = exe.with_file_name("Everything.ini");
+    let disabled = fs::read_to_string(ini).is_ok_and(|ini| {
+        ini.lines()
+            .any(|line| line.trim().replace(' ', "") == "alpha_instance=0")
+    });
+    if disabled {
+        Instance::Default
+    } else {
+        Instance::alpha()
+    }
+}
+
+/// The `REG_SZ` value, `None` if not found.
+fn registry_string(
+    root: HKEY,
+    subkey: &str,
+    value: &str,
+    view: REG_ROUTINE_FLAGS,
+) -> Option<OsString> {
+    let (subkey, value) = (HSTRING::from(subkey), HSTRING::from(value));
+    let flags = RRF_RT_REG_SZ | view;
+    let mut size = 0u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            &subkey,
+            &value,
+            flags,
+            None,
+            None,
+            Some(&mut size as *mut u32),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let mut buf = vec![0u16; (size as usize + 1) / 2];
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            &subkey,
+            &value,
+            flags,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut size as *mut u32),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    Some(OsString::from_wide(&buf[..len]))
+}
@@ -0,0 +1,168 @@
+//! Which Everything instance to talk to, and the fallback chain over the versions.
+//!
+//! Everything 1.5 alpha runs as the named instance `1.5a` by default, alongside or instead of
+//! the default instance of Everything 1.4, and they have their own IPC windows. So the apps
+//! should try both of them to work out of the box, see [`EverythingGlobal::connect`].
+
+use std::ffi::OsStr;
+use std::fmt;
+
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::FindWindowW;
+
+use super::logging::debug;
+use super::{access, EverythingError, EverythingGlobal, Result};
+use crate::raw;
+
+/// An Everything instance, by its name.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Instance {
+    /// The default (unnamed) instance, that is Everything 1.4, or 1.5 with `alpha_instance=0`.
+    ///
+    /// It is the one the SDK talks to if no instance is set, the same for `IpcClient::new` and
+    /// [`EverythingGlobal::ensure_running`].
+    #[default]
+    Default,
+    /// The named instance, which is started by `Everything.exe -instance <name>`.
+    Named(String),
+}
+
+impl Instance {
+    /// The name of Everything 1.5 alpha.
+    pub const ALPHA_NAME: &'static str = "1.5a";
+
+    /// The instance of Everything 1.5 alpha, `1.5a`.
+    pub fn alpha() -> Self {
+        Instance::Named(Self::ALPHA_NAME.to_string())
+    }
+
+    /// The instances tried by [`EverythingGlobal::connect`], in order: Everything 1.5 alpha,
+    /// and then Everything 1.4.
+    pub fn fallback_chain() -> [Instance; 2] {
+        [Instance::alpha(), Instance::Default]
+    }
+
+    /// `None` for the default instance.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Instance::Default => None,
+            Instance::Named(name) => Some(name),
+        }
+    }
+
+    /// The class of the IPC window, `EVERYTHING_TASKBAR_NOTIFICATION_(<name>)`, or without the
+    /// name for the default instance.
+    pub fn window_class(&self) -> String {
+        match self.name() {
+            None => IPC_WNDCLASS.to_string(),
+            Some(name) => format!("{IPC_WNDCLASS}_({name})"),
+        }
+    }
+
+    /// The inverse of [`Instance::window_class`], `None` if not a class of Everything.
+    fn from_window_class(class: &str) -> Option<Self> {
+        let rest = class.strip_prefix(IPC_WNDCLASS)?;
+        if rest.is_empty() {
+            return Some(Instance::Default);
+        }
+        let name = rest.strip_prefix("_(")?.strip_suffix(')')?;
+        Some(Instance::Named(name.to_string()))
+    }
+
+    /// The IPC window, `None` if the instance is not running.
+    pub(crate) fn ipc_window(&self) -> Option<HWND> {
+        find_ipc_window(self.window_class().as_ref())
+    }
+
+    /// Whether the instance is running, by looking for its IPC window.
+    ///
+    /// No IPC call is made, so it is cheap, and the last error of the SDK is untouched. Not
+    /// whether the database is loaded, see [`EverythingGlobal::is_db_loaded`] for that.
+    pub fn is_running(&self) -> bool {
+        self.ipc_window().is_some()
+    }
+}
+
+const IPC_WNDCLASS: &str = "EVERYTHING_TASKBAR_NOTIFICATION";
+
+fn find_ipc_window(class: &OsStr) -> Option<HWND> {
+    let class = HSTRING::from(class);
+    let hwnd = unsafe { FindWindowW(&class, PCWSTR::null()) };
+    (hwnd.0 != 0).then_some(hwnd)
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instance::Default => f.write_str("(default)"),
+            Instance::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl EverythingGlobal {
+    /// Talk to the `instance` from now on, all the IPC calls are affected.
+    ///
+    /// It does not check whether the instance is running, see [`EverythingGlobal::connect`].
+    /// Fail with [`EverythingError::InvalidParameter`] if the name is too long or has a nul.
+    pub fn set_instance(&mut self, instance: Instance) -> Result<()> {
+        let name = instance.name().map(OsStr::new);
+        if raw::Everything_SetInstanceName(&access(), name) {
+            self.instance = Some(instance);
+            Ok(())
+        } else {
+            Err(EverythingError::InvalidParameter)
+        }
+    }
+
+    /// The instance set by [`EverythingGlobal::set_instance`] or found by
+    /// [`EverythingGlobal::connect`], `None` if neither has been called.
+    pub fn instance(&self) -> Option<&Instance> {
+        self.instance.as_ref()
+    }
+
+    /// The instance the SDK actually talks to, by the class of the IPC window it looks for.
+    ///
+    /// It is the default instance unless set, by [`EverythingGlobal::set_instance`] or by
+    /// [`raw`] directly.
+    pub(crate) fn target_instance(&self) -> Instance {
+        let class = raw::Everything_GetIpcWindowClass(&access());
+        class
+            .to_str()
+            .and_then(Instance::from_window_class)
+            .unwrap_or_default()
+    }
+
+    /// Whether Everything of the current instance is running, see [`Instance::is_running`].
+    ///
+    /// The IPC window is looked up by the class the SDK actually talks to, so it agrees with the
+    /// following IPC calls even if the instance was set by [`raw`] directly. Check this before
+    /// the first query to tell "not running" from "the database not loaded", without an IPC
+    /// error.
+    pub fn is_everything_running(&self) -> bool {
+        find_ipc_window(&raw::Everything_GetIpcWindowClass(&access())).is_some()
+    }
+
+    /// Find the running instance by [`Instance::fallback_chain`], and talk to it from now on.
+    ///
+    /// Return the instance used, which is recorded in [`EverythingGlobal::instance`] too.
+    pub fn connect(&mut self) -> Result<Instance> {
+        self.connect_with(&Instance::fallback_chain())
+    }
+
+    /// Try the instances in order, the first one responding to IPC is used.
+    ///
+    /// Fail with [`EverythingError::Ipc`] if none of them is running, and then the last one
+    /// tried is left in use.
+    pub fn connect_with(&mut self, chain: &[Instance]) -> Result<Instance> {
+        for instance in chain {
+            self.set_instance(instance.clone())?;
+            if instance.is_running() && self.get_major_version().is_ok() {
+                debug!("connected to the Everything instance {instance}");
+                return Ok(instance.clone());
+            }
+        }
+        Err(EverythingError::Ipc)
+    }
+}
@@ -0,0 +1,314 @@
+//! Talk to Everything over `WM_COPYDATA` in pure Rust, without the global state of the SDK.
+//!
+//! Everything-SDK keeps one search state and one reply buffer for the whole process, which is
+//! why [`global`](super::global) is a lock. An [`IpcClient`] builds `EVERYTHING_IPC_QUERY2`
+//! by itself instead, and receives the reply in a hidden window of its own, on a thread of its
+//! own for each query. So the clients are independent of each other and of the global lock,
+//! and any number of queries can run at the same time.
+//!
+//! It needs Everything 1.4.1 or later (the version 2 of the query).
+//!
+//! Only the queries of the clients skip the SDK. The `ipc` feature does not remove the SDK
+//! from the build: `everything-sdk-sys` still compiles `Everything.c` (which needs a C
+//! compiler like MSVC), as the rest of this crate, [`global`](super::global) included, is
+//! built on it.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::ipc::IpcClient;
+//! use everything_sdk::*;
+//!
+//! let client = IpcClient::new();
+//! let results = client.search(&SearchOptions::new("*.rs"))?;
+//! for item in results.items() {
+//!     println!("{:?}", item.full_path);
+//! }
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::thread;
+use std::time::Duration;
+
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    ChangeWindowMessageFilterEx, CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW,
+    GetMessageW, PostQuitMessage, RegisterClassExW, SendMessageW, SetTimer, TranslateMessage, MSG,
+    MSGFLT_ALLOW, WINDOW_EX_STYLE, WINDOW_STYLE, WM_COPYDATA, WM_TIMER, WM_USER, WNDCLASSEXW,
+};
+
+use super::backend::SearchBackend;
+use super::{EverythingError, Instance, OwnedResults, Result, SearchOptions};
+use crate::raw::{copydata_of, parse_query_reply, QueryReply, QueryVersion};
+
+const EVERYTHING_WM_IPC: u32 = WM_USER;
+const EVERYTHING_IPC_GET_MAJOR_VERSION: usize = 0;
+const EVERYTHING_IPC_GET_MINOR_VERSION: usize = 1;
+const EVERYTHING_IPC_GET_REVISION: usize = 2;
+const EVERYTHING_IPC_GET_BUILD_NUMBER: usize = 3;
+const EVERYTHING_IPC_IS_DB_LOADED: usize = 401;
+const EVERYTHING_IPC_COPYDATA_QUERY2W: usize = 18;
+
+const EVERYTHING_IPC_MATCHCASE: u32 = 0x00000001;
+const EVERYTHING_IPC_MATCHWHOLEWORD: u32 = 0x00000002;
+const EVERYTHING_IPC_MATCHPATH: u32 = 0x00000004;
+const EVERYTHING_IPC_REGEX: u32 = 0x00000008;
+
+/// The class of the reply windows, registered once per process.
+const REPLY_WNDCLASS: &str = "EVERYTHING_SDK_RS_IPC";
+/// The `dwData` of the reply, any number works as each window waits for one reply only.
+const REPLY_ID: u32 = 0x4553_5253; // "ESRS"
+const TIMEOUT_TIMER_ID: usize = 1;
+
+thread_local! {
+    /// The reply received by the window of the query thread.
+    static REPLY: RefCell<Option<Vec<u8>>> = RefCell::new(None);
+}
+
+/// An independent connection to an Everything instance over IPC.
+///
+/// It is cheap, nothing is kept open between the queries.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IpcClient {
+    instance: Instance,
+    timeout: Duration,
+}
+
+impl Default for IpcClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IpcClient {
+    /// The client of the default instance, with the timeout of 30 seconds.
+    pub fn new() -> Self {
+        Self::with_instance(Instance::Default)
+    }
+
+    pub fn with_instance(instance: Instance) -> Self {
+        IpcClient {
+            instance,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    /// How long to wait for the reply of each query, then [`EverythingError::Timeout`].
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// The IPC window of the instance, fail with [`EverythingError::Ipc`] if not running.
+    fn everything_window(&self) -> Result<HWND> {
+        self.instance.ipc_window().ok_or(EverythingError::Ipc)
+    }
+
+    fn send_ipc(&self, command: usize) -> Result<u32> {
+        let hwnd = self.everything_window()?;
+        let ret = unsafe { SendMessageW(hwnd, EVERYTHING_WM_IPC, WPARAM(command), LPARAM(0)) };
+        Ok(ret.0 as u32)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.instance.is_running()
+    }
+
+    /// The (major, minor, revision, build) version of Everything.
+    pub fn version(&self) -> Result<(u32, u32, u32, u32)> {
+        Ok((
+            self.send_ipc(EVERYTHING_IPC_GET_MAJOR_VERSION)?,
+            self.send_ipc(EVERYTHING_IPC_GET_MINOR_VERSION)?,
+            self.send_ipc(EVERYTHING_IPC_GET_REVISION)?,
+            self.send_ipc(EVERYTHING_IPC_GET_BUILD_NUMBER)?,
+        ))
+    }
+
+    pub fn is_db_loaded(&self) -> Result<bool> {
+        Ok(self.send_ipc(EVERYTHING_IPC_IS_DB_LOADED)? != 0)
+    }
+
+    /// Query by the `options` and wait for the decoded reply.
+    pub fn query(&self, options: &SearchOptions) -> Result<QueryReply> {
+        let everything = self.everything_window()?;
+        let timeout = self.timeout;
+        let data = thread::scope(|s| {
+            thread::Builder::new()
+                .name("everything-sdk-ipc".to_string())
+                .spawn_scoped(s, || query_on_this_thread(everything, options, timeout))
+                .map_err(|_| EverythingError::CreateThread)?
+                .join()
+                .unwrap_or(Err(EverythingError::CreateThread))
+        })?;
+        parse_query_reply(&data, QueryVersion::V2).ok_or(EverythingError::Ipc)
+    }
+
+    /// Query by the `options` into a snapshot, the same as
+    /// [`SearchOptions::query_snapshot`] without the global lock.
+    pub fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        let reply = self.query(options)?;
+        Ok(OwnedResults::from_reply(reply, options.clone()))
+    }
+}
+
+impl SearchBackend for IpcClient {
+    fn name(&self) -> &str {
+        "Everything"
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_db_loaded().unwrap_or(false)
+    }
+
+    fn search(&self, options: &SearchOptions) -> Result<OwnedResults> {
+        IpcClient::search(self, options)
+    }
+}
+
+/// The `EVERYTHING_IPC_QUERY2` (`#pragma pack(1)`): 7 DWORDs and then the null-terminated
+/// search text.
+fn query2_bytes(reply_hwnd: HWND, options: &SearchOptions) -> Vec<u8> {
+    let mut search_flags = 0;
+    for (on, flag) in [
+        (options.match_case, EVERYTHING_IPC_MATCHCASE),
+        (options.match_whole_word, EVERYTHING_IPC_MATCHWHOLEWORD),
+        (options.match_path, EVERYTHING_IPC_MATCHPATH),
+        (options.regex, EVERYTHING_IPC_REGEX),
+    ] {
+        if on {
+            search_flags |= flag;
+        }
+    }
+    let header = [
+        reply_hwnd.0 as u32, // the window handles are 32-bit values even in 64-bit Windows
+        REPLY_ID,
+        search_flags,
+        options.offset,
+        options.max,
+        options.request_flags.bits(),
+        options.sort.to_u32(),
+    ];
+    let mut bytes = Vec::with_capacity(header.len() * 4 + (options.search.len() + 1) * 2);
+    bytes.extend(header.iter().flat_map(|n| n.to_le_bytes()));
+    bytes.extend(
+        options
+            .search
+            .encode_utf16()
+            .chain([0])
+            .flat_map(u16::to_le_bytes),
+    );
+    bytes
+}
+
+/// Register the class of the reply windows, it is fine if registered already.
+fn register_class() -> Result<HSTRING> {
+    let class = HSTRING::from(REPLY_WNDCLASS);
+    let instance =
+        unsafe { GetModuleHandleW(None) }.map_err(|_| EverythingError::RegisterClassEx)?;
+    let wcex = WNDCLASSEXW {
+        cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+        lpfnWndProc: Some(reply_wndproc),
+        hInstance: instance.into(),
+        lpszClassName: PCWSTR(class.as_ptr()),
+        ..Default::default()
+    };
+    // It fails with ERROR_CLASS_ALREADY_EXISTS after the first time, and then creating the
+    // window tells whether the class is really there.
+    unsafe { RegisterClassExW(&wcex) };
+    Ok(class)
+}
+
+/// Create the reply window, send the query, and pump the messages until the reply or the
+/// timeout. Only on a thread of its own, as `PostQuitMessage` ends the message loop.
+fn query_on_this_thread(
+    everything: HWND,
+    options: &SearchOptions,
+    timeout: Duration,
+) -> Result<Vec<u8>> {
+    let class = register_class()?;
+    let hwnd = unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            &class,
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+    if hwnd.0 == 0 {
+        return Err(EverythingError::CreateWindow);
+    }
+    // Allow the reply from the lower integrity level, such as Everything not run as admin.
+    let _ = unsafe { ChangeWindowMessageFilterEx(hwnd, WM_COPYDATA, MSGFLT_ALLOW, None) };
+
+    let query = query2_bytes(hwnd, options);
+    let cds = COPYDATASTRUCT {
+        dwData: EVERYTHING_IPC_COPYDATA_QUERY2W,
+        cbData: query.len() as u32,
+        lpData: query.as_ptr() as *mut c_void,
+    };
+    let sent = unsafe {
+        SendMessageW(
+            everything,
+            WM_COPYDATA,
+            WPARAM(hwnd.0 as usize),
+            LPARAM(&cds as *const COPYDATASTRUCT as isize),
+        )
+    };
+    let reply = if sent.0 == 0 {
+        Err(EverythingError::Ipc)
+    } else {
+        let millis = timeout.as_millis().clamp(1, u32::MAX as u128) as u32;
+        unsafe { SetTimer(hwnd, TIMEOUT_TIMER_ID, millis, None) };
+        let mut msg = MSG::default();
+        while unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 > 0 {
+            unsafe {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+        REPLY
+            .with(|reply| reply.borrow_mut().take())
+            .ok_or(EverythingError::Timeout)
+    };
+    let _ = unsafe { DestroyWindow(hwnd) };
+    reply
+}
+
+extern "system" fn reply_wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_COPYDATA => {
+            // SAFETY: the `lparam` of WM_COPYDATA is a valid COPYDATASTRUCT during the call.
+            if let Some((REPLY_ID, data)) = unsafe { copydata_of(lparam) } {
+                REPLY.with(|reply| *reply.borrow_mut() = Some(data.to_vec()));
+                unsafe { PostQuitMessage(0) };
+                return LRESULT(1);
+            }
+        }
+        WM_TIMER if wparam.0 == TIMEOUT_TIMER_ID => {
+            unsafe { PostQuitMessage(0) };
+            return LRESULT(0);
+        }
+        _ => {}
+    }
+    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+}
@@ -0,0 +1,152 @@
+//! Start Everything if it is not running, and wait for the database, which every app started
+//! at login needs before the first query.
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::logging::debug;
+use super::{install, EverythingError, EverythingGlobal, Result};
+
+/// The options of [`EverythingGlobal::ensure_running`].
+#[derive(Clone, Debug)]
+pub struct LaunchOptions {
+    /// The `Everything.exe` to start, found by [`install::find_exe`] if `None`.
+    pub path: Option<PathBuf>,
+    /// Start in the background (`-startup`), without showing the search window.
+    pub startup: bool,
+    /// How long to wait for Everything to start and load the database.
+    pub timeout: Duration,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        LaunchOptions {
+            path: None,
+            startup: true,
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+const FIRST_DELAY: Duration = Duration::from_millis(50);
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// The delays between the polls, doubling up to [`MAX_DELAY`] and ending at the deadline.
+struct Backoff {
+    deadline: Instant,
+    delay: Duration,
+}
+
+impl Backoff {
+    fn new(timeout: Duration) -> Self {
+        Backoff {
+            deadline: Instant::now() + timeout,
+            delay: FIRST_DELAY,
+        }
+    }
+
+    /// The delay before the next poll, or `None` if the time is up.
+    fn next_delay(&mut self) -> Option<Duration> {
+        let left = self.deadline.checked_duration_since(Instant::now())?;
+        if left.is_zero() {
+            return None;
+        }
+        let delay = self.delay.min(left);
+        self.delay = (self.delay * 2).min(MAX_DELAY);
+        Some(delay)
+    }
+}
+
+/// Sleep on a thread of its own, so it works with any executor.
+#[cfg(feature = "async")]
+async fn sleep(delay: Duration) {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(delay);
+        let _ = sender.send(());
+    });
+    let _ = receiver.await;
+}
+
+impl EverythingGlobal {
+    /// Make sure Everything is running with the database loaded, start it if not.
+    ///
+    /// If the IPC is not available, it starts `Everything.exe` (of the current instance, see
+    /// [`EverythingGlobal::set_instance`]), and then waits for the database in any case. Fail
+    /// with [`EverythingError::Timeout`] if not ready in time, or [`EverythingError::NotFound`]
+    /// if Everything is not installed.
+    ///
+    /// It blocks the current thread while waiting, even with the `async` feature.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// everything.ensure_running(&launch::LaunchOptions::default())?;
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn ensure_running(&mut self, options: &LaunchOptions) -> Result<()> {
+        let start = Instant::now();
+        match self.is_db_loaded() {
+            Ok(true) => return Ok(()),
+            Ok(false) => debug!("Everything is running, waiting for the database"),
+            Err(EverythingError::Ipc) => self.launch(options)?,
+            Err(e) => return Err(e),
+        }
+        self.wait_for_db_loaded(options.timeout.saturating_sub(start.elapsed()))
+    }
+
+    /// Block until the database is loaded, polling [`EverythingGlobal::is_db_loaded`] with the
+    /// backoff (from 50ms up to 1s). Fail with [`EverythingError::Timeout`] if not in `timeout`.
+    ///
+    /// Everything not running yet counts as not loaded, so it works while Everything is still
+    /// starting up too, and after [`EverythingGlobal::rebuild_db`].
+    pub fn wait_for_db_loaded(&self, timeout: Duration) -> Result<()> {
+        let mut backoff = Backoff::new(timeout);
+        while !self.poll_db_loaded()? {
+            let delay = backoff.next_delay().ok_or(EverythingError::Timeout)?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    /// The same as [`EverythingGlobal::wait_for_db_loaded`], but without blocking the executor.
+    #[cfg(feature = "async")]
+    pub async fn wait_for_db_loaded_async(&self, timeout: Duration) -> Result<()> {
+        let mut backoff = Backoff::new(timeout);
+        while !self.poll_db_loaded()? {
+            let delay = backoff.next_delay().ok_or(EverythingError::Timeout)?;
+            sleep(delay).await;
+        }
+        Ok(())
+    }
+
+    fn poll_db_loaded(&self) -> Result<bool> {
+        match self.is_db_loaded() {
+            Err(EverythingError::Ipc) => Ok(false),
+            loaded => loaded,
+        }
+    }
+
+    fn launch(&self, options: &LaunchOptions) -> Result<()> {
+        let path = match &options.path {
+            Some(path) => path.clone(),
+            None => install::find_exe().ok_or(EverythingError::NotFound)?,
+        };
+        let mut command = Command::new(&path);
+        if options.startup {
+            command.arg("-startup");
+        }
+        // the instance the following IPC calls look for, not only the one recorded here
+        if let Some(name) = self.target_instance().name() {
+            command.arg("-instance").arg(name);
+        }
+        debug!("launch {command:?}");
+        // Everything keeps running in the background, so never wait for it.
+        command.spawn().map_err(EverythingError::Launch)?;
+        Ok(())
+    }
+}
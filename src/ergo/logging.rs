@@ -0,0 +1,30 @@
+//! The debug output, to `tracing` (by default) or to `log`, or compiled out without both.
+//!
+//! If both the `tracing` and the `log` features are enabled, `tracing` wins, whose own `log`
+//! feature forwards to `log` anyway.
+
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        ::log::debug!($($arg)*);
+        // Still type check the arguments, and keep them used.
+        #[cfg(not(any(feature = "tracing", feature = "log")))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)*);
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        ::log::warn!($($arg)*);
+        #[cfg(not(any(feature = "tracing", feature = "log")))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+pub(crate) use debug;
+pub(crate) use warn;
@@ -0,0 +1,192 @@
+//! The serializable request and response of a search. (feature `serde`)
+//!
+//! For the frameworks passing the data across a serialization boundary, like the commands of
+//! [Tauri](https://tauri.app), so the frontend and the backend can share these types instead of
+//! defining their own DTOs.
+//!
+//! The texts are in UTF-8 (converted lossily), the dates are the FILETIME numbers (see
+//! [`FileTime`](super::FileTime)), and [`SortType`] and [`RequestFlags`] are in their numeric
+//! values.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    EverythingError, ItemData, ItemKind, OwnedResults, RequestFlags, Result, SearchOptions,
+    SortType,
+};
+
+/// The serializable mirror of [`SearchOptions`], all fields are optional when deserializing.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchRequest {
+    pub search: String,
+    pub match_path: bool,
+    pub match_case: bool,
+    pub match_whole_word: bool,
+    pub regex: bool,
+    pub max: u32,
+    pub offset: u32,
+    /// The value of [`SortType`].
+    pub sort: u32,
+    /// The bits of [`RequestFlags`].
+    pub request_flags: u32,
+}
+
+impl Default for SearchRequest {
+    fn default() -> Self {
+        SearchRequest::from(&SearchOptions::default())
+    }
+}
+
+impl SearchRequest {
+    /// The `search` text with all the others default.
+    pub fn new(search: impl Into<String>) -> Self {
+        SearchRequest {
+            search: search.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&SearchOptions> for SearchRequest {
+    fn from(options: &SearchOptions) -> Self {
+        SearchRequest {
+            search: options.search.clone(),
+            match_path: options.match_path,
+            match_case: options.match_case,
+            match_whole_word: options.match_whole_word,
+            regex: options.regex,
+            max: options.max,
+            offset: options.offset,
+            sort: options.sort.to_u32(),
+            request_flags: options.request_flags.bits(),
+        }
+    }
+}
+
+impl TryFrom<SearchRequest> for SearchOptions {
+    type Error = EverythingError;
+
+    /// Fail with [`EverythingError::InvalidParameter`] on the unknown sort type or request flags.
+    fn try_from(request: SearchRequest) -> Result<Self> {
+        let sort = match SortType::from(request.sort) {
+            SortType::Unknown(_) => return Err(EverythingError::InvalidParameter),
+            sort => sort,
+        };
+        let request_flags = RequestFlags::from_bits(request.request_flags)
+            .ok_or(EverythingError::InvalidParameter)?;
+        Ok(SearchOptions {
+            search: request.search,
+            match_path: request.match_path,
+            match_case: request.match_case,
+            match_whole_word: request.match_whole_word,
+            regex: request.regex,
+            max: request.max,
+            offset: request.offset,
+            sort,
+            request_flags,
+        })
+    }
+}
+
+/// The serializable mirror of [`ItemData`], only the requested fields are present.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchItem {
+    pub index: u32,
+    pub kind: ItemKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_created: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_accessed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_list_filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_run: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_recently_changed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlighted_filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlighted_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlighted_full_path_and_filename: Option<String>,
+}
+
+impl From<&ItemData> for SearchItem {
+    fn from(data: &ItemData) -> Self {
+        let text =
+            |s: &Option<std::ffi::OsString>| s.as_ref().map(|s| s.to_string_lossy().into_owned());
+        let path =
+            |p: &Option<std::path::PathBuf>| p.as_ref().map(|p| p.to_string_lossy().into_owned());
+        SearchItem {
+            index: data.index,
+            kind: data.kind,
+            filename: text(&data.filename),
+            path: path(&data.path),
+            full_path: path(&data.full_path),
+            extension: text(&data.extension),
+            size: data.size,
+            date_created: data.date_created,
+            date_modified: data.date_modified,
+            date_accessed: data.date_accessed,
+            attributes: data.attributes,
+            file_list_filename: text(&data.file_list_filename),
+            run_count: data.run_count,
+            date_run: data.date_run,
+            date_recently_changed: data.date_recently_changed,
+            highlighted_filename: text(&data.highlighted_filename),
+            highlighted_path: text(&data.highlighted_path),
+            highlighted_full_path_and_filename: text(&data.highlighted_full_path_and_filename),
+        }
+    }
+}
+
+/// The serializable mirror of [`OwnedResults`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchResponse {
+    /// The number of all the results, not only the visible ones in `items`.
+    pub total: u32,
+    /// The value of [`SortType`].
+    pub sort: u32,
+    /// The bits of [`RequestFlags`].
+    pub request_flags: u32,
+    pub items: Vec<SearchItem>,
+}
+
+impl From<&OwnedResults> for SearchResponse {
+    fn from(results: &OwnedResults) -> Self {
+        SearchResponse {
+            total: results.total(),
+            sort: results.sort_type().to_u32(),
+            request_flags: results.request_flags().bits(),
+            items: results.iter().map(SearchItem::from).collect(),
+        }
+    }
+}
+
+impl From<OwnedResults> for SearchResponse {
+    fn from(results: OwnedResults) -> Self {
+        SearchResponse::from(&results)
+    }
+}
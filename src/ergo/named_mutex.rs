@@ -0,0 +1,104 @@
+//! The cross-process coordination by a named mutex.
+//!
+//! The [`global`](super::global) lock only serializes the IPC calls in this process. When
+//! several tools using this crate query at the same time, Everything handles their IPC calls
+//! one by one anyway, and the ones waiting too long may fail. Hold the [`NamedMutex`] around
+//! the queries to line them up across the processes too, cooperatively.
+//!
+//! ```no_run
+//! use everything_sdk::{named_mutex::NamedMutex, *};
+//!
+//! let mutex = NamedMutex::open().unwrap();
+//! let _guard = mutex.lock().unwrap(); // take it before the global lock
+//! let mut everything = global().lock().unwrap();
+//! let mut searcher = everything.searcher();
+//! let _results = searcher.set_search("cargo.toml").query();
+//! ```
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use windows::core::{Result, HSTRING};
+use windows::Win32::Foundation::{
+    CloseHandle, FALSE, HANDLE, WAIT_ABANDONED, WAIT_OBJECT_0, WAIT_TIMEOUT,
+};
+use windows::Win32::System::Threading::{
+    CreateMutexW, ReleaseMutex, WaitForSingleObject, INFINITE,
+};
+
+/// The named mutex shared by all the processes in all the sessions.
+#[derive(Debug)]
+pub struct NamedMutex {
+    handle: HANDLE,
+}
+
+impl NamedMutex {
+    /// The name used by [`NamedMutex::open`], for all the processes using this crate.
+    pub const DEFAULT_NAME: &'static str = r"Global\everything-sdk-rs";
+
+    /// Open (or create) the mutex of [`NamedMutex::DEFAULT_NAME`].
+    pub fn open() -> Result<Self> {
+        Self::open_named(Self::DEFAULT_NAME)
+    }
+
+    /// Open (or create) the mutex of `name`, such as `Local\my-app` for the current session only.
+    pub fn open_named(name: &str) -> Result<Self> {
+        let handle = unsafe { CreateMutexW(None, FALSE, &HSTRING::from(name))? };
+        Ok(NamedMutex { handle })
+    }
+
+    /// Block until the mutex is acquired.
+    pub fn lock(&self) -> Result<NamedMutexGuard<'_>> {
+        self.wait(INFINITE)
+            .map(|guard| guard.expect("no timeout for INFINITE"))
+    }
+
+    /// Acquire the mutex if it is free now, `None` otherwise.
+    pub fn try_lock(&self) -> Result<Option<NamedMutexGuard<'_>>> {
+        self.wait(0)
+    }
+
+    /// Block until the mutex is acquired, `None` if the `timeout` elapsed.
+    pub fn lock_timeout(&self, timeout: Duration) -> Result<Option<NamedMutexGuard<'_>>> {
+        // `INFINITE` is u32::MAX, so keep it finite.
+        let millis = u32::try_from(timeout.as_millis()).unwrap_or(INFINITE - 1);
+        self.wait(millis)
+    }
+
+    fn wait(&self, millis: u32) -> Result<Option<NamedMutexGuard<'_>>> {
+        let event = unsafe { WaitForSingleObject(self.handle, millis) };
+        // The abandoned one (its owner exited without releasing) is acquired too, and there is
+        // nothing to recover for the IPC calls.
+        if event.0 == WAIT_OBJECT_0.0 || event.0 == WAIT_ABANDONED.0 {
+            Ok(Some(NamedMutexGuard {
+                mutex: self,
+                _not_send: PhantomData,
+            }))
+        } else if event.0 == WAIT_TIMEOUT.0 {
+            Ok(None)
+        } else {
+            Err(windows::core::Error::from_win32())
+        }
+    }
+}
+
+impl Drop for NamedMutex {
+    fn drop(&mut self) {
+        let _ = unsafe { CloseHandle(self.handle) };
+    }
+}
+
+/// The mutex is released when it is dropped.
+///
+/// It is not `Send`, as a mutex can only be released by the thread which acquired it.
+#[derive(Debug)]
+pub struct NamedMutexGuard<'a> {
+    mutex: &'a NamedMutex,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for NamedMutexGuard<'_> {
+    fn drop(&mut self) {
+        let _ = unsafe { ReleaseMutex(self.mutex.handle) };
+    }
+}
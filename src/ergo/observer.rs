@@ -0,0 +1,156 @@
+//! Hooks on every query, for the logging, the metrics or the progress in the UI.
+//!
+//! The observer is installed on the [`EverythingGlobal`], so it sees all the queries of the
+//! searchers, including the ones done for you, like by the
+//! [`EverythingBroker`](super::EverythingBroker) or [`spawn_query`](super::spawn_query).
+
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use super::{
+    access, EverythingError, EverythingGlobal, EverythingResults, OwnedResults, Result,
+    SearchOptions,
+};
+use crate::raw;
+
+/// The hooks called on the thread doing the query, mostly with the global lock held, so keep
+/// them quick and never query in them.
+///
+/// All of them do nothing by default.
+///
+/// # Examples
+/// ```no_run
+/// use std::sync::Arc;
+/// use everything_sdk::*;
+/// use everything_sdk::observer::{QueryObserver, QueryStats};
+///
+/// struct Log;
+///
+/// impl QueryObserver for Log {
+///     fn on_reply(&self, options: &SearchOptions, stats: &QueryStats) {
+///         println!("{:?}: {} results in {:?}", options.search, stats.total, stats.elapsed);
+///     }
+/// }
+///
+/// global().lock().unwrap().set_query_observer(Some(Arc::new(Log)));
+/// ```
+pub trait QueryObserver: Send + Sync {
+    /// The query is about to be sent.
+    fn on_query_start(&self, options: &SearchOptions) {
+        let _ = options;
+    }
+
+    /// The reply arrived.
+    fn on_reply(&self, options: &SearchOptions, stats: &QueryStats) {
+        let _ = (options, stats);
+    }
+
+    /// The query failed (or timed out) after `elapsed`.
+    fn on_error(&self, options: &SearchOptions, error: &EverythingError, elapsed: Duration) {
+        let _ = (options, error, elapsed);
+    }
+}
+
+/// The timing and the counts of a query, see [`QueryObserver::on_reply`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct QueryStats {
+    /// From sending the query to the reply.
+    pub elapsed: Duration,
+    /// The number of the visible results, limited by the `offset` and the `max`.
+    pub len: u32,
+    /// The number of all the results.
+    pub total: u32,
+}
+
+/// In a static, as there is only one [`EverythingGlobal`] anyway.
+static OBSERVER: RwLock<Option<Arc<dyn QueryObserver>>> = RwLock::new(None);
+
+impl EverythingGlobal {
+    /// Install the `observer` of all the queries (or uninstall with `None`), and return the
+    /// last one.
+    pub fn set_query_observer(
+        &mut self,
+        observer: Option<Arc<dyn QueryObserver>>,
+    ) -> Option<Arc<dyn QueryObserver>> {
+        let mut installed = OBSERVER.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *installed, observer)
+    }
+}
+
+/// A query being observed.
+pub(super) struct Observation {
+    observer: Arc<dyn QueryObserver>,
+    options: SearchOptions,
+    start: Instant,
+}
+
+/// Start observing the query with the current search state, `None` if no observer. The caller
+/// must hold the global lock.
+pub(super) fn start() -> Option<Observation> {
+    let observer = OBSERVER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()?;
+    Some(Observation::new(observer, SearchOptions::current()))
+}
+
+/// Start observing the query of `options`, `None` if no observer.
+pub(super) fn start_with(options: &SearchOptions) -> Option<Observation> {
+    let observer = OBSERVER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()?;
+    Some(Observation::new(observer, options.clone()))
+}
+
+impl Observation {
+    fn new(observer: Arc<dyn QueryObserver>, options: SearchOptions) -> Self {
+        observer.on_query_start(&options);
+        Observation {
+            observer,
+            options,
+            start: Instant::now(),
+        }
+    }
+
+    fn reply(self, len: u32, total: u32) {
+        let stats = QueryStats {
+            elapsed: self.start.elapsed(),
+            len,
+            total,
+        };
+        self.observer.on_reply(&self.options, &stats);
+    }
+
+    /// Finish with the `error`, such as of the query failed to send.
+    pub(super) fn error(self, error: &EverythingError) {
+        let elapsed = self.start.elapsed();
+        self.observer.on_error(&self.options, error, elapsed);
+    }
+
+    /// Finish with the outcome of the blocking query, the results are in the global state if
+    /// `ok`. The caller must hold the global lock.
+    pub(super) fn finish_blocking(self, ok: bool) {
+        if ok {
+            let len = raw::Everything_GetNumResults(&access());
+            self.reply(len, raw::Everything_GetTotResults(&access()));
+        } else {
+            self.error(&raw::Everything_GetLastError(&access()).into());
+        }
+    }
+
+    pub(super) fn finish(self, result: &Result<EverythingResults<'_>>) {
+        match result {
+            Ok(results) => self.reply(results.len(), results.total()),
+            Err(err) => self.error(err),
+        }
+    }
+
+    pub(super) fn finish_owned(self, result: &Result<OwnedResults>) {
+        match result {
+            Ok(results) => self.reply(results.len() as u32, results.total()),
+            Err(err) => self.error(err),
+        }
+    }
+}
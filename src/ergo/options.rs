@@ -0,0 +1,133 @@
+//! The whole search state as a plain value, which can be stored, sent and applied at once.
+
+use std::thread::{self, JoinHandle};
+
+use super::{access, global, EverythingSearcher, OwnedResults, RequestFlags, Result, SortType};
+use crate::raw;
+
+/// All the search state of [`EverythingSearcher`], with the same defaults as Everything.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SearchOptions {
+    pub search: String,
+    pub match_path: bool,
+    pub match_case: bool,
+    pub match_whole_word: bool,
+    pub regex: bool,
+    /// `u32::MAX` for all results.
+    pub max: u32,
+    pub offset: u32,
+    pub sort: SortType,
+    pub request_flags: RequestFlags,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            search: String::new(),
+            match_path: false,
+            match_case: false,
+            match_whole_word: false,
+            regex: false,
+            max: u32::MAX,
+            offset: 0,
+            sort: SortType::default(),
+            request_flags: RequestFlags::default(),
+        }
+    }
+}
+
+impl SearchOptions {
+    /// The `search` text with all the others default.
+    pub fn new(search: impl Into<String>) -> Self {
+        SearchOptions {
+            search: search.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Read the search state in the global shared states, the caller must hold (or borrow
+    /// from) the guard of the global lock.
+    pub(super) fn current() -> Self {
+        SearchOptions {
+            search: raw::Everything_GetSearch(&access())
+                .to_string_lossy()
+                .into_owned(),
+            match_path: raw::Everything_GetMatchPath(&access()),
+            match_case: raw::Everything_GetMatchCase(&access()),
+            match_whole_word: raw::Everything_GetMatchWholeWord(&access()),
+            regex: raw::Everything_GetRegex(&access()),
+            max: raw::Everything_GetMax(&access()),
+            offset: raw::Everything_GetOffset(&access()),
+            sort: raw::Everything_GetSort(&access()),
+            request_flags: raw::Everything_GetRequestFlags(&access()),
+        }
+    }
+
+    /// Take the [`global`] lock, query with the options and snapshot the results, and then
+    /// release the lock. It blocks on the lock, even with the `async` feature.
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn query_snapshot(&self) -> Result<OwnedResults> {
+        let mut everything = global()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        everything.is_db_loaded()?; // fail fast with the `Ipc` error if not running
+        let mut searcher = everything.searcher();
+        searcher.set_options(self);
+        let snapshot = searcher.query().snapshot();
+        Ok(snapshot)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn query_snapshot(&self) -> Result<OwnedResults> {
+        futures::executor::block_on(async {
+            let mut everything = global().lock().await;
+            everything.is_db_loaded()?; // fail fast with the `Ipc` error if not running
+            let mut searcher = everything.searcher();
+            searcher.set_options(self);
+            let snapshot = searcher.query().await.snapshot();
+            Ok::<_, super::EverythingError>(snapshot)
+        })
+    }
+}
+
+/// Query in a new thread, which takes the [`global`] lock, queries, snapshots the results and
+/// releases the lock, so the caller is never blocked.
+///
+/// It is the simplest way to not block the UI thread, without the `async` feature or a
+/// dedicated worker. The queries from multiple threads are still serialized by the lock.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::*;
+/// let handle = spawn_query(SearchOptions::new("*.toml"));
+/// // ... do something else ...
+/// let results = handle.join().unwrap().unwrap();
+/// println!("{} results", results.len());
+/// ```
+pub fn spawn_query(options: SearchOptions) -> JoinHandle<Result<OwnedResults>> {
+    thread::Builder::new()
+        .name("everything-sdk-query".to_string())
+        .spawn(move || options.query_snapshot())
+        .expect("failed to spawn the query thread")
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Set all the search state from the `options`.
+    pub fn set_options(&mut self, options: &SearchOptions) -> &'_ mut EverythingSearcher<'a> {
+        self.set_search(&options.search)
+            .set_match_path(options.match_path)
+            .set_match_case(options.match_case)
+            .set_match_whole_word(options.match_whole_word)
+            .set_regex(options.regex)
+            .set_max(options.max)
+            .set_offset(options.offset)
+            .set_sort(options.sort)
+            .set_request_flags(options.request_flags)
+    }
+
+    /// Read all the current search state. (the search text is converted lossily)
+    pub fn get_options(&self) -> SearchOptions {
+        SearchOptions::current()
+    }
+}
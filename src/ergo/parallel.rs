@@ -0,0 +1,58 @@
+//! Process the owned results in parallel by [rayon](https://docs.rs/rayon), for the CPU-heavy
+//! work on each item, like hashing or reading the contents.
+//!
+//! Only the [`OwnedResults`] can be parallel. The live
+//! [`EverythingResults`](super::EverythingResults) reads each item from the global state of
+//! the Everything-SDK (a C library not thread-safe) behind the global lock, so it is neither
+//! `Send` nor `Sync`, and never will be. Take a snapshot first, like by
+//! [`EverythingResults::snapshot`](super::EverythingResults::snapshot) or
+//! [`spawn_query`](super::spawn_query), then the lock is free for the other queries while
+//! the items are processed.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::*;
+//! use rayon::prelude::*;
+//!
+//! let mut options = SearchOptions::new("ext:log");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME;
+//! let results = spawn_query(options).join().unwrap()?;
+//! let errors: usize = results
+//!     .par_iter()
+//!     .filter_map(|data| std::fs::read_to_string(data.full_path.as_ref()?).ok())
+//!     .map(|text| text.matches("ERROR").count())
+//!     .sum();
+//! println!("{errors} errors");
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use rayon::iter::IntoParallelIterator;
+
+use super::{ItemData, OwnedResults};
+
+impl<'a> IntoParallelIterator for &'a OwnedResults {
+    type Item = &'a ItemData;
+    type Iter = rayon::slice::Iter<'a, ItemData>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.items.as_slice().into_par_iter()
+    }
+}
+
+impl<'a> IntoParallelIterator for &'a mut OwnedResults {
+    type Item = &'a mut ItemData;
+    type Iter = rayon::slice::IterMut<'a, ItemData>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.items.as_mut_slice().into_par_iter()
+    }
+}
+
+impl IntoParallelIterator for OwnedResults {
+    type Item = ItemData;
+    type Iter = rayon::vec::IntoIter<ItemData>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.items.into_par_iter()
+    }
+}
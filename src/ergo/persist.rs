@@ -0,0 +1,378 @@
+//! Save the snapshots into a compact binary file and load them back, to cache a large query
+//! (millions of items) and reload it at the next startup without querying again.
+//!
+//! The format is private to this crate, and only the files of the same version can be loaded,
+//! so it is a cache rather than an exchange format (see [`efu`](super::efu) or
+//! [`export`](super::export) for those). The names and the paths are kept in UTF-16 exactly,
+//! and the numbers are in little-endian.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let results = match OwnedResults::load("cache.bin") {
+//!     Ok(results) => results,
+//!     Err(_) => {
+//!         let results = spawn_query(SearchOptions::new("ext:dll")).join().unwrap()?;
+//!         results.save("cache.bin")?;
+//!         results
+//!     }
+//! };
+//! println!("{} results", results.len());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error as ThisError;
+
+use super::{ItemData, ItemKind, OwnedResults, RequestFlags, SearchOptions, SortType};
+
+const MAGIC: &[u8; 8] = b"ESDKSNAP";
+/// Bump it on any change of the format.
+const VERSION: u32 = 1;
+
+#[derive(ThisError, Debug)]
+pub enum LoadError {
+    #[error("failed to read the snapshot.")]
+    Io(#[from] io::Error),
+    #[error("not a snapshot file.")]
+    NotSnapshot,
+    #[error("unsupported snapshot version {0}, expected {VERSION}.")]
+    UnsupportedVersion(u32),
+    #[error("invalid item kind {0}.")]
+    InvalidKind(u8),
+}
+
+impl OwnedResults {
+    /// Save into the file at `path`, which is replaced if exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Load the file saved by [`OwnedResults::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<OwnedResults, LoadError> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Write the binary encoding into `writer`, see [`OwnedResults::save`].
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut out = Encoder(writer);
+        out.0.write_all(MAGIC)?;
+        out.u32(VERSION)?;
+        out.u32(self.request_flags().bits())?;
+        out.u32(self.sort_type().into())?;
+        out.u32(self.total())?;
+        out.options(self.options())?;
+        out.u32(self.items.len() as u32)?;
+        for data in &self.items {
+            out.item(data)?;
+        }
+        out.0.flush()
+    }
+
+    /// Read the binary encoding from `reader`, see [`OwnedResults::load`].
+    pub fn read_from<R: Read>(reader: R) -> Result<OwnedResults, LoadError> {
+        let mut input = Decoder(reader);
+        let mut magic = [0; 8];
+        match input.0.read_exact(&mut magic) {
+            Ok(()) if &magic == MAGIC => {}
+            Ok(()) => return Err(LoadError::NotSnapshot),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(LoadError::NotSnapshot)
+            }
+            Err(err) => return Err(err.into()),
+        }
+        match input.u32()? {
+            VERSION => {}
+            version => return Err(LoadError::UnsupportedVersion(version)),
+        }
+        let request_flags = RequestFlags::from_bits_retain(input.u32()?);
+        let sort_type = SortType::from(input.u32()?);
+        let total = input.u32()?;
+        let options = input.options()?;
+        let len = input.u32()? as usize;
+        // Not trusting the length for the allocation, in case of a broken file.
+        let mut items = Vec::with_capacity(len.min(1 << 16));
+        for _ in 0..len {
+            items.push(input.item()?);
+        }
+        Ok(OwnedResults::from_parts(
+            items,
+            request_flags,
+            sort_type,
+            total,
+            options,
+        ))
+    }
+}
+
+struct Encoder<W>(W);
+
+impl<W: Write> Encoder<W> {
+    fn u8(&mut self, n: u8) -> io::Result<()> {
+        self.0.write_all(&[n])
+    }
+
+    fn u32(&mut self, n: u32) -> io::Result<()> {
+        self.0.write_all(&n.to_le_bytes())
+    }
+
+    fn u64(&mut self, n: u64) -> io::Result<()> {
+        self.0.write_all(&n.to_le_bytes())
+    }
+
+    /// The number of the UTF-16 units, and then the units.
+    fn os_str(&mut self, s: &OsStr) -> io::Result<()> {
+        let wide: Vec<u8> = s.encode_wide().flat_map(u16::to_le_bytes).collect();
+        self.u32((wide.len() / 2) as u32)?;
+        self.0.write_all(&wide)
+    }
+
+    fn options(&mut self, options: &SearchOptions) -> io::Result<()> {
+        self.os_str(OsStr::new(&options.search))?;
+        let switches = [
+            options.match_path,
+            options.match_case,
+            options.match_whole_word,
+            options.regex,
+        ];
+        let bits = switches
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (i, &on)| bits | u8::from(on) << i);
+        self.u8(bits)?;
+        self.u32(options.max)?;
+        self.u32(options.offset)?;
+        self.u32(options.sort.into())?;
+        self.u32(options.request_flags.bits())
+    }
+
+    /// The bit mask of the fields available, and then each of them in order.
+    fn item(&mut self, data: &ItemData) -> io::Result<()> {
+        self.u32(data.index)?;
+        self.u8(match data.kind {
+            ItemKind::File => 0,
+            ItemKind::Folder => 1,
+            ItemKind::Volume => 2,
+        })?;
+        self.u32(data.request_flags.bits())?;
+        let strings = [
+            data.filename.as_deref(),
+            data.path.as_deref().map(Path::as_os_str),
+            data.full_path.as_deref().map(Path::as_os_str),
+            data.extension.as_deref(),
+            data.file_list_filename.as_deref(),
+            data.highlighted_filename.as_deref(),
+            data.highlighted_path.as_deref(),
+            data.highlighted_full_path_and_filename.as_deref(),
+        ];
+        let numbers = [
+            data.size,
+            data.date_created,
+            data.date_modified,
+            data.date_accessed,
+            data.attributes.map(u64::from),
+            data.run_count.map(u64::from),
+            data.date_run,
+            data.date_recently_changed,
+        ];
+        let mask = strings
+            .iter()
+            .map(Option::is_some)
+            .chain(numbers.iter().map(Option::is_some))
+            .enumerate()
+            .fold(0u32, |mask, (i, some)| mask | u32::from(some) << i);
+        self.u32(mask)?;
+        for s in strings.into_iter().flatten() {
+            self.os_str(s)?;
+        }
+        for n in numbers.into_iter().flatten() {
+            self.u64(n)?;
+        }
+        Ok(())
+    }
+}
+
+struct Decoder<R>(R);
+
+impl<R: Read> Decoder<R> {
+    fn u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0; 1];
+        self.0.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let mut buf = [0; 4];
+        self.0.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        self.0.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn os_string(&mut self) -> io::Result<OsString> {
+        let len = self.u32()? as u64 * 2;
+        // Read up to the end rather than allocating by the length, in case of a broken file.
+        let mut bytes = Vec::new();
+        (&mut self.0).take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let wide: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        Ok(OsString::from_wide(&wide))
+    }
+
+    fn os_string_if(&mut self, present: bool) -> io::Result<Option<OsString>> {
+        present.then(|| self.os_string()).transpose()
+    }
+
+    fn u64_if(&mut self, present: bool) -> io::Result<Option<u64>> {
+        present.then(|| self.u64()).transpose()
+    }
+
+    fn options(&mut self) -> io::Result<SearchOptions> {
+        let search = self.os_string()?.to_string_lossy().into_owned();
+        let bits = self.u8()?;
+        Ok(SearchOptions {
+            search,
+            match_path: bits & 1 != 0,
+            match_case: bits & 1 << 1 != 0,
+            match_whole_word: bits & 1 << 2 != 0,
+            regex: bits & 1 << 3 != 0,
+            max: self.u32()?,
+            offset: self.u32()?,
+            sort: SortType::from(self.u32()?),
+            request_flags: RequestFlags::from_bits_retain(self.u32()?),
+        })
+    }
+
+    fn item(&mut self) -> Result<ItemData, LoadError> {
+        let index = self.u32()?;
+        let kind = match self.u8()? {
+            0 => ItemKind::File,
+            1 => ItemKind::Folder,
+            2 => ItemKind::Volume,
+            kind => return Err(LoadError::InvalidKind(kind)),
+        };
+        let request_flags = RequestFlags::from_bits_retain(self.u32()?);
+        let mask = self.u32()?;
+        // The fields in the same order as written, by the bits of the mask.
+        let mut bits = (0..).map(|i| mask & 1 << i != 0);
+        let mut has = || bits.next().unwrap_or(false);
+        Ok(ItemData {
+            index,
+            kind,
+            request_flags,
+            filename: self.os_string_if(has())?,
+            path: self.os_string_if(has())?.map(PathBuf::from),
+            full_path: self.os_string_if(has())?.map(PathBuf::from),
+            extension: self.os_string_if(has())?,
+            file_list_filename: self.os_string_if(has())?,
+            highlighted_filename: self.os_string_if(has())?,
+            highlighted_path: self.os_string_if(has())?,
+            highlighted_full_path_and_filename: self.os_string_if(has())?,
+            size: self.u64_if(has())?,
+            date_created: self.u64_if(has())?,
+            date_modified: self.u64_if(has())?,
+            date_accessed: self.u64_if(has())?,
+            attributes: self.u64_if(has())?.map(|n| n as u32),
+            run_count: self.u64_if(has())?.map(|n| n as u32),
+            date_run: self.u64_if(has())?,
+            date_recently_changed: self.u64_if(has())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> OwnedResults {
+        let mut options = SearchOptions::new("ext:dll");
+        options.match_case = true;
+        options.max = 100;
+        options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+            | RequestFlags::EVERYTHING_REQUEST_SIZE;
+        let items = vec![
+            ItemData {
+                index: 0,
+                kind: ItemKind::Folder,
+                request_flags: options.request_flags,
+                full_path: Some(PathBuf::from("C:\\Windows")),
+                ..Default::default()
+            },
+            ItemData {
+                index: 1,
+                kind: ItemKind::File,
+                request_flags: options.request_flags,
+                // An unpaired surrogate is kept as it is.
+                full_path: Some(PathBuf::from(OsString::from_wide(&[
+                    u16::from(b'C'),
+                    u16::from(b':'),
+                    u16::from(b'\\'),
+                    0xD800,
+                ]))),
+                size: Some(42),
+                ..Default::default()
+            },
+        ];
+        let request_flags = options.request_flags;
+        OwnedResults::from_parts(
+            items,
+            request_flags,
+            SortType::EVERYTHING_SORT_SIZE_DESCENDING,
+            7,
+            options,
+        )
+    }
+
+    fn encode(results: &OwnedResults) -> Vec<u8> {
+        let mut buf = Vec::new();
+        results.write_to(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn save_and_load() {
+        let results = results();
+        let path = std::env::temp_dir().join("everything-sdk-persist.bin");
+        results.save(&path).unwrap();
+        let loaded = OwnedResults::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), results);
+    }
+
+    #[test]
+    fn truncated() {
+        let buf = encode(&results());
+        let err = OwnedResults::read_from(&buf[..buf.len() - 1]).unwrap_err();
+        assert!(matches!(err, LoadError::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof));
+        let err = OwnedResults::read_from(&buf[..4]).unwrap_err();
+        assert!(matches!(err, LoadError::NotSnapshot));
+    }
+
+    #[test]
+    fn wrong_version() {
+        let mut buf = encode(&results());
+        buf[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        let err = OwnedResults::read_from(buf.as_slice()).unwrap_err();
+        assert!(matches!(err, LoadError::UnsupportedVersion(v) if v == VERSION + 1));
+    }
+
+    #[test]
+    fn not_snapshot() {
+        let err = OwnedResults::read_from(&b"Filename,Size"[..]).unwrap_err();
+        assert!(matches!(err, LoadError::NotSnapshot));
+    }
+}
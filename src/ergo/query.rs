@@ -0,0 +1,475 @@
+//! Helpers to compose the search text in [Everything search syntax](https://www.voidtools.com/support/everything/searching/).
+//!
+//! Concatenating the search text by hand is error-prone, e.g. a path with spaces must be
+//! quoted, a folder must end with `\` to match the files in it, and the OR groups must be
+//! wrapped as a whole. The types here render the correct text by [`Display`].
+
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::time::Duration;
+
+mod parse;
+
+pub use parse::{is_modifier, parse, Ast, ParseError, ParseErrorKind, MODIFIERS};
+
+/// The path scope of a search, that is "only search in these folders".
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::query::Scope;
+/// let scope = Scope::folders(["C:\\Program Files", "D:/Games/"]);
+/// assert_eq!(scope.to_string(), r#"("C:\Program Files\" | "D:\Games\")"#);
+/// assert_eq!(scope.search("*.exe"), r#"("C:\Program Files\" | "D:\Games\") *.exe"#);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Scope {
+    /// The normalized folders, each one ends with only one `\`.
+    folders: Vec<String>,
+}
+
+impl Scope {
+    /// The scope of only one folder.
+    pub fn folder(folder: impl AsRef<Path>) -> Self {
+        Self::folders([folder])
+    }
+
+    /// The scope of multiple folders, matching the items in any of them.
+    ///
+    /// The `/` is replaced by `\`, and the trailing slashes are normalized to one `\`.
+    /// The empty folders are ignored.
+    pub fn folders<P: AsRef<Path>>(folders: impl IntoIterator<Item = P>) -> Self {
+        let folders = folders
+            .into_iter()
+            .filter_map(|folder| normalize_folder(folder.as_ref()))
+            .collect();
+        Scope { folders }
+    }
+
+    /// Add one more folder into the scope.
+    pub fn or_folder(mut self, folder: impl AsRef<Path>) -> Self {
+        self.folders.extend(normalize_folder(folder.as_ref()));
+        self
+    }
+
+    /// No folder in the scope, it renders an empty string which means no limit.
+    pub fn is_empty(&self) -> bool {
+        self.folders.is_empty()
+    }
+
+    /// Render the search text of `text` in this scope.
+    pub fn search(&self, text: &str) -> String {
+        match (self.is_empty(), text.trim().is_empty()) {
+            (true, _) => text.to_string(),
+            (false, true) => self.to_string(),
+            (false, false) => format!("{self} {text}"),
+        }
+    }
+}
+
+/// The characters which are the operators or the separators in Everything search syntax.
+const SPECIAL_CHARS: &[char] = &[' ', '\t', ':', '|', '<', '>', '!', '"', '(', ')'];
+
+/// Escape the `text` to be searched verbatim, such as a raw path or any user input.
+///
+/// The text with any of `: | < > ! " ( )` or spaces is quoted, and the `"` in it is matched
+/// by the `quot:` macro, since there is no escape character in Everything. The wildcards `*`
+/// and `?` are kept, as they are not allowed in the file names anyway.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::query::escape;
+/// assert_eq!(escape("notes.txt"), "notes.txt");
+/// assert_eq!(escape(r"C:\Program Files\a|b"), r#""C:\Program Files\a|b""#);
+/// assert_eq!(escape(r#"say "hi""#), r#""say "quot:"hi"quot:"#);
+/// ```
+pub fn escape(text: &str) -> String {
+    if !text.contains(SPECIAL_CHARS) {
+        return text.to_string();
+    }
+    text.split('"')
+        .map(|part| match part {
+            "" => String::new(),
+            part => format!("\"{part}\""),
+        })
+        .collect::<Vec<_>>()
+        .join("quot:")
+}
+
+/// Normalize the folder to `X:\path\to\folder\`, return `None` if it is empty.
+///
+/// It is lossy for the paths which are not valid unicode, and the `"` (not allowed in file
+/// names on Windows) is removed, since it can not be escaped in the quoted text.
+fn normalize_folder(folder: &Path) -> Option<String> {
+    let folder = folder.to_string_lossy().replace('/', "\\").replace('"', "");
+    let folder = folder.trim_end_matches('\\');
+    if folder.is_empty() {
+        None
+    } else {
+        Some(format!("{folder}\\"))
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.folders.as_slice() {
+            [] => Ok(()),
+            [folder] => write!(f, "\"{folder}\""),
+            [first, rest @ ..] => {
+                write!(f, "(\"{first}\"")?;
+                for folder in rest {
+                    write!(f, " | \"{folder}\"")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// The search expression tree, rendered in Everything search syntax by [`Display`].
+///
+/// The grouping is added only when it is needed, note that `|` (OR) has a higher precedence
+/// than space (AND) in Everything, so `a b | c` means `a (b | c)`.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::query::Expr;
+/// let expr = Expr::term("*.rs")
+///     .and(Expr::exclude_path("C:\\Users\\me\\My Projects\\target"))
+///     .and(!Expr::any([Expr::term("test"), Expr::term("bench")]));
+/// assert_eq!(
+///     expr.to_string(),
+///     r#"*.rs !"C:\Users\me\My Projects\target\" !(test | bench)"#
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Expr {
+    /// The search term rendered as it is, such as `*.rs` or `size:>1mb`.
+    Term(String),
+    /// The text rendered in quotes, so spaces and operators in it are literal.
+    Phrase(String),
+    /// A folder (normalized, ends with `\`) rendered in quotes, matching the items in it.
+    Path(String),
+    /// All of them should match. (space)
+    And(Vec<Expr>),
+    /// Any of them should match. (`|`)
+    Or(Vec<Expr>),
+    /// It should not match. (`!`)
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    pub fn term(text: impl Into<String>) -> Self {
+        Expr::Term(text.into())
+    }
+
+    /// The quoted text. (the `"` in it is removed, as it can not be escaped in quotes)
+    pub fn phrase(text: impl AsRef<str>) -> Self {
+        Expr::Phrase(text.as_ref().replace('"', ""))
+    }
+
+    /// Match the items in the folder.
+    pub fn path(folder: impl AsRef<Path>) -> Self {
+        match normalize_folder(folder.as_ref()) {
+            Some(folder) => Expr::Path(folder),
+            None => Expr::And(Vec::new()),
+        }
+    }
+
+    /// The text searched verbatim, see [`escape`].
+    pub fn literal(text: impl AsRef<str>) -> Self {
+        Expr::Term(escape(text.as_ref()))
+    }
+
+    /// Negate the `expr`, rendered as `!term`, `!"phrase"` or `!(group)`.
+    pub fn not(expr: impl Into<Expr>) -> Self {
+        Expr::Not(Box::new(expr.into()))
+    }
+
+    /// Exclude the items in the folder, the same as `Expr::not(Expr::path(folder))`.
+    pub fn exclude_path(folder: impl AsRef<Path>) -> Self {
+        Expr::not(Expr::path(folder))
+    }
+
+    /// All of the expressions should match.
+    pub fn all(exprs: impl IntoIterator<Item = Expr>) -> Self {
+        exprs.into_iter().fold(Expr::And(Vec::new()), Expr::and)
+    }
+
+    /// Any of the expressions should match.
+    pub fn any(exprs: impl IntoIterator<Item = Expr>) -> Self {
+        exprs.into_iter().fold(Expr::Or(Vec::new()), Expr::or)
+    }
+
+    pub fn and(self, other: impl Into<Expr>) -> Self {
+        let mut exprs = match self {
+            Expr::And(exprs) => exprs,
+            expr if expr.is_empty() => Vec::new(),
+            expr => vec![expr],
+        };
+        match other.into() {
+            Expr::And(others) => exprs.extend(others),
+            other if other.is_empty() => {}
+            other => exprs.push(other),
+        }
+        Expr::And(exprs)
+    }
+
+    pub fn or(self, other: impl Into<Expr>) -> Self {
+        let mut exprs = match self {
+            Expr::Or(exprs) => exprs,
+            expr if expr.is_empty() => Vec::new(),
+            expr => vec![expr],
+        };
+        match other.into() {
+            Expr::Or(others) => exprs.extend(others),
+            other if other.is_empty() => {}
+            other => exprs.push(other),
+        }
+        Expr::Or(exprs)
+    }
+
+    /// Skip the hidden or system items, like the Explorer does by default.
+    ///
+    /// It appends `!attrib:H !attrib:S`, see also [`EverythingResults::skip_hidden_system`]
+    /// to do it on the results.
+    ///
+    /// [`EverythingResults::skip_hidden_system`]: crate::EverythingResults::skip_hidden_system
+    pub fn skip_hidden_system(self) -> Self {
+        self.and(Expr::not(Expr::term("attrib:H")))
+            .and(Expr::not(Expr::term("attrib:S")))
+    }
+
+    /// It renders nothing, which matches everything.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Expr::Term(text) => text.trim().is_empty(),
+            Expr::Phrase(_) | Expr::Path(_) => false,
+            Expr::And(exprs) | Expr::Or(exprs) => exprs.iter().all(Expr::is_empty),
+            Expr::Not(expr) => expr.is_empty(),
+        }
+    }
+
+    /// Whether it is a single unit, which needs no grouping anywhere.
+    fn is_atom(&self) -> bool {
+        match self {
+            Expr::Term(_) | Expr::Phrase(_) | Expr::Path(_) | Expr::Not(_) => true,
+            Expr::And(exprs) | Expr::Or(exprs) => {
+                exprs.iter().filter(|expr| !expr.is_empty()).count() <= 1
+                    && exprs.iter().all(Expr::is_atom)
+            }
+        }
+    }
+
+    fn fmt_grouped(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_atom() {
+            write!(f, "{self}")
+        } else {
+            write!(f, "({self})")
+        }
+    }
+
+    fn fmt_joined(exprs: &[Expr], sep: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut exprs = exprs.iter().filter(|expr| !expr.is_empty());
+        if let Some(first) = exprs.next() {
+            first.fmt_grouped(f)?;
+            for expr in exprs {
+                f.write_str(sep)?;
+                expr.fmt_grouped(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Term(text) => f.write_str(text.trim()),
+            Expr::Phrase(text) => write!(f, "\"{text}\""),
+            Expr::Path(folder) => write!(f, "\"{folder}\""),
+            Expr::And(exprs) => Expr::fmt_joined(exprs, " ", f),
+            Expr::Or(exprs) => Expr::fmt_joined(exprs, " | ", f),
+            Expr::Not(expr) if expr.is_empty() => Ok(()),
+            Expr::Not(expr) => {
+                f.write_str("!")?;
+                expr.fmt_grouped(f)
+            }
+        }
+    }
+}
+
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Self::Output {
+        Expr::not(self)
+    }
+}
+
+impl From<Scope> for Expr {
+    fn from(scope: Scope) -> Self {
+        Expr::Or(scope.folders.into_iter().map(Expr::Path).collect())
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(text: &str) -> Self {
+        Expr::term(text)
+    }
+}
+
+impl From<String> for Expr {
+    fn from(text: String) -> Self {
+        Expr::Term(text)
+    }
+}
+
+/// Compose the search of the common filters step by step, without knowing the modifiers.
+///
+/// Each method adds one more filter which should match too (AND), and the values are
+/// rendered with the right units and quotes.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use everything_sdk::query::Builder;
+///
+/// let search = Builder::new()
+///     .ext(["jpg", ".png"])
+///     .size_gt(1_000_000)
+///     .modified_within(Duration::from_secs(3600))
+///     .path("D:\\My Photos")
+///     .not("thumbnail")
+///     .build();
+/// assert_eq!(
+///     search.to_string(),
+///     r#"ext:jpg;png size:>1000000 dm:last3600secs "D:\My Photos\" !thumbnail"#
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Builder {
+    expr: Expr,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// No filter, which matches everything.
+    pub fn new() -> Self {
+        Builder {
+            expr: Expr::And(Vec::new()),
+        }
+    }
+
+    /// Add any expression, such as an [`Expr::any`] group.
+    pub fn and(mut self, expr: impl Into<Expr>) -> Self {
+        self.expr = self.expr.and(expr);
+        self
+    }
+
+    /// The search term as it is, such as `*.rs`.
+    pub fn term(self, text: impl Into<String>) -> Self {
+        self.and(Expr::term(text))
+    }
+
+    /// The text in quotes, so the spaces and the operators in it are literal.
+    pub fn phrase(self, text: impl AsRef<str>) -> Self {
+        self.and(Expr::phrase(text))
+    }
+
+    /// It should not match the `expr`.
+    pub fn not(self, expr: impl Into<Expr>) -> Self {
+        self.and(Expr::not(expr))
+    }
+
+    /// Any of the extensions, with or without the leading dot. No filter if no extension.
+    pub fn ext<S: AsRef<str>>(self, exts: impl IntoIterator<Item = S>) -> Self {
+        let exts: Vec<String> = exts
+            .into_iter()
+            .map(|ext| {
+                let ext = ext.as_ref().trim().trim_start_matches('.');
+                ext.replace([';', '"', ' ', '|'], "")
+            })
+            .filter(|ext| !ext.is_empty())
+            .collect();
+        if exts.is_empty() {
+            self
+        } else {
+            self.term(format!("ext:{}", exts.join(";")))
+        }
+    }
+
+    /// The size is greater than `bytes`. The folders have no size in Everything 1.4, so they
+    /// never match.
+    pub fn size_gt(self, bytes: u64) -> Self {
+        self.term(format!("size:>{bytes}"))
+    }
+
+    pub fn size_ge(self, bytes: u64) -> Self {
+        self.term(format!("size:>={bytes}"))
+    }
+
+    pub fn size_lt(self, bytes: u64) -> Self {
+        self.term(format!("size:<{bytes}"))
+    }
+
+    pub fn size_le(self, bytes: u64) -> Self {
+        self.term(format!("size:<={bytes}"))
+    }
+
+    /// Modified within the `duration` until now, in seconds (at least 1).
+    pub fn modified_within(self, duration: Duration) -> Self {
+        self.term(format!("dm:last{}secs", duration.as_secs().max(1)))
+    }
+
+    /// Created within the `duration` until now, in seconds (at least 1).
+    pub fn created_within(self, duration: Duration) -> Self {
+        self.term(format!("dc:last{}secs", duration.as_secs().max(1)))
+    }
+
+    /// The items in the folder, recursively.
+    pub fn path(self, folder: impl AsRef<Path>) -> Self {
+        self.and(Expr::path(folder))
+    }
+
+    /// The items directly in the folder, not in its subfolders. No filter if it is empty.
+    pub fn parent(self, folder: impl AsRef<Path>) -> Self {
+        match normalize_folder(folder.as_ref()) {
+            // The root keeps the `\`, like `C:\`, and the others do not.
+            Some(folder) if folder.ends_with(":\\") => self.term(format!("parent:\"{folder}\"")),
+            Some(folder) => self.term(format!("parent:\"{}\"", folder.trim_end_matches('\\'))),
+            None => self,
+        }
+    }
+
+    /// Only the files.
+    pub fn files_only(self) -> Self {
+        self.term("file:")
+    }
+
+    /// Only the folders.
+    pub fn folders_only(self) -> Self {
+        self.term("folder:")
+    }
+
+    /// The expression, which renders the search text by [`Display`].
+    pub fn build(self) -> Expr {
+        self.expr
+    }
+}
+
+impl From<Builder> for Expr {
+    fn from(builder: Builder) -> Self {
+        builder.build()
+    }
+}
+
+impl Display for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expr.fmt(f)
+    }
+}
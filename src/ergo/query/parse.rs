@@ -0,0 +1,852 @@
+//! The parser of Everything search syntax.
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+use thiserror::Error as ThisError;
+
+use super::Scope;
+
+/// The syntax tree of a search text, see [`parse`], and it is printed back by [`Display`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Ast {
+    /// Nothing to search, which matches everything.
+    Empty,
+    /// The plain term like `foo`, `*.rs` or `"foo bar"`, where `text` is without the quotes.
+    Term { text: String, quoted: bool },
+    /// The search function like `ext:rs`, `size:>1mb` or `parent:"C:\Program Files"`.
+    Function {
+        name: String,
+        /// The value without the quotes, including the comparison operator, like `>1mb`.
+        value: String,
+        quoted: bool,
+    },
+    /// The search modifier like `case:` and `path:`, applied to the `expr` after it. (it is
+    /// [`Ast::Empty`] for the standalone one like `file:`)
+    Modifier { name: String, expr: Box<Ast> },
+    /// `!expr`
+    Not(Box<Ast>),
+    /// `a b c`
+    And(Vec<Ast>),
+    /// `a | b | c`, which has the higher precedence than [`Ast::And`].
+    Or(Vec<Ast>),
+    /// `<expr>` or `(expr)`
+    Group(Box<Ast>),
+}
+
+impl Ast {
+    /// Limit the search in the `scope`, which is put before the search text.
+    pub fn with_scope(self, scope: &Scope) -> Ast {
+        let mut folders: Vec<_> = scope
+            .folders
+            .iter()
+            .map(|folder| Ast::Term {
+                text: folder.clone(),
+                quoted: true,
+            })
+            .collect();
+        let scope = match folders.len() {
+            0 => Ast::Empty,
+            1 => folders.pop().unwrap(),
+            _ => Ast::Group(Box::new(Ast::Or(folders))),
+        };
+        scope.and(self)
+    }
+
+    /// Remove the modifier `name` (case-insensitive) everywhere, keeping the expressions it
+    /// applied to, e.g. strip `case` from `case:foo !case:bar` is `foo !bar`.
+    pub fn strip_modifier(self, name: &str) -> Ast {
+        match self {
+            Ast::Modifier { name: m, expr } if m.eq_ignore_ascii_case(name) => {
+                expr.strip_modifier(name)
+            }
+            Ast::Modifier { name: m, expr } => Ast::Modifier {
+                name: m,
+                expr: Box::new(expr.strip_modifier(name)),
+            },
+            Ast::Not(expr) => Ast::Not(Box::new(expr.strip_modifier(name))),
+            Ast::Group(expr) => Ast::Group(Box::new(expr.strip_modifier(name))),
+            Ast::And(items) => {
+                Ast::And(items.into_iter().map(|a| a.strip_modifier(name)).collect())
+            }
+            Ast::Or(items) => Ast::Or(items.into_iter().map(|a| a.strip_modifier(name)).collect()),
+            ast => ast,
+        }
+    }
+
+    /// Both should match, e.g. "search within the current results" is `current.and(new)`.
+    pub fn and(self, other: Ast) -> Ast {
+        let mut items = match self {
+            Ast::Empty => return other,
+            Ast::And(items) => items,
+            ast => vec![ast],
+        };
+        match other {
+            Ast::Empty => {}
+            Ast::And(others) => items.extend(others),
+            other => items.push(other),
+        }
+        Ast::And(items)
+    }
+
+    /// Any of them should match, e.g. merge the saved filters.
+    pub fn or(self, other: Ast) -> Ast {
+        let mut items = match self {
+            // empty matches everything, so does the OR of it.
+            Ast::Empty => return Ast::Empty,
+            Ast::Or(items) => items,
+            ast => vec![ast],
+        };
+        match other {
+            Ast::Empty => return Ast::Empty,
+            Ast::Or(others) => items.extend(others),
+            other => items.push(other),
+        }
+        Ast::Or(items)
+    }
+
+    /// It should not match. (negating empty is still empty)
+    pub fn negate(self) -> Ast {
+        match self {
+            Ast::Empty => Ast::Empty,
+            ast => Ast::Not(Box::new(ast)),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Ast::Empty => true,
+            Ast::And(items) | Ast::Or(items) => items.iter().all(Ast::is_empty),
+            Ast::Group(expr) => expr.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Print as an operand of `!`, `|` or a modifier, grouping the AND and OR.
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, group_or: bool) -> fmt::Result {
+        match self {
+            Ast::And(items) if items.iter().filter(|a| !a.is_empty()).count() > 1 => {
+                write!(f, "<{self}>")
+            }
+            Ast::Or(items) if group_or && items.iter().filter(|a| !a.is_empty()).count() > 1 => {
+                write!(f, "<{self}>")
+            }
+            ast => write!(f, "{ast}"),
+        }
+    }
+}
+
+/// The text needs the quotes to be parsed back as one term.
+fn needs_quotes(text: &str, is_value: bool) -> bool {
+    let starts_with_operator = !is_value && text.starts_with(['!', '<', '(']);
+    let looks_like_prefix = !is_value && {
+        let len = text
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(text.len());
+        len >= 2 && text[len..].starts_with(':')
+    };
+    let body = if is_value {
+        // the leading comparison operator of a function value, like `>` in `size:>1mb`.
+        text.chars().next().map_or("", |c| &text[c.len_utf8()..])
+    } else {
+        text
+    };
+    (text.is_empty() && !is_value)
+        || starts_with_operator
+        || looks_like_prefix
+        || body.contains(|c: char| c.is_whitespace() || matches!(c, '|' | '>' | ')'))
+}
+
+impl Display for Ast {
+    /// Print in Everything search syntax, it is parsed back to the same (or equivalent) AST.
+    ///
+    /// The groups are printed as `<>`, which is supported by Everything 1.4 and later.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ast::Empty => Ok(()),
+            Ast::Term { text, quoted } => {
+                if *quoted || needs_quotes(text, false) {
+                    write!(f, "\"{text}\"")
+                } else {
+                    f.write_str(text)
+                }
+            }
+            Ast::Function {
+                name,
+                value,
+                quoted,
+            } => {
+                if *quoted || needs_quotes(value, true) {
+                    write!(f, "{name}:\"{value}\"")
+                } else {
+                    write!(f, "{name}:{value}")
+                }
+            }
+            Ast::Modifier { name, expr } => {
+                write!(f, "{name}:")?;
+                expr.fmt_operand(f, true)
+            }
+            Ast::Not(expr) if expr.is_empty() => Ok(()),
+            Ast::Not(expr) => {
+                f.write_str("!")?;
+                expr.fmt_operand(f, true)
+            }
+            Ast::And(items) | Ast::Or(items) => {
+                let is_and = matches!(self, Ast::And(_));
+                let mut items = items.iter().filter(|a| !a.is_empty());
+                if let Some(first) = items.next() {
+                    first.fmt_operand(f, false)?;
+                    for item in items {
+                        f.write_str(if is_and { " " } else { " | " })?;
+                        item.fmt_operand(f, false)?;
+                    }
+                }
+                Ok(())
+            }
+            Ast::Group(expr) => write!(f, "<{expr}>"),
+        }
+    }
+}
+
+/// The syntax error with the byte range of the input, for highlighting.
+#[derive(ThisError, Clone, Debug, Eq, PartialEq)]
+#[error("{kind} at {}..{}", span.start, span.end)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+}
+
+#[non_exhaustive]
+#[derive(ThisError, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    #[error("the quote is not closed")]
+    UnclosedQuote,
+    #[error("the group is not closed")]
+    UnclosedGroup,
+    #[error("the operator has no operand")]
+    MissingOperand,
+}
+
+/// The search modifiers in Everything 1.4 (and a few in 1.5), which are applied to the terms
+/// after them, the other `name:` prefixes are treated as the search functions.
+pub const MODIFIERS: &[&str] = &[
+    "ascii",
+    "case",
+    "diacritics",
+    "endwith",
+    "file",
+    "files",
+    "folder",
+    "folders",
+    "noascii",
+    "nocase",
+    "nodiacritics",
+    "nofileonly",
+    "nofolderonly",
+    "nopath",
+    "noregex",
+    "nowfn",
+    "nowholefilename",
+    "nowholeword",
+    "nowildcards",
+    "noww",
+    "path",
+    "prefix",
+    "regex",
+    "startwith",
+    "suffix",
+    "utf8",
+    "wfn",
+    "wholefilename",
+    "wholeword",
+    "wildcards",
+    "ww",
+];
+
+/// Whether the `name` (case-insensitive) is a search modifier rather than a function.
+pub fn is_modifier(name: &str) -> bool {
+    MODIFIERS.iter().any(|m| m.eq_ignore_ascii_case(name))
+}
+
+/// Parse the search text in Everything search syntax.
+///
+/// The space is AND, `|` is OR (binds tighter than AND), `!` is NOT, `<>` or `()` is the group,
+/// and `"` quotes the spaces and operators. The `name:` prefix is a modifier (see
+/// [`MODIFIERS`]) or a function, and the single letter like `C:` is just a term.
+///
+/// # Examples
+/// ```no_run
+/// use everything_sdk::query::{parse, Ast};
+/// let ast = parse(r#"case:readme | license !ext:md"#).unwrap();
+/// assert!(matches!(ast, Ast::And(_)));
+/// assert!(parse(r#"foo "bar"#).is_err());
+/// ```
+pub fn parse(text: &str) -> Result<Ast, ParseError> {
+    let tokens = Lexer::new(text).lex()?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        len: text.len(),
+    };
+    parser.parse_and(None)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Tok {
+    Space,
+    Or,
+    Not,
+    Open,
+    Close,
+    Modifier(String),
+    Function(String),
+    Word { text: String, quoted: bool },
+}
+
+struct Lexer<'a> {
+    text: &'a str,
+    pos: usize,
+    /// The closer (`>` or `)`) of each open group.
+    closers: Vec<char>,
+    tokens: Vec<(Tok, Range<usize>)>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Lexer {
+            text,
+            pos: 0,
+            closers: Vec::new(),
+            tokens: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn push(&mut self, tok: Tok, start: usize) {
+        self.tokens.push((tok, start..self.pos));
+    }
+
+    fn is_closer(&self, c: char) -> bool {
+        self.closers.last() == Some(&c)
+    }
+
+    fn lex(mut self) -> Result<Vec<(Tok, Range<usize>)>, ParseError> {
+        // whether it is at the start of a primary, where `!`, `<` and `name:` are recognized.
+        let mut at_start = true;
+        while let Some(c) = self.peek() {
+            let start = self.pos;
+            if c.is_whitespace() {
+                while self.peek().is_some_and(char::is_whitespace) {
+                    self.pos += self.peek().unwrap().len_utf8();
+                }
+                self.push(Tok::Space, start);
+                at_start = true;
+            } else if c == '|' {
+                self.pos += 1;
+                self.push(Tok::Or, start);
+                at_start = true;
+            } else if self.is_closer(c) {
+                self.pos += 1;
+                self.closers.pop();
+                self.push(Tok::Close, start);
+                at_start = false;
+            } else if at_start && c == '!' {
+                self.pos += 1;
+                self.push(Tok::Not, start);
+            } else if at_start && (c == '<' || c == '(') {
+                self.pos += 1;
+                self.closers.push(if c == '<' { '>' } else { ')' });
+                self.push(Tok::Open, start);
+            } else if let Some(name) = self.prefix().filter(|_| at_start) {
+                self.pos += name.len() + 1;
+                if is_modifier(name) {
+                    self.push(Tok::Modifier(name.to_string()), start);
+                } else {
+                    self.push(Tok::Function(name.to_string()), start);
+                    let start = self.pos;
+                    let (text, quoted) = self.word(true)?;
+                    self.push(Tok::Word { text, quoted }, start);
+                    at_start = false;
+                }
+            } else {
+                let (text, quoted) = self.word(false)?;
+                self.push(Tok::Word { text, quoted }, start);
+                at_start = false;
+            }
+        }
+        Ok(self.tokens)
+    }
+
+    /// The `name` of the `name:` prefix at current position, at least two letters.
+    fn prefix(&self) -> Option<&'a str> {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        (len >= 2 && rest[len..].starts_with(':')).then(|| &rest[..len])
+    }
+
+    /// Read a word until the space, `|` or the closer of current group, with the quotes
+    /// removed. The `value` of function can start with the comparison operator like `>`.
+    fn word(&mut self, value: bool) -> Result<(String, bool), ParseError> {
+        let (mut text, mut quoted) = (String::new(), false);
+        let mut quote_start = None;
+        let value_start = self.pos;
+        while let Some(c) = self.peek() {
+            if quote_start.is_some() {
+                if c == '"' {
+                    quote_start = None;
+                } else {
+                    text.push(c);
+                }
+            } else if c == '"' {
+                quote_start = Some(self.pos);
+                quoted = true;
+            } else if c.is_whitespace()
+                || c == '|'
+                || (self.is_closer(c) && !(value && self.pos == value_start))
+            {
+                break;
+            } else {
+                text.push(c);
+            }
+            self.pos += c.len_utf8();
+        }
+        match quote_start {
+            Some(start) => Err(ParseError {
+                kind: ParseErrorKind::UnclosedQuote,
+                span: start..self.pos,
+            }),
+            None => Ok((text, quoted)),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Tok, Range<usize>)>,
+    pos: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|(tok, _)| tok)
+    }
+
+    fn span(&self) -> Range<usize> {
+        match self.tokens.get(self.pos) {
+            Some((_, span)) => span.clone(),
+            None => self.len..self.len,
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(&Tok::Space) {
+            self.pos += 1;
+        }
+    }
+
+    fn missing_operand(&self, span: Range<usize>) -> ParseError {
+        ParseError {
+            kind: ParseErrorKind::MissingOperand,
+            span,
+        }
+    }
+
+    /// Parse until the end, or the closer of the group opened at `open`.
+    fn parse_and(&mut self, open: Option<Range<usize>>) -> Result<Ast, ParseError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_spaces();
+            match (self.peek(), &open) {
+                (None, None) | (Some(Tok::Close), Some(_)) => break,
+                (None, Some(open)) => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnclosedGroup,
+                        span: open.clone(),
+                    })
+                }
+                _ => items.push(self.parse_or()?),
+            }
+        }
+        Ok(match items.len() {
+            0 => Ast::Empty,
+            1 => items.pop().unwrap(),
+            _ => Ast::And(items),
+        })
+    }
+
+    fn parse_or(&mut self) -> Result<Ast, ParseError> {
+        let mut items = vec![self.parse_unary()?];
+        loop {
+            let before_spaces = self.pos;
+            self.skip_spaces();
+            if self.peek() != Some(&Tok::Or) {
+                self.pos = before_spaces;
+                break;
+            }
+            let or = self.span();
+            self.pos += 1;
+            self.skip_spaces();
+            match self.peek() {
+                None | Some(Tok::Or) | Some(Tok::Close) => return Err(self.missing_operand(or)),
+                _ => items.push(self.parse_unary()?),
+            }
+        }
+        Ok(match items.len() {
+            1 => items.pop().unwrap(),
+            _ => Ast::Or(items),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Ast, ParseError> {
+        if self.peek() == Some(&Tok::Not) {
+            let not = self.span();
+            self.pos += 1;
+            return match self.peek() {
+                None | Some(Tok::Space | Tok::Or | Tok::Close) => Err(self.missing_operand(not)),
+                _ => Ok(Ast::Not(Box::new(self.parse_unary()?))),
+            };
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Ast, ParseError> {
+        let span = self.span();
+        let Some((tok, _)) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.missing_operand(span));
+        };
+        self.pos += 1;
+        match tok {
+            Tok::Open => {
+                let inner = self.parse_and(Some(span))?;
+                self.pos += 1; // the closer
+                Ok(Ast::Group(Box::new(inner)))
+            }
+            Tok::Modifier(name) => {
+                let expr = match self.peek() {
+                    None | Some(Tok::Space | Tok::Or | Tok::Close) => Ast::Empty,
+                    _ => self.parse_unary()?,
+                };
+                Ok(Ast::Modifier {
+                    name,
+                    expr: Box::new(expr),
+                })
+            }
+            Tok::Function(name) => match self.tokens.get(self.pos).cloned() {
+                Some((Tok::Word { text, quoted }, _)) => {
+                    self.pos += 1;
+                    Ok(Ast::Function {
+                        name,
+                        value: text,
+                        quoted,
+                    })
+                }
+                _ => unreachable!("the lexer always emits the value after a function"),
+            },
+            Tok::Word { text, quoted } => Ok(Ast::Term { text, quoted }),
+            Tok::Space | Tok::Or | Tok::Not | Tok::Close => {
+                self.pos -= 1;
+                Err(self.missing_operand(span))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str) -> Ast {
+        Ast::Term {
+            text: text.to_string(),
+            quoted: false,
+        }
+    }
+
+    fn quoted(text: &str) -> Ast {
+        Ast::Term {
+            text: text.to_string(),
+            quoted: true,
+        }
+    }
+
+    fn function(name: &str, value: &str, quoted: bool) -> Ast {
+        Ast::Function {
+            name: name.to_string(),
+            value: value.to_string(),
+            quoted,
+        }
+    }
+
+    fn modifier(name: &str, expr: Ast) -> Ast {
+        Ast::Modifier {
+            name: name.to_string(),
+            expr: Box::new(expr),
+        }
+    }
+
+    fn group(expr: Ast) -> Ast {
+        Ast::Group(Box::new(expr))
+    }
+
+    fn not(expr: Ast) -> Ast {
+        Ast::Not(Box::new(expr))
+    }
+
+    fn error(kind: ParseErrorKind, span: Range<usize>) -> Result<Ast, ParseError> {
+        Err(ParseError { kind, span })
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse(""), Ok(Ast::Empty));
+        assert_eq!(parse(" \t "), Ok(Ast::Empty));
+        assert_eq!(parse("<>"), Ok(group(Ast::Empty)));
+    }
+
+    #[test]
+    fn terms_and_spaces() {
+        assert_eq!(parse("foo"), Ok(term("foo")));
+        assert_eq!(parse("  *.rs  "), Ok(term("*.rs")));
+        assert_eq!(
+            parse("foo  bar\tbaz"),
+            Ok(Ast::And(vec![term("foo"), term("bar"), term("baz")]))
+        );
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(parse(r#""foo bar""#), Ok(quoted("foo bar")));
+        assert_eq!(parse(r#""""#), Ok(quoted("")));
+        // the quotes can be anywhere in the word, and only the spaces in them are kept
+        assert_eq!(parse(r#"foo"bar baz"qux"#), Ok(quoted("foobar bazqux")));
+        // the operators in the quotes are just text
+        assert_eq!(parse(r#""a | b""#), Ok(quoted("a | b")));
+        assert_eq!(parse(r#""!a""#), Ok(quoted("!a")));
+        assert_eq!(parse(r#""<a>""#), Ok(quoted("<a>")));
+        assert_eq!(parse(r#""case:a""#), Ok(quoted("case:a")));
+        assert_eq!(
+            parse(r#"parent:"C:\Program Files""#),
+            Ok(function("parent", r"C:\Program Files", true))
+        );
+    }
+
+    #[test]
+    fn no_escapes() {
+        // the backslash is the path separator, not an escape
+        assert_eq!(parse(r"C:\Windows\*.dll"), Ok(term(r"C:\Windows\*.dll")));
+        assert_eq!(parse(r#""C:\""#), Ok(quoted(r"C:\")));
+        assert_eq!(parse(r"a\|b"), Ok(Ast::Or(vec![term(r"a\"), term("b")])));
+    }
+
+    #[test]
+    fn functions_and_modifiers() {
+        assert_eq!(parse("ext:rs"), Ok(function("ext", "rs", false)));
+        assert_eq!(parse("size:>1mb"), Ok(function("size", ">1mb", false)));
+        assert_eq!(parse("dm:"), Ok(function("dm", "", false)));
+        assert_eq!(parse("case:foo"), Ok(modifier("case", term("foo"))));
+        assert_eq!(parse("CASE:foo"), Ok(modifier("CASE", term("foo"))));
+        assert_eq!(
+            parse("file: foo"),
+            Ok(Ast::And(vec![modifier("file", Ast::Empty), term("foo")]))
+        );
+        assert_eq!(
+            parse("case:path:foo"),
+            Ok(modifier("case", modifier("path", term("foo"))))
+        );
+        // the single letter is the drive, not a function
+        assert_eq!(parse("C:"), Ok(term("C:")));
+        // only at the start of a term
+        assert_eq!(parse("foo:bar:baz"), Ok(function("foo", "bar:baz", false)));
+        assert_eq!(parse("a!case:b"), Ok(term("a!case:b")));
+    }
+
+    #[test]
+    fn groups() {
+        assert_eq!(
+            parse("<a b>"),
+            Ok(group(Ast::And(vec![term("a"), term("b")])))
+        );
+        assert_eq!(
+            parse("(a | b) c"),
+            Ok(Ast::And(vec![
+                group(Ast::Or(vec![term("a"), term("b")])),
+                term("c")
+            ]))
+        );
+        assert_eq!(
+            parse("<a <b | c>>"),
+            Ok(group(Ast::And(vec![
+                term("a"),
+                group(Ast::Or(vec![term("b"), term("c")]))
+            ])))
+        );
+        // the closer of the other kind is just text
+        assert_eq!(parse("<a)>"), Ok(group(term("a)"))));
+        assert_eq!(parse("(a>)"), Ok(group(term("a>"))));
+        // outside of a group too
+        assert_eq!(parse("a>b"), Ok(term("a>b")));
+        assert_eq!(parse("a)"), Ok(term("a)")));
+        // the comparison operator of the value is not the closer
+        assert_eq!(
+            parse("<size:>1mb>"),
+            Ok(group(function("size", ">1mb", false)))
+        );
+    }
+
+    #[test]
+    fn or_and_not() {
+        // `|` binds tighter than the space
+        assert_eq!(
+            parse("a | b c"),
+            Ok(Ast::And(vec![
+                Ast::Or(vec![term("a"), term("b")]),
+                term("c")
+            ]))
+        );
+        assert_eq!(
+            parse("a|b|c"),
+            Ok(Ast::Or(vec![term("a"), term("b"), term("c")]))
+        );
+        assert_eq!(parse("!a"), Ok(not(term("a"))));
+        assert_eq!(parse("!!a"), Ok(not(not(term("a")))));
+        assert_eq!(parse("a !b"), Ok(Ast::And(vec![term("a"), not(term("b"))])));
+        assert_eq!(
+            parse("!a | b"),
+            Ok(Ast::Or(vec![not(term("a")), term("b")]))
+        );
+        assert_eq!(
+            parse("!<a | b>"),
+            Ok(not(group(Ast::Or(vec![term("a"), term("b")]))))
+        );
+        assert_eq!(parse("!case:a"), Ok(not(modifier("case", term("a")))));
+        // `!` in the middle of a word is just text
+        assert_eq!(parse("a!b"), Ok(term("a!b")));
+    }
+
+    #[test]
+    fn malformed() {
+        use ParseErrorKind::*;
+        assert_eq!(parse(r#"foo "bar"#), error(UnclosedQuote, 4..8));
+        assert_eq!(parse(r#"ext:"rs"#), error(UnclosedQuote, 4..7));
+        assert_eq!(parse("<foo"), error(UnclosedGroup, 0..1));
+        assert_eq!(parse("<a (b>"), error(UnclosedGroup, 3..4));
+        assert_eq!(parse("a |"), error(MissingOperand, 2..3));
+        assert_eq!(parse("| a"), error(MissingOperand, 0..1));
+        assert_eq!(parse("a | | b"), error(MissingOperand, 2..3));
+        assert_eq!(parse("<a |>"), error(MissingOperand, 3..4));
+        assert_eq!(parse("!"), error(MissingOperand, 0..1));
+        assert_eq!(parse("! a"), error(MissingOperand, 0..1));
+        assert_eq!(parse("a !|b"), error(MissingOperand, 2..3));
+    }
+
+    #[test]
+    fn error_display() {
+        let err = parse(r#"foo "bar"#).unwrap_err();
+        assert_eq!(err.to_string(), "the quote is not closed at 4..8");
+    }
+
+    /// Print and parse back, which should be the same as parsed at first.
+    fn round_trip(text: &str) {
+        let ast = parse(text).unwrap();
+        let printed = ast.to_string();
+        assert_eq!(
+            parse(&printed),
+            Ok(ast),
+            "{text:?} is printed as {printed:?}"
+        );
+    }
+
+    #[test]
+    fn print_round_trip() {
+        let texts = [
+            "",
+            "<>",
+            "foo",
+            "foo  bar",
+            r#""foo bar""#,
+            r#""""#,
+            r#"foo"bar baz"qux"#,
+            r#""a | b""#,
+            r#""!a""#,
+            r#""<a>""#,
+            r#""case:a""#,
+            r"C:\Windows\*.dll",
+            r#"parent:"C:\Program Files""#,
+            "ext:rs",
+            "size:>1mb",
+            "dm:",
+            "C:",
+            "foo:bar:baz",
+            "case:foo",
+            "file: foo",
+            "case:path:foo",
+            "case:<a | b>",
+            "<a b>",
+            "(a | b) c",
+            "<a <b | c>>",
+            "<size:>1mb>",
+            "a | b c",
+            "a|b|c",
+            "!a",
+            "!!a",
+            "a !b",
+            "!a | b",
+            "!<a | b>",
+            "!case:a",
+            "a!b",
+        ];
+        for text in texts {
+            round_trip(text);
+        }
+    }
+
+    #[test]
+    fn print_quotes_the_text_with_operators() {
+        // the same terms, but quoted when printed
+        for (text, printed) in [
+            ("<a)>", r#"<"a)">"#),
+            ("(a>)", r#"<"a>">"#),
+            ("a>b", r#""a>b""#),
+            ("(a | b) c", "<a | b> c"),
+        ] {
+            assert_eq!(parse(text).unwrap().to_string(), printed);
+            round_trip(printed);
+        }
+        for (ast, printed) in [
+            (term("a b"), r#""a b""#),
+            (term("a|b"), r#""a|b""#),
+            (term("!a"), r#""!a""#),
+            (term("<a"), r#""<a""#),
+            (term("ext:rs"), r#""ext:rs""#),
+            (term(""), r#""""#),
+            (function("parent", r"C:\a b", false), r#"parent:"C:\a b""#),
+            (function("size", ">1 mb", false), r#"size:">1 mb""#),
+        ] {
+            assert_eq!(ast.to_string(), printed);
+            round_trip(printed);
+        }
+    }
+
+    #[test]
+    fn print_the_rewritten() {
+        let ast = term("a").and(term("b")).or(term("c"));
+        assert_eq!(ast.to_string(), "<a b> | c");
+        round_trip("<a b> | c");
+
+        let ast = parse("case:foo !case:bar").unwrap().strip_modifier("case");
+        assert_eq!(ast, parse("foo !bar").unwrap());
+
+        let ast = term("a").negate().and(Ast::Empty.negate());
+        assert_eq!(ast.to_string(), "!a");
+
+        let scope = Scope::folders([r"C:\src", r"D:\"]);
+        let ast = parse("*.rs").unwrap().with_scope(&scope);
+        assert_eq!(ast.to_string(), r#"<"C:\src\" | "D:\"> *.rs"#);
+        assert_eq!(parse(&ast.to_string()), Ok(ast));
+    }
+}
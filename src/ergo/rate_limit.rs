@@ -0,0 +1,139 @@
+//! Keep the queries from flooding Everything, such as the ones on each keystroke.
+//!
+//! Every query is a window message to Everything, which searches them one by one on its UI
+//! thread, so a burst of queries makes Everything itself lag. The [`RateLimiter`] bounds the
+//! queries in flight and spaces them out. Give it to
+//! [`EverythingBroker::spawn_limited`](super::EverythingBroker::spawn_limited), or take a
+//! [`Permit`] around your own queries.
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct State {
+    in_flight: usize,
+    last_start: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+    max_in_flight: usize,
+    min_interval: Duration,
+}
+
+/// The limit of the queries, shared by its clones.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use everything_sdk::*;
+/// use everything_sdk::rate_limit::RateLimiter;
+///
+/// // One query at a time, and at most 10 queries per second.
+/// let limiter = RateLimiter::new(1, Duration::from_millis(100));
+/// let _permit = limiter.acquire();
+/// let mut everything = global().lock().unwrap();
+/// let mut searcher = everything.searcher();
+/// println!("{}", searcher.set_search("*.rs").query().len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    shared: Arc<Shared>,
+}
+
+impl RateLimiter {
+    /// At most `max_in_flight` queries at the same time (at least 1), and each one starts at
+    /// least `min_interval` after the last one started.
+    pub fn new(max_in_flight: usize, min_interval: Duration) -> Self {
+        RateLimiter {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    in_flight: 0,
+                    last_start: None,
+                }),
+                changed: Condvar::new(),
+                max_in_flight: max_in_flight.max(1),
+                min_interval,
+            }),
+        }
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.shared.max_in_flight
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        self.shared.min_interval
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Block until a query is allowed, which is counted in flight until the permit is dropped.
+    pub fn acquire(&self) -> Permit {
+        let mut state = self.lock();
+        loop {
+            if state.in_flight >= self.shared.max_in_flight {
+                state = self
+                    .shared
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+                continue;
+            }
+            let since_last = state
+                .last_start
+                .map_or(Duration::MAX, |last| last.elapsed());
+            if since_last < self.shared.min_interval {
+                state = self
+                    .shared
+                    .changed
+                    .wait_timeout(state, self.shared.min_interval - since_last)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+                continue;
+            }
+            state.in_flight += 1;
+            state.last_start = Some(Instant::now());
+            return Permit {
+                limiter: self.clone(),
+            };
+        }
+    }
+
+    /// The permit if a query is allowed right now, without blocking.
+    pub fn try_acquire(&self) -> Option<Permit> {
+        let mut state = self.lock();
+        let since_last = state
+            .last_start
+            .map_or(Duration::MAX, |last| last.elapsed());
+        if state.in_flight >= self.shared.max_in_flight || since_last < self.shared.min_interval {
+            return None;
+        }
+        state.in_flight += 1;
+        state.last_start = Some(Instant::now());
+        Some(Permit {
+            limiter: self.clone(),
+        })
+    }
+}
+
+/// A query in flight, see [`RateLimiter::acquire`].
+#[must_use = "the query is not counted in flight when the permit is dropped"]
+#[derive(Debug)]
+pub struct Permit {
+    limiter: RateLimiter,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.limiter.lock().in_flight -= 1;
+        self.limiter.shared.changed.notify_all();
+    }
+}
@@ -0,0 +1,121 @@
+//! What Everything indexes, in one report for the users wondering why a search finds nothing.
+
+use super::{access, EverythingError, EverythingGlobal, Feature, FileInfoType, Result, SortType};
+use crate::raw;
+
+/// The file information indexed, by [`EverythingGlobal::is_file_info_indexed`].
+///
+/// The searches and the sorts by the information not indexed are slow, and the results have
+/// no such data unless it is requested (which is slow too).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedInfo {
+    pub file_size: bool,
+    pub folder_size: bool,
+    pub date_created: bool,
+    pub date_modified: bool,
+    pub date_accessed: bool,
+    pub attributes: bool,
+}
+
+impl IndexedInfo {
+    /// Whether the `info` is indexed.
+    pub fn is_indexed(&self, info: FileInfoType) -> bool {
+        match info {
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_FILE_SIZE => self.file_size,
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE => self.folder_size,
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_CREATED => self.date_created,
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED => self.date_modified,
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED => self.date_accessed,
+            FileInfoType::EVERYTHING_IPC_FILE_INFO_ATTRIBUTES => self.attributes,
+        }
+    }
+
+    /// The information not indexed.
+    pub fn missing(&self) -> Vec<FileInfoType> {
+        FILE_INFO_TYPES
+            .into_iter()
+            .filter(|&info| !self.is_indexed(info))
+            .collect()
+    }
+}
+
+const FILE_INFO_TYPES: [FileInfoType; 6] = [
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_FILE_SIZE,
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE,
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_CREATED,
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED,
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED,
+    FileInfoType::EVERYTHING_IPC_FILE_INFO_ATTRIBUTES,
+];
+
+/// The state of the index of Everything, by [`EverythingGlobal::index_report`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexReport {
+    /// Everything finds nothing while it is loading the database.
+    pub db_loaded: bool,
+    /// Everything running as administrator, or as the standard user (with the service maybe).
+    pub admin: bool,
+    /// The settings and the database are in `%APPDATA%\Everything`, or next to the exe.
+    pub appdata: bool,
+    pub indexed: IndexedInfo,
+    /// The values of the [`SortType`]s with the fast sort, the others are slow.
+    pub fast_sorts: Vec<u32>,
+}
+
+impl IndexReport {
+    /// Whether sorting by `sort` is fast.
+    pub fn is_fast_sort(&self, sort: SortType) -> bool {
+        self.fast_sorts.contains(&sort.to_u32())
+    }
+}
+
+impl EverythingGlobal {
+    /// Check everything about the index at once, see [`IndexReport`].
+    ///
+    /// It needs Everything 1.4.1.859 or later, or fails with [`EverythingError::Unsupported`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let everything = global().lock().unwrap();
+    /// let report = everything.index_report()?;
+    /// if !report.db_loaded {
+    ///     println!("Everything is still loading the database");
+    /// }
+    /// for info in report.indexed.missing() {
+    ///     println!("{info:?} is not indexed, searching by it is slow");
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn index_report(&self) -> Result<IndexReport> {
+        // Check the version once, instead of in each of the calls.
+        self.require(Feature::FileInfoIndexed)?;
+        let indexed = |info| -> Result<bool> {
+            raw::Everything_IsFileInfoIndexed(&access(), info).map_err(EverythingError::from)
+        };
+        let indexed = IndexedInfo {
+            file_size: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_FILE_SIZE)?,
+            folder_size: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE)?,
+            date_created: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_CREATED)?,
+            date_modified: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED)?,
+            date_accessed: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED)?,
+            attributes: indexed(FileInfoType::EVERYTHING_IPC_FILE_INFO_ATTRIBUTES)?,
+        };
+        let mut fast_sorts = Vec::new();
+        for sort in SortType::KNOWN {
+            if raw::Everything_IsFastSort(&access(), sort)? {
+                fast_sorts.push(sort.to_u32());
+            }
+        }
+        Ok(IndexReport {
+            db_loaded: self.is_db_loaded()?,
+            admin: self.is_admin()?,
+            appdata: self.is_appdata()?,
+            indexed,
+            fast_sorts,
+        })
+    }
+}
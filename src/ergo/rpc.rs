@@ -0,0 +1,289 @@
+//! The search over a localhost HTTP/JSON-RPC endpoint. (feature `rpc`)
+//!
+//! So the local processes not in Rust (Electron shells, scripts, ...) can share one Everything
+//! connection managed by this crate, instead of each of them talking IPC to Everything.
+//!
+//! It is [JSON-RPC 2.0](https://www.jsonrpc.org/specification) by `POST` with the
+//! `Content-Type: application/json`, one call per HTTP request, and the methods are:
+//!
+//! - `search`, the params are a [`SearchRequest`] and the result is a [`SearchResponse`], in
+//!   the same JSON as their serde. (all the fields of the params are optional)
+//! - `version`, the result is `{ "major": _, "minor": _, "revision": _, "build": _ }`.
+//!
+//! ```text
+//! $ curl -H 'Content-Type: application/json' http://127.0.0.1:8573 \
+//!     -d '{"jsonrpc": "2.0", "id": 1, "method": "search", "params": {"search": "cargo.toml", "max": 10}}'
+//! ```
+//!
+//! The server is bound to the loopback address only. The browsers can not call it from a web
+//! page, since the JSON content type needs a CORS preflight, which is never allowed here.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::logging::debug;
+use super::{global, EverythingError, SearchOptions, SearchRequest, SearchResponse};
+
+/// The port of [`RpcServer::bind_default`].
+pub const DEFAULT_PORT: u16 = 8573;
+
+const MAX_HEAD_LEN: usize = 16 * 1024;
+const MAX_BODY_LEN: usize = 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The JSON-RPC server over HTTP on `127.0.0.1`.
+///
+/// The calls are handled one by one, which is the same as the global lock does anyway.
+#[derive(Debug)]
+pub struct RpcServer {
+    listener: TcpListener,
+}
+
+impl RpcServer {
+    /// Bind to `127.0.0.1:port`, the port `0` for any free one (see [`RpcServer::local_addr`]).
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        Ok(RpcServer { listener })
+    }
+
+    /// Bind to `127.0.0.1:8573`.
+    pub fn bind_default() -> io::Result<Self> {
+        Self::bind(DEFAULT_PORT)
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve forever in the current thread, it only returns on the error of the listener.
+    ///
+    /// Each call takes the [`global`] lock while querying, and releases it before responding.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            if let Err(err) = handle_connection(stream) {
+                debug!("[rpc] connection error: {err}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve forever in a new thread.
+    pub fn spawn(self) -> io::Result<JoinHandle<io::Result<()>>> {
+        thread::Builder::new()
+            .name("everything-sdk-rpc".to_string())
+            .spawn(move || self.serve())
+    }
+}
+
+struct HttpRequest {
+    method: String,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+    let request = match read_request(&mut reader) {
+        Ok(request) => request,
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            return write_response(&mut stream, "400 Bad Request", None);
+        }
+        Err(err) => return Err(err),
+    };
+    if request.method != "POST" {
+        return write_response(&mut stream, "405 Method Not Allowed", None);
+    }
+    let is_json = request.content_type.as_deref().is_some_and(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+    });
+    if !is_json {
+        return write_response(&mut stream, "415 Unsupported Media Type", None);
+    }
+    match handle_call(&request.body) {
+        Some(reply) => write_response(&mut stream, "200 OK", Some(&reply)),
+        // a notification, no reply
+        None => write_response(&mut stream, "204 No Content", None),
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<HttpRequest> {
+    let bad = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut head_len = 0;
+    let mut line = String::new();
+    let mut read_line = |line: &mut String| -> io::Result<()> {
+        line.clear();
+        let n = (&mut *reader)
+            .take((MAX_HEAD_LEN - head_len) as u64)
+            .read_line(line)?;
+        head_len += n;
+        if !line.ends_with('\n') {
+            return Err(bad("truncated or too long head"));
+        }
+        Ok(())
+    };
+
+    read_line(&mut line)?;
+    let method = line
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let mut content_type = None;
+    let mut content_length = 0;
+    loop {
+        read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(bad("malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().map_err(|_| bad("bad content length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(bad("chunked body is not supported"));
+        }
+    }
+    if content_length > MAX_BODY_LEN {
+        return Err(bad("body too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(HttpRequest {
+        method,
+        content_type,
+        body,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, body: Option<&Value>) -> io::Result<()> {
+    let body = body.map(Value::to_string).unwrap_or_default();
+    let content_type = if body.is_empty() {
+        ""
+    } else {
+        "Content-Type: application/json\r\n"
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{content_type}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+// The error codes of JSON-RPC 2.0.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The [`EverythingError`], in the range of the server errors.
+const EVERYTHING_ERROR: i64 = -32000;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<EverythingError> for RpcError {
+    fn from(err: EverythingError) -> Self {
+        RpcError::new(EVERYTHING_ERROR, err.to_string())
+    }
+}
+
+/// Handle one JSON-RPC call, `None` if it is a notification.
+fn handle_call(body: &[u8]) -> Option<Value> {
+    let call: Value = match serde_json::from_slice(body) {
+        Ok(call) => call,
+        Err(err) => {
+            return Some(error_reply(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, err.to_string()),
+            ))
+        }
+    };
+    let Some(call) = call.as_object() else {
+        return Some(error_reply(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "batch or non-object call is not supported"),
+        ));
+    };
+    let id = call.get("id").cloned();
+    let result = match (call.get("jsonrpc"), call.get("method")) {
+        (Some(Value::String(version)), Some(Value::String(method))) if version == "2.0" => {
+            dispatch(method, call.get("params"))
+        }
+        _ => Err(RpcError::new(INVALID_REQUEST, "not a JSON-RPC 2.0 call")),
+    };
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(err) => error_reply(id, err),
+    })
+}
+
+fn error_reply(id: Value, err: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": err.code, "message": err.message },
+        "id": id,
+    })
+}
+
+fn dispatch(method: &str, params: Option<&Value>) -> Result<Value, RpcError> {
+    match method {
+        "search" => {
+            let request: SearchRequest = match params {
+                None | Some(Value::Null) => SearchRequest::default(),
+                Some(params) => SearchRequest::deserialize(params)
+                    .map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string()))?,
+            };
+            let options = SearchOptions::try_from(request)
+                .map_err(|_| RpcError::new(INVALID_PARAMS, "invalid `sort` or `request_flags`"))?;
+            let results = options.query_snapshot()?;
+            Ok(json!(SearchResponse::from(results)))
+        }
+        "version" => {
+            let (major, minor, revision, build) = version()?;
+            Ok(json!({ "major": major, "minor": minor, "revision": revision, "build": build }))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("no method `{method}`"),
+        )),
+    }
+}
+
+fn version() -> Result<(u32, u32, u32, u32), EverythingError> {
+    #[cfg(not(feature = "async"))]
+    let everything = global()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    #[cfg(feature = "async")]
+    let everything = futures::executor::block_on(global().lock());
+    let (major, minor, revision, build, _) = everything.version()?;
+    Ok((major, minor, revision, build))
+}
@@ -0,0 +1,264 @@
+//! Read the run history of Everything back from its `Run History.csv`.
+//!
+//! The SDK can only get the run count of one file at a time, while the whole history is in the
+//! file, once it is saved by [`EverythingGlobal::save_run_history`].
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut everything = global().lock().unwrap();
+//! everything.save_run_history()?;
+//! let path = everything.run_history_path()?;
+//! drop(everything);
+//! for record in run_history::load(path).unwrap() {
+//!     println!("{} runs: {}", record.run_count, record.path.display());
+//! }
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error as ThisError;
+
+use super::efu::split_row;
+use super::{
+    install, EverythingError, EverythingGlobal, EverythingSearcher, Feature, Instance, ItemData,
+    RequestFlags, Result, SortType,
+};
+
+/// A file run from Everything.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunRecord {
+    pub path: PathBuf,
+    pub run_count: u32,
+    /// In FILETIME, `None` if unknown.
+    pub date_run: Option<u64>,
+}
+
+#[derive(ThisError, Debug)]
+pub enum RunHistoryError {
+    #[error("failed to read the run history.")]
+    Io(#[from] io::Error),
+    #[error("the quote is not closed at line {line}.")]
+    UnclosedQuote { line: usize },
+    #[error("invalid number {value:?} at line {line}.")]
+    InvalidNumber { line: usize, value: String },
+}
+
+/// The file name of the run history of the `instance`, like `Run History-1.5a.csv`.
+pub fn file_name(instance: &Instance) -> String {
+    match instance.name() {
+        None => "Run History.csv".to_string(),
+        Some(name) => format!("Run History-{name}.csv"),
+    }
+}
+
+/// Where the run history of the `instance` is, in `%APPDATA%\Everything` or next to the
+/// installed `Everything.exe`, `None` if it is found in neither.
+///
+/// It guesses by the files existing, see [`EverythingGlobal::run_history_path`] for the exact
+/// one asked from Everything.
+pub fn locate(instance: &Instance) -> Option<PathBuf> {
+    let file_name = file_name(instance);
+    let appdata = std::env::var_os("APPDATA").map(|dir| Path::new(&dir).join("Everything"));
+    let install_dir = install::find().map(|found| found.dir().to_path_buf());
+    [appdata, install_dir]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Parse the run history, in the order of the file.
+///
+/// The columns are the path, the run count and the date run. The header line is optional, and
+/// the rows without a path are skipped.
+pub fn parse<R: BufRead>(reader: R) -> std::result::Result<Vec<RunRecord>, RunHistoryError> {
+    let mut records = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim_start_matches('\u{feff}');
+        let cells = split_row(line).ok_or(RunHistoryError::UnclosedQuote { line: i + 1 })?;
+        let mut cells = cells.into_iter();
+        let path = cells.next().unwrap_or_default();
+        if path.is_empty() || (i == 0 && path.eq_ignore_ascii_case("Filename")) {
+            continue;
+        }
+        let number = |cell: Option<String>| match cell.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(n) => n
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|_| RunHistoryError::InvalidNumber {
+                    line: i + 1,
+                    value: n.to_string(),
+                }),
+        };
+        let run_count = number(cells.next())?.unwrap_or_default();
+        records.push(RunRecord {
+            path: PathBuf::from(path),
+            run_count: u32::try_from(run_count).unwrap_or(u32::MAX),
+            date_run: number(cells.next())?,
+        });
+    }
+    Ok(records)
+}
+
+/// Read the run history file at `path`, see [`parse`].
+pub fn load(path: impl AsRef<Path>) -> std::result::Result<Vec<RunRecord>, RunHistoryError> {
+    parse(BufReader::new(File::open(path)?))
+}
+
+impl EverythingGlobal {
+    /// The path of `Run History.csv` of the connected Everything, by
+    /// [`EverythingGlobal::is_appdata`] and the exe found by [`install::find`].
+    ///
+    /// Fails with [`EverythingError::NotFound`] if it is not in `%APPDATA%` and Everything is
+    /// not installed.
+    pub fn run_history_path(&self) -> Result<PathBuf> {
+        let instance = self.instance().cloned().unwrap_or_default();
+        let dir = if self.is_appdata()? {
+            std::env::var_os("APPDATA").map(|dir| Path::new(&dir).join("Everything"))
+        } else {
+            install::find().map(|found| found.dir().to_path_buf())
+        };
+        dir.map(|dir| dir.join(file_name(&instance)))
+            .ok_or(EverythingError::NotFound)
+    }
+}
+
+/// The search of the files run at least once.
+const RUN_SEARCH: &str = "runcount:>0";
+
+impl EverythingGlobal {
+    /// The `n` files run most, with the most run first, by the run count descending sort.
+    ///
+    /// It is live from Everything, no need to save the run history first.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// for record in everything.top_run_items(10)? {
+    ///     println!("{:>4} {}", record.run_count, record.path.display());
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    #[cfg(not(feature = "async"))]
+    pub fn top_run_items(&mut self, n: u32) -> Result<Vec<RunRecord>> {
+        self.require(Feature::RunHistory)?;
+        let mut searcher = self.searcher();
+        set_top_run(&mut searcher, n);
+        let snapshot = searcher.query().snapshot();
+        Ok(snapshot
+            .into_items()
+            .into_iter()
+            .filter_map(record_of)
+            .collect())
+    }
+
+    /// The `n` files run most, see the blocking one.
+    #[cfg(feature = "async")]
+    pub async fn top_run_items(&mut self, n: u32) -> Result<Vec<RunRecord>> {
+        self.require(Feature::RunHistory)?;
+        let mut searcher = self.searcher();
+        set_top_run(&mut searcher, n);
+        let snapshot = searcher.query().await.snapshot();
+        Ok(snapshot
+            .into_items()
+            .into_iter()
+            .filter_map(record_of)
+            .collect())
+    }
+}
+
+fn set_top_run(searcher: &mut EverythingSearcher<'_>, n: u32) {
+    searcher
+        .set_search(RUN_SEARCH)
+        .set_sort(SortType::EVERYTHING_SORT_RUN_COUNT_DESCENDING)
+        .set_request_flags(
+            RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+                | RequestFlags::EVERYTHING_REQUEST_RUN_COUNT
+                | RequestFlags::EVERYTHING_REQUEST_DATE_RUN,
+        )
+        .set_max(n);
+}
+
+/// The record of a result, `None` if it has never run (or has no path).
+fn record_of(data: ItemData) -> Option<RunRecord> {
+    let run_count = data.run_count.filter(|&n| n > 0)?;
+    Some(RunRecord {
+        path: data.full_path?,
+        run_count,
+        date_run: data.date_run.filter(|&date| date != 0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, run_count: u32, date_run: Option<u64>) -> RunRecord {
+        RunRecord {
+            path: PathBuf::from(path),
+            run_count,
+            date_run,
+        }
+    }
+
+    #[test]
+    fn with_and_without_header() {
+        let rows = "\"C:\\a.exe\",3,133000000000000000\r\n\"C:\\b.exe\",1,\r\n";
+        let expected = vec![
+            record("C:\\a.exe", 3, Some(133000000000000000)),
+            record("C:\\b.exe", 1, None),
+        ];
+        assert_eq!(parse(rows.as_bytes()).unwrap(), expected);
+        let with_header = format!("Filename,Run Count,Date Run\r\n{rows}");
+        assert_eq!(parse(with_header.as_bytes()).unwrap(), expected);
+    }
+
+    #[test]
+    fn bom() {
+        let text = "\u{feff}Filename,Run Count,Date Run\r\nC:\\a.exe,2,\r\n";
+        assert_eq!(
+            parse(text.as_bytes()).unwrap(),
+            vec![record("C:\\a.exe", 2, None)]
+        );
+    }
+
+    #[test]
+    fn quoted_path_with_commas() {
+        let text = "\"C:\\a, \"\"b\"\".exe\",5,7\r\n\r\n,1,2\r\n";
+        assert_eq!(
+            parse(text.as_bytes()).unwrap(),
+            vec![record("C:\\a, \"b\".exe", 5, Some(7))]
+        );
+    }
+
+    #[test]
+    fn invalid_number() {
+        let text = "Filename,Run Count,Date Run\r\nC:\\a.exe,1,\r\nC:\\b.exe,x,\r\n";
+        match parse(text.as_bytes()) {
+            Err(RunHistoryError::InvalidNumber { line, value }) => {
+                assert_eq!(line, 3);
+                assert_eq!(value, "x");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unclosed_quote() {
+        let text = "C:\\a.exe,1,\r\n\"C:\\b.exe,1,\r\n";
+        assert!(matches!(
+            parse(text.as_bytes()),
+            Err(RunHistoryError::UnclosedQuote { line: 2 })
+        ));
+    }
+}
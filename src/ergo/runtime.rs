@@ -0,0 +1,55 @@
+//! Query on the blocking pool of the async runtime, by the `async-tokio` or `async-smol` feature.
+//!
+//! The futures of this crate (like [`EverythingSearcher::query`](super::EverythingSearcher::query)
+//! and [`PendingQuery`](super::PendingQuery)) are woken by the reply window of our own, so they
+//! work with any executor. That is all of the `async` feature being runtime-agnostic: it still
+//! depends on `futures` and makes [`global`](super::global) a [`futures::lock::Mutex`], and the
+//! sync code locks it by [`global_blocking`](super::global_blocking) instead.
+//!
+//! These are only the counterparts of [`spawn_query`](super::spawn_query) for the runtimes,
+//! which take the lock, query and snapshot without an extra thread per query.
+
+/// On the blocking pool of [tokio](https://docs.rs/tokio), which needs a tokio runtime.
+#[cfg(feature = "async-tokio")]
+pub mod tokio {
+    use crate::{OwnedResults, Result, SearchOptions};
+
+    /// Query in [`tokio::task::spawn_blocking`](::tokio::task::spawn_blocking), the same as
+    /// [`spawn_query`](crate::spawn_query) otherwise.
+    ///
+    /// # Panics
+    /// Resume the panic of the query on the blocking pool, or panic if the runtime is shutting
+    /// down and the query is cancelled.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// # async fn run() -> Result<()> {
+    /// let results = runtime::tokio::spawn_query(SearchOptions::new("*.rs")).await?;
+    /// println!("{} results", results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn spawn_query(options: SearchOptions) -> Result<OwnedResults> {
+        match ::tokio::task::spawn_blocking(move || options.query_snapshot()).await {
+            Ok(results) => results,
+            Err(err) => match err.try_into_panic() {
+                Ok(payload) => std::panic::resume_unwind(payload),
+                Err(err) => panic!("the query on the blocking pool is cancelled: {err}"),
+            },
+        }
+    }
+}
+
+/// On the blocking pool of [smol](https://docs.rs/smol), which works with any executor.
+#[cfg(feature = "async-smol")]
+pub mod smol {
+    use crate::{OwnedResults, Result, SearchOptions};
+
+    /// Query in [`blocking::unblock`], the same as [`spawn_query`](crate::spawn_query)
+    /// otherwise.
+    pub async fn spawn_query(options: SearchOptions) -> Result<OwnedResults> {
+        blocking::unblock(move || options.query_snapshot()).await
+    }
+}
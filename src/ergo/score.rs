@@ -0,0 +1,200 @@
+//! The relevance scoring of the results, for a "best match first" ordering in the launchers.
+
+use std::time::Duration;
+
+use super::{EverythingSearcher, FileTime, ItemData, OwnedResults, RequestFlags};
+
+/// The pluggable scorer of the items, the higher the better.
+pub trait Scorer {
+    /// The request flags needed by [`Scorer::score`], see
+    /// [`EverythingSearcher::request_flags_for`].
+    fn request_flags(&self) -> RequestFlags;
+
+    fn score(&self, data: &ItemData) -> f64;
+}
+
+/// The default heuristic scorer, a weighted sum of run count, recency and path depth.
+///
+/// - run count: `ln(1 + run_count)`, the items you run often.
+/// - recency: `0.5 ^ (age / half_life)` in `[0, 1]`, by the latest one of the date run and
+///   the date modified.
+/// - path depth: `1 / (1 + depth)` in `(0, 1]`, the shallow ones like `C:\Tools\app.exe`
+///   rather than the ones buried in `node_modules`.
+#[derive(Clone, Debug)]
+pub struct HeuristicScorer {
+    pub run_count_weight: f64,
+    pub recency_weight: f64,
+    pub depth_weight: f64,
+    /// The score of recency is halved for each `half_life` elapsed.
+    pub half_life: Duration,
+    now: FileTime,
+}
+
+impl Default for HeuristicScorer {
+    fn default() -> Self {
+        HeuristicScorer {
+            run_count_weight: 1.0,
+            recency_weight: 1.0,
+            depth_weight: 0.5,
+            half_life: Duration::from_secs(7 * 24 * 60 * 60),
+            now: FileTime::now(),
+        }
+    }
+}
+
+impl HeuristicScorer {
+    /// The same as `HeuristicScorer::default()`, the recency is relative to now.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn recency(&self, data: &ItemData) -> f64 {
+        let Some(latest) = data.date_run.max(data.date_modified) else {
+            return 0.0;
+        };
+        let age = self.now.get().saturating_sub(latest) as f64 * 100e-9;
+        let half_life = self.half_life.as_secs_f64();
+        if half_life > 0.0 {
+            0.5f64.powf(age / half_life)
+        } else {
+            0.0
+        }
+    }
+
+    fn depth(data: &ItemData) -> Option<usize> {
+        match (&data.path, &data.full_path) {
+            (Some(path), _) => Some(path.components().count()),
+            (None, Some(full_path)) => Some(full_path.components().count().saturating_sub(1)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Scorer for HeuristicScorer {
+    fn request_flags(&self) -> RequestFlags {
+        RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+            | RequestFlags::EVERYTHING_REQUEST_PATH
+            | RequestFlags::EVERYTHING_REQUEST_RUN_COUNT
+            | RequestFlags::EVERYTHING_REQUEST_DATE_RUN
+            | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED
+    }
+
+    fn score(&self, data: &ItemData) -> f64 {
+        let run_count = f64::from(data.run_count.unwrap_or(0)).ln_1p();
+        let depth = Self::depth(data).map_or(0.0, |depth| 1.0 / (1.0 + depth as f64));
+        self.run_count_weight * run_count
+            + self.recency_weight * self.recency(data)
+            + self.depth_weight * depth
+    }
+}
+
+/// The frecency (frequency and recency) scorer, like the address bars of the browsers.
+///
+/// Each run counts by how long ago the item was last run, in the buckets of 4, 14, 31 and 90
+/// days (100, 70, 50, 30, and 10 for the older ones), times the run count. The date modified
+/// counts as a fraction of a run, so the items never run are still ordered by recency.
+#[derive(Clone, Debug)]
+pub struct FrecencyScorer {
+    /// How much the date modified counts, as a run at that date.
+    pub modified_weight: f64,
+    now: FileTime,
+}
+
+impl Default for FrecencyScorer {
+    fn default() -> Self {
+        FrecencyScorer {
+            modified_weight: 0.1,
+            now: FileTime::now(),
+        }
+    }
+}
+
+impl FrecencyScorer {
+    /// The same as `FrecencyScorer::default()`, the recency is relative to now.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn bucket(&self, date: Option<u64>) -> f64 {
+        const DAY: u64 = 24 * 60 * 60 * 10_000_000;
+        let Some(date) = date else {
+            return 0.0;
+        };
+        match self.now.get().saturating_sub(date) / DAY {
+            0..=3 => 100.0,
+            4..=13 => 70.0,
+            14..=30 => 50.0,
+            31..=89 => 30.0,
+            _ => 10.0,
+        }
+    }
+}
+
+impl Scorer for FrecencyScorer {
+    fn request_flags(&self) -> RequestFlags {
+        RequestFlags::EVERYTHING_REQUEST_RUN_COUNT
+            | RequestFlags::EVERYTHING_REQUEST_DATE_RUN
+            | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED
+    }
+
+    fn score(&self, data: &ItemData) -> f64 {
+        let runs = f64::from(data.run_count.unwrap_or(0)) * self.bucket(data.date_run);
+        runs + self.modified_weight * self.bucket(data.date_modified)
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Add the request flags needed by the `scorer` to the current ones.
+    pub fn request_flags_for(&mut self, scorer: &impl Scorer) -> &'_ mut EverythingSearcher<'a> {
+        let flags = self.get_request_flags() | scorer.request_flags();
+        self.set_request_flags(flags)
+    }
+}
+
+impl OwnedResults {
+    /// Sort the items by the score of `scorer`, the best first. The sort is stable.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::{score::HeuristicScorer, *};
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// let scorer = HeuristicScorer::new();
+    /// searcher.set_search("code.exe").request_flags_for(&scorer);
+    /// let mut snapshot = searcher.query().snapshot();
+    /// snapshot.rank_by(&scorer);
+    /// ```
+    pub fn rank_by(&mut self, scorer: &impl Scorer) {
+        self.items
+            .sort_by_cached_key(|data| std::cmp::Reverse(OrderedScore(scorer.score(data))));
+    }
+
+    /// Sort the items by [`FrecencyScorer`], the best first, which needs the run count, the
+    /// date run and the date modified requested.
+    pub fn rank_by_frecency(&mut self) {
+        self.rank_by(&FrecencyScorer::new());
+    }
+}
+
+/// The total order of the scores by [`f64::total_cmp`].
+struct OrderedScore(f64);
+
+impl PartialEq for OrderedScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0).is_eq()
+    }
+}
+
+impl Eq for OrderedScore {}
+
+impl PartialOrd for OrderedScore {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedScore {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
@@ -0,0 +1,252 @@
+//! Control the Everything service by the Service Control Manager.
+//!
+//! The `Everything_MSIStartService` of the SDK is only for the installer, and does nothing
+//! about the errors. Starting and stopping a service need the admin rights, so the app
+//! running as the standard user gets [`ServiceError::AccessDenied`], and then may ask the user
+//! by [`EverythingService::run_elevated`] (the UAC prompt).
+//!
+//! ```no_run
+//! use everything_sdk::service::{EverythingService, ServiceAction, ServiceError};
+//!
+//! let service = EverythingService::open()?;
+//! match service.start() {
+//!     Err(ServiceError::AccessDenied) => {
+//!         service.run_elevated(ServiceAction::Start)?;
+//!     }
+//!     result => result?,
+//! }
+//! # Ok::<(), ServiceError>(())
+//! ```
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use thiserror::Error as ThisError;
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_ACCESS_DENIED, ERROR_CANCELLED, ERROR_SERVICE_ALREADY_RUNNING,
+    ERROR_SERVICE_DOES_NOT_EXIST, ERROR_SERVICE_NOT_ACTIVE, WIN32_ERROR,
+};
+use windows::Win32::System::Services::{
+    CloseServiceHandle, ControlService, OpenSCManagerW, OpenServiceW, QueryServiceStatus,
+    StartServiceW, SC_HANDLE, SC_MANAGER_CONNECT, SERVICE_CONTROL_STOP, SERVICE_QUERY_STATUS,
+    SERVICE_START, SERVICE_STATUS, SERVICE_STOP,
+};
+use windows::Win32::System::Threading::{WaitForSingleObject, INFINITE};
+use windows::Win32::UI::Shell::{ShellExecuteExW, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW};
+use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+
+use super::install::SERVICE_NAMES;
+
+/// The errors of the service control.
+#[derive(ThisError, Debug)]
+pub enum ServiceError {
+    #[error("the Everything service is not installed.")]
+    NotInstalled,
+    #[error("access denied, the service control needs the admin rights.")]
+    AccessDenied,
+    #[error("the elevation is cancelled by the user.")]
+    Cancelled,
+    #[error("the service is still {0:?} after the timeout.")]
+    Timeout(ServiceState),
+    #[error(transparent)]
+    Os(windows::core::Error),
+}
+
+impl From<windows::core::Error> for ServiceError {
+    fn from(e: windows::core::Error) -> Self {
+        let is = |code: WIN32_ERROR| e.code() == code.to_hresult();
+        if is(ERROR_ACCESS_DENIED) {
+            ServiceError::AccessDenied
+        } else if is(ERROR_SERVICE_DOES_NOT_EXIST) {
+            ServiceError::NotInstalled
+        } else if is(ERROR_CANCELLED) {
+            ServiceError::Cancelled
+        } else {
+            ServiceError::Os(e)
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ServiceError>;
+
+/// The state of the service, `dwCurrentState` of `SERVICE_STATUS`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ServiceState {
+    Stopped,
+    StartPending,
+    StopPending,
+    Running,
+    ContinuePending,
+    PausePending,
+    Paused,
+    Unknown(u32),
+}
+
+impl From<u32> for ServiceState {
+    fn from(state: u32) -> Self {
+        match state {
+            1 => ServiceState::Stopped,
+            2 => ServiceState::StartPending,
+            3 => ServiceState::StopPending,
+            4 => ServiceState::Running,
+            5 => ServiceState::ContinuePending,
+            6 => ServiceState::PausePending,
+            7 => ServiceState::Paused,
+            _ => ServiceState::Unknown(state),
+        }
+    }
+}
+
+/// What [`EverythingService::run_elevated`] does.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+/// The closing handle of the service or the manager.
+struct Handle(SC_HANDLE);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseServiceHandle(self.0);
+        }
+    }
+}
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The Everything service, by its name.
+///
+/// It keeps only the name, and opens the service with the access needed for each call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EverythingService {
+    name: String,
+}
+
+impl EverythingService {
+    /// The service installed, of Everything 1.4 or then 1.5 alpha.
+    pub fn open() -> Result<Self> {
+        SERVICE_NAMES
+            .into_iter()
+            .find_map(|name| Self::open_named(name).ok())
+            .ok_or(ServiceError::NotInstalled)
+    }
+
+    /// The service of `name`, which fails if it is not installed.
+    pub fn open_named(name: &str) -> Result<Self> {
+        let service = EverythingService {
+            name: name.to_string(),
+        };
+        service.handle(SERVICE_QUERY_STATUS)?;
+        Ok(service)
+    }
+
+    /// The service name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn handle(&self, access: u32) -> Result<Handle> {
+        let manager =
+            unsafe { OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_CONNECT)? };
+        let manager = Handle(manager);
+        let service = unsafe { OpenServiceW(manager.0, &HSTRING::from(&self.name), access)? };
+        Ok(Handle(service))
+    }
+
+    /// The current state.
+    pub fn state(&self) -> Result<ServiceState> {
+        let service = self.handle(SERVICE_QUERY_STATUS)?;
+        let mut status = SERVICE_STATUS::default();
+        unsafe { QueryServiceStatus(service.0, &mut status)? };
+        Ok(ServiceState::from(status.dwCurrentState.0))
+    }
+
+    /// Ask the service to start, and return without waiting, see [`EverythingService::wait_for`].
+    ///
+    /// It is fine if the service is running already.
+    pub fn start(&self) -> Result<()> {
+        let service = self.handle(SERVICE_START)?;
+        match unsafe { StartServiceW(service.0, None) } {
+            Err(e) if e.code() == ERROR_SERVICE_ALREADY_RUNNING.to_hresult() => Ok(()),
+            result => Ok(result?),
+        }
+    }
+
+    /// Ask the service to stop, and return without waiting, see [`EverythingService::wait_for`].
+    ///
+    /// It is fine if the service is stopped already.
+    pub fn stop(&self) -> Result<()> {
+        let service = self.handle(SERVICE_STOP)?;
+        let mut status = SERVICE_STATUS::default();
+        match unsafe { ControlService(service.0, SERVICE_CONTROL_STOP, &mut status) } {
+            Err(e) if e.code() == ERROR_SERVICE_NOT_ACTIVE.to_hresult() => Ok(()),
+            result => Ok(result?),
+        }
+    }
+
+    /// Stop and start the service, waiting up to `timeout` for each.
+    pub fn restart(&self, timeout: Duration) -> Result<()> {
+        self.stop()?;
+        self.wait_for(ServiceState::Stopped, timeout)?;
+        self.start()?;
+        self.wait_for(ServiceState::Running, timeout)
+    }
+
+    /// Block until the service is in `state`, or [`ServiceError::Timeout`] with the last state.
+    pub fn wait_for(&self, state: ServiceState, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let current = self.state()?;
+            if current == state {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(ServiceError::Timeout(current));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Do the `action` by `net.exe` with the admin rights, which shows the UAC prompt to the
+    /// user. Block until it is done, and return the state after that.
+    ///
+    /// Fail with [`ServiceError::Cancelled`] if the user says no.
+    pub fn run_elevated(&self, action: ServiceAction) -> Result<ServiceState> {
+        let name = &self.name;
+        let (file, parameters) = match action {
+            ServiceAction::Start => ("net.exe", format!(r#"start "{name}""#)),
+            ServiceAction::Stop => ("net.exe", format!(r#"stop "{name}""#)),
+            ServiceAction::Restart => (
+                "cmd.exe",
+                format!(r#"/c net stop "{name}" & net start "{name}""#),
+            ),
+        };
+        let (verb, file, parameters) = (
+            HSTRING::from("runas"),
+            HSTRING::from(file),
+            HSTRING::from(parameters),
+        );
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            fMask: SEE_MASK_NOCLOSEPROCESS,
+            lpVerb: PCWSTR(verb.as_ptr()),
+            lpFile: PCWSTR(file.as_ptr()),
+            lpParameters: PCWSTR(parameters.as_ptr()),
+            nShow: SW_HIDE.0,
+            ..Default::default()
+        };
+        unsafe { ShellExecuteExW(&mut info)? };
+        if !info.hProcess.is_invalid() {
+            unsafe {
+                WaitForSingleObject(info.hProcess, INFINITE);
+                let _ = CloseHandle(info.hProcess);
+            }
+        }
+        self.state()
+    }
+}
@@ -0,0 +1,880 @@
+//! The owned snapshot of the results, and the client-side operations over it.
+//!
+//! Different from [`EverythingResults`], a snapshot does not borrow the global lock, so you can
+//! keep it around, send it to other threads, and do the things Everything can not do natively.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use super::{
+    EverythingError, EverythingResults, EverythingSearcher, InvalidRequestError, ItemData,
+    ItemKind, RequestFlags, Result, SearchOptions, SortType,
+};
+use crate::raw::{QueryReply, ReplyItem};
+
+/// The owned copy of all the visible results, see [`EverythingResults::snapshot`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct OwnedResults {
+    pub(super) items: Vec<ItemData>,
+    request_flags: RequestFlags,
+    sort_type: SortType,
+    total: u32,
+    options: SearchOptions,
+}
+
+impl<'a> EverythingResults<'a> {
+    /// Copy all the visible results out, and then the lock can be released as soon as possible.
+    pub fn snapshot(&self) -> OwnedResults {
+        let mut items = Vec::new();
+        self.fill_data(&mut items);
+        OwnedResults {
+            items,
+            request_flags: self.request_flags(),
+            sort_type: self.sort_type(),
+            total: self.total(),
+            options: SearchOptions::current(),
+        }
+    }
+
+    /// Consume the results into the owned snapshot, the same as [`EverythingResults::snapshot`]
+    /// but reads better at the end of a query chain.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let owned: OwnedResults = {
+    ///     let mut everything = global().lock().unwrap();
+    ///     let mut searcher = everything.searcher();
+    ///     searcher.set_search("*.rs");
+    ///     let results = searcher.query().into_owned();
+    ///     results
+    /// }; // the lock is released here, and the results live on
+    /// std::thread::spawn(move || {
+    ///     for item in &owned {
+    ///         println!("{:?}", item.full_path);
+    ///     }
+    /// });
+    /// ```
+    pub fn into_owned(self) -> OwnedResults {
+        self.snapshot()
+    }
+
+    /// Group the items by their extensions, the same as [`OwnedResults::group_by_extension`]
+    /// but without the snapshot of all the results in between.
+    ///
+    /// It needs `EVERYTHING_REQUEST_EXTENSION` or `EVERYTHING_REQUEST_FILE_NAME`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("D:\\Downloads\\");
+    /// let results = searcher.query();
+    /// for (extension, items) in results.group_by_extension()? {
+    ///     println!("{extension:?}: {} files", items.len());
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn group_by_extension(&self) -> Result<HashMap<OsString, Vec<ItemData>>> {
+        let flags = self.request_flags();
+        if !flags.intersects(
+            RequestFlags::EVERYTHING_REQUEST_EXTENSION | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+        ) {
+            return Err(InvalidRequestError::RequestFlagsNotSet(
+                RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            )
+            .into());
+        }
+        Ok(group_by_extension(self.iter().map(|item| item.to_data())))
+    }
+
+    /// Group the items by their parent folders, the same as [`OwnedResults::group_by_parent`]
+    /// but without the snapshot of all the results in between.
+    ///
+    /// It needs `EVERYTHING_REQUEST_PATH` or `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`.
+    pub fn group_by_parent(&self) -> Result<HashMap<PathBuf, Vec<ItemData>>> {
+        let flags = self.request_flags();
+        if !flags.intersects(
+            RequestFlags::EVERYTHING_REQUEST_PATH
+                | RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+        ) {
+            return Err(InvalidRequestError::RequestFlagsNotSet(
+                RequestFlags::EVERYTHING_REQUEST_PATH,
+            )
+            .into());
+        }
+        Ok(group_by_parent(self.iter().map(|item| item.to_data())))
+    }
+}
+
+// The snapshot is plain data, to be sent to other threads and kept around.
+const _: () = {
+    fn assert_plain_data<T: Send + Sync + Clone + 'static>() {}
+    let _ = assert_plain_data::<OwnedResults>;
+};
+
+impl OwnedResults {
+    /// The snapshot of the items from another source than the Everything IPC.
+    pub(super) fn from_parts(
+        items: Vec<ItemData>,
+        request_flags: RequestFlags,
+        sort_type: SortType,
+        total: u32,
+        options: SearchOptions,
+    ) -> Self {
+        OwnedResults {
+            items,
+            request_flags,
+            sort_type,
+            total,
+            options,
+        }
+    }
+
+    /// The snapshot of the reply decoded by ourselves, of the query by `options`.
+    pub(super) fn from_reply(reply: QueryReply, options: SearchOptions) -> Self {
+        let request_flags = reply.request_flags;
+        let sort_type = reply.sort_type.unwrap_or(options.sort);
+        let items = reply
+            .items
+            .into_iter()
+            .zip(reply.offset..)
+            .map(|(item, index)| item_data_of(item, index, request_flags))
+            .collect();
+        Self::from_parts(items, request_flags, sort_type, reply.total, options)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> &[ItemData] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut [ItemData] {
+        &mut self.items
+    }
+
+    pub fn into_items(self) -> Vec<ItemData> {
+        self.items
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ItemData> {
+        self.items.iter()
+    }
+
+    /// The request flags of the results when it was copied.
+    pub fn request_flags(&self) -> RequestFlags {
+        self.request_flags
+    }
+
+    /// The sort type of the results when it was copied, it is NOT updated by the client-side
+    /// sort like [`OwnedResults::sort_by_keys`], except [`OwnedResults::sort_fallback`].
+    pub fn sort_type(&self) -> SortType {
+        self.sort_type
+    }
+
+    /// The number of all the results, including the invisible ones out of offset and max.
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// The search state of the query when it was copied.
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// Narrow the results down by more search `terms`, see [`OwnedResults::refine_with`].
+    ///
+    /// It is done client-side by [`Refine::ClientSide`], without the IPC and the lock.
+    pub fn refine(&self, terms: &str) -> Result<OwnedResults> {
+        self.refine_with(terms, Refine::default())
+    }
+
+    /// Narrow the results down by more search `terms`, for the "search within results" UIs.
+    ///
+    /// With [`Refine::Requery`], the original search and `terms` are combined as
+    /// `(search) terms` and queried again with the same options, which takes the
+    /// [`global`](super::global) lock, so do NOT call it while holding the lock. The regex
+    /// search can not be combined, and it fails with [`EverythingError::InvalidParameter`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::{snapshot::Refine, *};
+    /// let snapshot = {
+    ///     let mut everything = global().lock().unwrap();
+    ///     let mut searcher = everything.searcher();
+    ///     searcher.set_search("*.rs");
+    ///     let snapshot = searcher.query().snapshot();
+    ///     snapshot
+    /// }; // release the lock before the requery
+    /// let tests = snapshot.refine_with("test", Refine::Requery).unwrap();
+    /// ```
+    pub fn refine_with(&self, terms: &str, refine: Refine) -> Result<OwnedResults> {
+        match refine {
+            Refine::ClientSide => {
+                let terms: Vec<_> = terms.split_whitespace().map(Term::new).collect();
+                let items: Vec<_> = self
+                    .items
+                    .iter()
+                    .filter(|data| terms.iter().all(|term| term.matches(data, &self.options)))
+                    .cloned()
+                    .collect();
+                Ok(OwnedResults {
+                    total: items.len() as u32,
+                    items,
+                    ..self.clone_empty()
+                })
+            }
+            Refine::Requery => {
+                if self.options.regex {
+                    return Err(EverythingError::InvalidParameter);
+                }
+                let mut options = self.options.clone();
+                options.search = match (options.search.trim(), terms.trim()) {
+                    (search, "") => search.to_string(),
+                    ("", terms) => terms.to_string(),
+                    (search, terms) => format!("({search}) {terms}"),
+                };
+                options.query_snapshot()
+            }
+        }
+    }
+
+    /// The same results without any item.
+    fn clone_empty(&self) -> OwnedResults {
+        OwnedResults {
+            items: Vec::new(),
+            request_flags: self.request_flags,
+            sort_type: self.sort_type,
+            total: 0,
+            options: self.options.clone(),
+        }
+    }
+
+    /// Sort the items by the keys in order, the later keys only break the ties of the earlier
+    /// ones, and the sort is stable, so the items are kept in the original order (usually the
+    /// sort order of Everything) when all the keys are equal.
+    ///
+    /// It covers the orderings Everything can not do natively, e.g. by extension then by size.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::{snapshot::Key, *};
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.rs").set_request_flags(
+    ///     RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+    ///         | RequestFlags::EVERYTHING_REQUEST_PATH
+    ///         | RequestFlags::EVERYTHING_REQUEST_SIZE,
+    /// );
+    /// let mut snapshot = searcher.query().snapshot();
+    /// snapshot.sort_by_keys(&[Key::Extension, Key::SizeDesc, Key::Name]);
+    /// ```
+    pub fn sort_by_keys(&mut self, keys: &[Key]) {
+        self.items.sort_by(|a, b| {
+            keys.iter()
+                .map(|key| key.compare(a, b))
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// Whether Everything sorted the results by the requested sort of [`options`](Self::options).
+    ///
+    /// Everything may fall back to another sort (usually by name) if the requested one has no
+    /// fast sort index and the query is too large to sort slowly.
+    pub fn is_sorted_as_requested(&self) -> bool {
+        self.sort_type == self.options.sort
+    }
+
+    /// Sort the items in Rust by the requested sort, if Everything sorted them by another one,
+    /// and then the [`sort_type`](Self::sort_type) is the requested one. Return whether sorted.
+    ///
+    /// The ties are sorted by the name then the path, so the order is deterministic. The
+    /// column of the sort should be requested, see [`Key::request_flags`]. Only the visible
+    /// results are sorted, so with the `offset` or the `max`, the page itself may still be
+    /// different from the one sorted by Everything.
+    ///
+    /// Nothing is done for the sort by type name, which needs the shell.
+    pub fn sort_fallback(&mut self) -> bool {
+        if self.is_sorted_as_requested() {
+            return false;
+        }
+        let Some(key) = Key::from_sort_type(self.options.sort) else {
+            return false;
+        };
+        self.sort_by_keys(&[key, Key::Name, Key::Path]);
+        self.sort_type = self.options.sort;
+        true
+    }
+
+    /// The order-independent hash of the items, to tell "nothing changed since the last query"
+    /// cheaply, without diffing the whole snapshots.
+    ///
+    /// Each item is hashed by its identity (the full path, or the path and the file name) and
+    /// the key columns (size, date modified and attributes, if requested), and the hashes are
+    /// summed up, so the order of the items does not matter. The total is hashed too.
+    ///
+    /// It is stable in one process, but NOT across the Rust versions, so do not persist it.
+    pub fn fingerprint(&self) -> u64 {
+        let items = self.items.iter().fold(0u64, |sum, data| {
+            let mut hasher = DefaultHasher::new();
+            data.full_path.hash(&mut hasher);
+            data.path.hash(&mut hasher);
+            data.filename.hash(&mut hasher);
+            data.size.hash(&mut hasher);
+            data.date_modified.hash(&mut hasher);
+            data.attributes.hash(&mut hasher);
+            sum.wrapping_add(hasher.finish())
+        });
+        let mut hasher = DefaultHasher::new();
+        (items, self.items.len(), self.total).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Group the items by their parent folders, for the "matches grouped by folder" UIs.
+    ///
+    /// It is done in one pass, and each parent path is allocated only once for its group. The
+    /// parent is the [`ItemData::path`], or the parent of [`ItemData::full_path`] if no path.
+    /// The items without both of them are skipped. The items in a group keep their order.
+    pub fn group_by_parent(&self) -> HashMap<PathBuf, Vec<ItemData>> {
+        group_by_parent(self.items.iter().cloned())
+    }
+
+    /// Group the items by their extensions in lowercase (empty for none and for the folders),
+    /// by [`ItemData::extension`] or the extension of [`ItemData::filename`], the same as the
+    /// [`Key::Extension`] sorting. The items without both of them are skipped. The items in a
+    /// group keep their order.
+    pub fn group_by_extension(&self) -> HashMap<OsString, Vec<ItemData>> {
+        group_by_extension(self.items.iter().cloned())
+    }
+
+    /// Group the items by the file lists (like the `.efu` ones) they come from, for the
+    /// offline-list workflows to tell the origin of each result.
+    ///
+    /// It needs `EVERYTHING_REQUEST_FILE_LIST_FILE_NAME`, otherwise all the items are
+    /// [`FileListGroups::unlisted`]. The items in a group keep their order.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("*.pdf").set_request_flags(
+    ///     RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+    ///         | RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME,
+    /// );
+    /// let groups = searcher.query().snapshot().group_by_file_list();
+    /// for (file_list, items) in &groups.lists {
+    ///     println!("{}: {} items", file_list.display(), items.len());
+    /// }
+    /// ```
+    pub fn group_by_file_list(&self) -> FileListGroups {
+        let mut groups = FileListGroups::default();
+        for data in &self.items {
+            let file_list = match &data.file_list_filename {
+                // Everything gives the empty name for the items not in a file list.
+                Some(name) if !name.is_empty() => Path::new(name),
+                _ => {
+                    groups.unlisted.push(data.clone());
+                    continue;
+                }
+            };
+            match groups.lists.get_mut(file_list) {
+                Some(group) => group.push(data.clone()),
+                None => {
+                    groups
+                        .lists
+                        .insert(file_list.to_path_buf(), vec![data.clone()]);
+                }
+            }
+        }
+        groups
+    }
+}
+
+impl EverythingSearcher<'_> {
+    /// Query and snapshot the results, which are sorted in Rust if Everything did not sort
+    /// them as requested, see [`OwnedResults::sort_fallback`].
+    ///
+    /// The request flags of the sort column are added to the search state, so the sort has
+    /// the data to compare.
+    #[cfg(not(feature = "async"))]
+    pub fn query_sorted(&mut self) -> OwnedResults {
+        self.request_sort_column();
+        let mut results = self.query().snapshot();
+        results.sort_fallback();
+        results
+    }
+
+    /// Query and snapshot the results, which are sorted in Rust if Everything did not sort
+    /// them as requested, see [`OwnedResults::sort_fallback`].
+    ///
+    /// The request flags of the sort column are added to the search state, so the sort has
+    /// the data to compare.
+    #[cfg(feature = "async")]
+    pub async fn query_sorted(&mut self) -> OwnedResults {
+        self.request_sort_column();
+        let mut results = self.query().await.snapshot();
+        results.sort_fallback();
+        results
+    }
+
+    fn request_sort_column(&mut self) {
+        if let Some(key) = Key::from_sort_type(self.get_sort()) {
+            let flags = self.get_request_flags() | key.request_flags();
+            self.set_request_flags(flags);
+        }
+    }
+}
+
+/// How [`OwnedResults::refine_with`] narrows the results down.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Refine {
+    /// Filter the items in the snapshot, instantly and offline.
+    ///
+    /// Each term (split by whitespace) should be a substring of the file name, or of the full
+    /// path if the term contains `\` or the match path is on, and the case follows the match
+    /// case. It is a plain substring match, the search syntax (wildcards, functions, `|` and
+    /// so on) is NOT supported.
+    #[default]
+    ClientSide,
+    /// Query again with the combined search, so the full search syntax works and the results
+    /// are exact, even for the ones out of the max in the snapshot.
+    Requery,
+}
+
+/// A term of [`Refine::ClientSide`].
+struct Term<'a> {
+    text: &'a str,
+    lowercase: String,
+    in_path: bool,
+}
+
+impl<'a> Term<'a> {
+    fn new(text: &'a str) -> Self {
+        Term {
+            text,
+            lowercase: text.to_lowercase(),
+            in_path: text.contains('\\'),
+        }
+    }
+
+    fn matches(&self, data: &ItemData, options: &SearchOptions) -> bool {
+        let haystack = if self.in_path || options.match_path {
+            data.full_path.as_ref().map(|path| path.as_os_str())
+        } else {
+            data.filename.as_deref()
+        };
+        let Some(haystack) = haystack else {
+            return false;
+        };
+        let haystack = haystack.to_string_lossy();
+        if options.match_case {
+            haystack.contains(self.text)
+        } else {
+            haystack.to_lowercase().contains(&self.lowercase)
+        }
+    }
+}
+
+/// The items grouped by their file lists, see [`OwnedResults::group_by_file_list`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FileListGroups {
+    /// The file list filename and its items.
+    pub lists: HashMap<PathBuf, Vec<ItemData>>,
+    /// The items not in any file list (from the indexed volumes and folders).
+    pub unlisted: Vec<ItemData>,
+}
+
+/// The sort key of the client-side sort, the `*Desc` ones are in descending order.
+///
+/// The text keys are compared case-insensitively. The items without the data of the key (not
+/// requested, or like the size of a folder without indexing) are always placed at the end.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Key {
+    Name,
+    NameDesc,
+    /// By the path (the parent folder), then by the name.
+    Path,
+    PathDesc,
+    /// By the extension, derived from the file name if `EVERYTHING_REQUEST_EXTENSION` is not
+    /// requested, and the folders have no extension.
+    Extension,
+    ExtensionDesc,
+    Size,
+    SizeDesc,
+    DateCreated,
+    DateCreatedDesc,
+    DateModified,
+    DateModifiedDesc,
+    DateAccessed,
+    DateAccessedDesc,
+    Attributes,
+    AttributesDesc,
+    FileListFilename,
+    FileListFilenameDesc,
+    RunCount,
+    RunCountDesc,
+    DateRun,
+    DateRunDesc,
+    DateRecentlyChanged,
+    DateRecentlyChangedDesc,
+    /// The folders (and volumes) before the files, like Everything does by default.
+    FoldersFirst,
+}
+
+impl Key {
+    /// The key of the same order as the sort of Everything, `None` for the sort by type name
+    /// (which needs the shell) and the unknown sorts.
+    pub fn from_sort_type(sort: SortType) -> Option<Key> {
+        use SortType::*;
+        let key = match sort {
+            EVERYTHING_SORT_NAME_ASCENDING => Key::Name,
+            EVERYTHING_SORT_NAME_DESCENDING => Key::NameDesc,
+            EVERYTHING_SORT_PATH_ASCENDING => Key::Path,
+            EVERYTHING_SORT_PATH_DESCENDING => Key::PathDesc,
+            EVERYTHING_SORT_SIZE_ASCENDING => Key::Size,
+            EVERYTHING_SORT_SIZE_DESCENDING => Key::SizeDesc,
+            EVERYTHING_SORT_EXTENSION_ASCENDING => Key::Extension,
+            EVERYTHING_SORT_EXTENSION_DESCENDING => Key::ExtensionDesc,
+            EVERYTHING_SORT_TYPE_NAME_ASCENDING | EVERYTHING_SORT_TYPE_NAME_DESCENDING => {
+                return None
+            }
+            EVERYTHING_SORT_DATE_CREATED_ASCENDING => Key::DateCreated,
+            EVERYTHING_SORT_DATE_CREATED_DESCENDING => Key::DateCreatedDesc,
+            EVERYTHING_SORT_DATE_MODIFIED_ASCENDING => Key::DateModified,
+            EVERYTHING_SORT_DATE_MODIFIED_DESCENDING => Key::DateModifiedDesc,
+            EVERYTHING_SORT_ATTRIBUTES_ASCENDING => Key::Attributes,
+            EVERYTHING_SORT_ATTRIBUTES_DESCENDING => Key::AttributesDesc,
+            EVERYTHING_SORT_FILE_LIST_FILENAME_ASCENDING => Key::FileListFilename,
+            EVERYTHING_SORT_FILE_LIST_FILENAME_DESCENDING => Key::FileListFilenameDesc,
+            EVERYTHING_SORT_RUN_COUNT_ASCENDING => Key::RunCount,
+            EVERYTHING_SORT_RUN_COUNT_DESCENDING => Key::RunCountDesc,
+            EVERYTHING_SORT_DATE_RECENTLY_CHANGED_ASCENDING => Key::DateRecentlyChanged,
+            EVERYTHING_SORT_DATE_RECENTLY_CHANGED_DESCENDING => Key::DateRecentlyChangedDesc,
+            EVERYTHING_SORT_DATE_ACCESSED_ASCENDING => Key::DateAccessed,
+            EVERYTHING_SORT_DATE_ACCESSED_DESCENDING => Key::DateAccessedDesc,
+            EVERYTHING_SORT_DATE_RUN_ASCENDING => Key::DateRun,
+            EVERYTHING_SORT_DATE_RUN_DESCENDING => Key::DateRunDesc,
+            Unknown(_) => return None,
+        };
+        Some(key)
+    }
+
+    /// The request flags of the data compared by this key, empty for [`Key::FoldersFirst`].
+    pub fn request_flags(&self) -> RequestFlags {
+        match self {
+            Key::Name | Key::NameDesc | Key::Extension | Key::ExtensionDesc => {
+                RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+            }
+            Key::Path | Key::PathDesc => {
+                RequestFlags::EVERYTHING_REQUEST_FILE_NAME | RequestFlags::EVERYTHING_REQUEST_PATH
+            }
+            Key::Size | Key::SizeDesc => RequestFlags::EVERYTHING_REQUEST_SIZE,
+            Key::DateCreated | Key::DateCreatedDesc => {
+                RequestFlags::EVERYTHING_REQUEST_DATE_CREATED
+            }
+            Key::DateModified | Key::DateModifiedDesc => {
+                RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED
+            }
+            Key::DateAccessed | Key::DateAccessedDesc => {
+                RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED
+            }
+            Key::Attributes | Key::AttributesDesc => RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+            Key::FileListFilename | Key::FileListFilenameDesc => {
+                RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME
+            }
+            Key::RunCount | Key::RunCountDesc => RequestFlags::EVERYTHING_REQUEST_RUN_COUNT,
+            Key::DateRun | Key::DateRunDesc => RequestFlags::EVERYTHING_REQUEST_DATE_RUN,
+            Key::DateRecentlyChanged | Key::DateRecentlyChangedDesc => {
+                RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED
+            }
+            Key::FoldersFirst => RequestFlags::empty(),
+        }
+    }
+
+    pub fn is_desc(&self) -> bool {
+        matches!(
+            self,
+            Key::NameDesc
+                | Key::PathDesc
+                | Key::ExtensionDesc
+                | Key::SizeDesc
+                | Key::DateCreatedDesc
+                | Key::DateModifiedDesc
+                | Key::DateAccessedDesc
+                | Key::AttributesDesc
+                | Key::FileListFilenameDesc
+                | Key::RunCountDesc
+                | Key::DateRunDesc
+                | Key::DateRecentlyChangedDesc
+        )
+    }
+
+    /// Compare two items by this key.
+    pub fn compare(&self, a: &ItemData, b: &ItemData) -> Ordering {
+        let ord = match self {
+            Key::Name | Key::NameDesc => cmp_with(&a.filename, &b.filename, cmp_text),
+            Key::Path | Key::PathDesc => cmp_with(&a.path, &b.path, cmp_text).map(|ord| {
+                let by_name = cmp_with(&a.filename, &b.filename, cmp_text);
+                ord.then(by_name.unwrap_or(Ordering::Equal))
+            }),
+            Key::Extension | Key::ExtensionDesc => {
+                cmp_with(&extension_of(a), &extension_of(b), cmp_text)
+            }
+            Key::Size | Key::SizeDesc => cmp_with(&a.size, &b.size, Ord::cmp),
+            Key::DateCreated | Key::DateCreatedDesc => {
+                cmp_with(&a.date_created, &b.date_created, Ord::cmp)
+            }
+            Key::DateModified | Key::DateModifiedDesc => {
+                cmp_with(&a.date_modified, &b.date_modified, Ord::cmp)
+            }
+            Key::DateAccessed | Key::DateAccessedDesc => {
+                cmp_with(&a.date_accessed, &b.date_accessed, Ord::cmp)
+            }
+            Key::Attributes | Key::AttributesDesc => {
+                cmp_with(&a.attributes, &b.attributes, Ord::cmp)
+            }
+            Key::FileListFilename | Key::FileListFilenameDesc => {
+                cmp_with(&a.file_list_filename, &b.file_list_filename, cmp_text)
+            }
+            Key::RunCount | Key::RunCountDesc => cmp_with(&a.run_count, &b.run_count, Ord::cmp),
+            Key::DateRun | Key::DateRunDesc => cmp_with(&a.date_run, &b.date_run, Ord::cmp),
+            Key::DateRecentlyChanged | Key::DateRecentlyChangedDesc => {
+                cmp_with(&a.date_recently_changed, &b.date_recently_changed, Ord::cmp)
+            }
+            Key::FoldersFirst => return b.is_folder().cmp(&a.is_folder()),
+        };
+        match ord {
+            Ok(ord) if self.is_desc() => ord.reverse(),
+            Ok(ord) => ord,
+            Err(missing) => missing,
+        }
+    }
+}
+
+/// Compare the values by `cmp`, or `Err(_)` to place the missing ones at the end regardless of
+/// the order direction.
+fn cmp_with<T>(
+    a: &Option<T>,
+    b: &Option<T>,
+    cmp: impl FnOnce(&T, &T) -> Ordering,
+) -> Result<Ordering, Ordering> {
+    match (a, b) {
+        (Some(a), Some(b)) => Ok(cmp(a, b)),
+        (Some(_), None) => Err(Ordering::Less),
+        (None, Some(_)) => Err(Ordering::Greater),
+        (None, None) => Err(Ordering::Equal),
+    }
+}
+
+/// Compare the text case-insensitively, so `"TXT"` and `"txt"` are equal and left to the next key.
+fn cmp_text<T: AsRef<OsStr>>(a: &T, b: &T) -> Ordering {
+    let (a, b) = (a.as_ref().to_string_lossy(), b.as_ref().to_string_lossy());
+    lowercase(&a).cmp(lowercase(&b))
+}
+
+fn lowercase(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().flat_map(char::to_lowercase)
+}
+
+/// The extension of the item, empty for none and for the folders (even with a dot in the name),
+/// `None` if neither the extension nor the file name is available.
+fn extension_of(data: &ItemData) -> Option<&OsStr> {
+    match (&data.extension, &data.filename) {
+        (None, None) => None,
+        _ if data.is_folder() => Some(OsStr::new("")),
+        (Some(extension), _) => Some(extension),
+        (None, Some(filename)) => Some(Path::new(filename).extension().unwrap_or_default()),
+    }
+}
+
+/// The parent folder of the item, by [`ItemData::path`] or the parent of
+/// [`ItemData::full_path`], `None` if neither is available.
+fn parent_of(data: &ItemData) -> Option<&Path> {
+    match (&data.path, &data.full_path) {
+        (Some(path), _) => Some(path),
+        (None, Some(full_path)) => Some(full_path.parent().unwrap_or(Path::new(""))),
+        (None, None) => None,
+    }
+}
+
+fn group_by_parent(items: impl Iterator<Item = ItemData>) -> HashMap<PathBuf, Vec<ItemData>> {
+    let mut groups: HashMap<PathBuf, Vec<ItemData>> = HashMap::new();
+    for data in items {
+        let Some(parent) = parent_of(&data) else {
+            continue;
+        };
+        // Each parent path is allocated only once for its group.
+        match groups.get_mut(parent) {
+            Some(group) => group.push(data),
+            None => {
+                let parent = parent.to_path_buf();
+                groups.insert(parent, vec![data]);
+            }
+        }
+    }
+    groups
+}
+
+fn group_by_extension(items: impl Iterator<Item = ItemData>) -> HashMap<OsString, Vec<ItemData>> {
+    let mut groups: HashMap<OsString, Vec<ItemData>> = HashMap::new();
+    for data in items {
+        let Some(extension) = extension_of(&data) else {
+            continue;
+        };
+        let mut extension = extension.to_os_string();
+        extension.make_ascii_lowercase();
+        groups.entry(extension).or_default().push(data);
+    }
+    groups
+}
+
+impl<'a> IntoIterator for &'a OwnedResults {
+    type Item = &'a ItemData;
+    type IntoIter = std::slice::Iter<'a, ItemData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl IntoIterator for OwnedResults {
+    type Item = ItemData;
+    type IntoIter = std::vec::IntoIter<ItemData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+fn item_data_of(item: ReplyItem, index: u32, request_flags: RequestFlags) -> ItemData {
+    let kind = if item.is_volume {
+        ItemKind::Volume
+    } else if item.is_folder {
+        ItemKind::Folder
+    } else {
+        ItemKind::File
+    };
+    let full_path = match (&item.full_path_and_filename, &item.path, &item.filename) {
+        (Some(full_path), _, _) => Some(PathBuf::from(full_path)),
+        (None, Some(path), Some(filename)) if path.is_empty() => Some(PathBuf::from(filename)),
+        (None, Some(path), Some(filename)) => Some(Path::new(path).join(filename)),
+        _ => None,
+    };
+    ItemData {
+        index,
+        kind,
+        request_flags,
+        filename: item.filename,
+        path: item.path.map(PathBuf::from),
+        full_path,
+        extension: item.extension,
+        size: item.size,
+        date_created: item.date_created,
+        date_modified: item.date_modified,
+        date_accessed: item.date_accessed,
+        attributes: item.attributes,
+        file_list_filename: item.file_list_filename,
+        run_count: item.run_count,
+        date_run: item.date_run,
+        date_recently_changed: item.date_recently_changed,
+        highlighted_filename: item.highlighted_filename,
+        highlighted_path: item.highlighted_path,
+        highlighted_full_path_and_filename: item.highlighted_full_path_and_filename,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: ItemKind, full_path: &str) -> ItemData {
+        let full_path = PathBuf::from(full_path);
+        ItemData {
+            kind,
+            filename: full_path.file_name().map(OsStr::to_os_string),
+            path: full_path.parent().map(Path::to_path_buf),
+            full_path: Some(full_path),
+            ..Default::default()
+        }
+    }
+
+    fn results(items: Vec<ItemData>) -> OwnedResults {
+        OwnedResults::from_parts(
+            items,
+            RequestFlags::EVERYTHING_REQUEST_FILE_NAME | RequestFlags::EVERYTHING_REQUEST_PATH,
+            SortType::default(),
+            0,
+            SearchOptions::new(""),
+        )
+    }
+
+    fn names(items: &[ItemData]) -> Vec<&OsStr> {
+        items
+            .iter()
+            .filter_map(|data| data.filename.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn group_by_extension_in_lowercase_and_folders_have_none() {
+        let groups = results(vec![
+            item(ItemKind::File, r"C:\a.TXT"),
+            item(ItemKind::Folder, r"C:\notes.txt"),
+            item(ItemKind::File, r"C:\b.txt"),
+            item(ItemKind::File, r"C:\Makefile"),
+            ItemData::default(),
+        ])
+        .group_by_extension();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(names(&groups[OsStr::new("txt")]), ["a.TXT", "b.txt"]);
+        assert_eq!(names(&groups[OsStr::new("")]), ["notes.txt", "Makefile"]);
+    }
+
+    #[test]
+    fn group_by_extension_agrees_with_the_extension_column() {
+        let mut file = item(ItemKind::File, r"C:\a.tar.GZ");
+        file.extension = Some("GZ".into());
+        let mut folder = item(ItemKind::Folder, r"C:\x.d");
+        folder.extension = Some("d".into());
+        let groups = results(vec![file, folder]).group_by_extension();
+        assert_eq!(names(&groups[OsStr::new("gz")]), ["a.tar.GZ"]);
+        assert_eq!(names(&groups[OsStr::new("")]), ["x.d"]);
+    }
+
+    #[test]
+    fn group_by_parent_by_path_or_full_path() {
+        let mut only_full_path = item(ItemKind::File, r"D:\data\c.bin");
+        only_full_path.path = None;
+        let groups = results(vec![
+            item(ItemKind::File, r"D:\data\a.bin"),
+            item(ItemKind::Folder, r"D:\data\b"),
+            only_full_path,
+            item(ItemKind::File, r"D:\d.bin"),
+            ItemData::default(),
+        ])
+        .group_by_parent();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            names(&groups[Path::new(r"D:\data")]),
+            ["a.bin", "b", "c.bin"]
+        );
+        assert_eq!(names(&groups[Path::new(r"D:\")]), ["d.bin"]);
+    }
+}
@@ -0,0 +1,249 @@
+//! Save the results into a SQLite table and load them back, for the offline analysis in SQL
+//! without querying Everything again. (feature `sqlite`)
+//!
+//! The table has the columns of `id` (the index in the results), `kind` (`file`, `folder` or
+//! `volume`), `full_path`, `path`, `name`, `ext`, `size`, `date_created`, `date_modified`,
+//! `date_accessed`, `attributes`, `file_list_filename`, `run_count`, `date_run` and
+//! `date_recently_changed`, which are `NULL` if not requested. The names and the paths are
+//! converted to UTF-8 lossily.
+//!
+//! The dates are the raw FILETIME numbers (`-1` if unknown), which are converted in SQL by
+//! `datetime(date_modified / 10000000 - 11644473600, 'unixepoch')`.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut options = SearchOptions::new(r"C:\Users\ size:>100mb");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+//!     | RequestFlags::EVERYTHING_REQUEST_SIZE
+//!     | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED;
+//! let results = spawn_query(options).join().unwrap()?;
+//! results.write_sqlite("index.db", "large_files").unwrap();
+//! // sqlite3 index.db "SELECT ext, sum(size) FROM large_files GROUP BY ext"
+//! # Ok::<(), EverythingError>(())
+//! ```
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, Row};
+
+use super::{ItemData, ItemKind, OwnedResults, RequestFlags, SearchOptions, SortType};
+
+/// The columns to select, in the order of [`ItemData::from_row`].
+const COLUMNS: &str = "id, kind, full_path, path, name, ext, size, date_created, \
+    date_modified, date_accessed, attributes, file_list_filename, run_count, date_run, \
+    date_recently_changed";
+
+/// The columns indexed for the common filters and sorts.
+const INDEXED: [&str; 5] = ["path", "name", "ext", "size", "date_modified"];
+
+/// Quote the identifier for SQL, so any table name is fine.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn text(s: Option<impl AsRef<OsStr>>) -> Option<String> {
+    s.map(|s| s.as_ref().to_string_lossy().into_owned())
+}
+
+/// The u64 numbers are stored bit by bit in the i64 of SQLite, so `u64::MAX` is `-1`.
+fn to_sql(n: Option<u64>) -> Option<i64> {
+    n.map(|n| n as i64)
+}
+
+fn from_sql(n: Option<i64>) -> Option<u64> {
+    n.map(|n| n as u64)
+}
+
+impl OwnedResults {
+    /// Write the items into the `table` of the SQLite database at `path`, which is created if
+    /// not exists, and return the number of the rows.
+    ///
+    /// The `table` is replaced if it exists, in one transaction.
+    pub fn write_sqlite(&self, path: impl AsRef<Path>, table: &str) -> rusqlite::Result<usize> {
+        let mut connection = Connection::open(path)?;
+        self.write_sqlite_to(&mut connection, table)
+    }
+
+    /// Write the items into the `table` of the opened `connection`, see
+    /// [`OwnedResults::write_sqlite`].
+    pub fn write_sqlite_to(
+        &self,
+        connection: &mut Connection,
+        table: &str,
+    ) -> rusqlite::Result<usize> {
+        let transaction = connection.transaction()?;
+        let quoted = quote(table);
+        transaction.execute_batch(&format!(
+            "DROP TABLE IF EXISTS {quoted};
+            CREATE TABLE {quoted} (
+                id INTEGER PRIMARY KEY,
+                kind TEXT NOT NULL,
+                full_path TEXT,
+                path TEXT,
+                name TEXT,
+                ext TEXT,
+                size INTEGER,
+                date_created INTEGER,
+                date_modified INTEGER,
+                date_accessed INTEGER,
+                attributes INTEGER,
+                file_list_filename TEXT,
+                run_count INTEGER,
+                date_run INTEGER,
+                date_recently_changed INTEGER
+            );"
+        ))?;
+        for column in INDEXED {
+            let index = quote(&format!("{table}_{column}"));
+            transaction.execute_batch(&format!("CREATE INDEX {index} ON {quoted} ({column});"))?;
+        }
+        {
+            let mut insert = transaction.prepare(&format!(
+                "INSERT INTO {quoted} ({COLUMNS}) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+            ))?;
+            for data in &self.items {
+                let kind = match data.kind {
+                    ItemKind::File => "file",
+                    ItemKind::Folder => "folder",
+                    ItemKind::Volume => "volume",
+                };
+                insert.execute(params![
+                    data.index,
+                    kind,
+                    text(data.full_path.as_ref()),
+                    text(data.path.as_ref()),
+                    text(data.filename.as_ref()),
+                    text(data.extension.as_ref()),
+                    to_sql(data.size),
+                    to_sql(data.date_created),
+                    to_sql(data.date_modified),
+                    to_sql(data.date_accessed),
+                    data.attributes,
+                    text(data.file_list_filename.as_ref()),
+                    data.run_count,
+                    to_sql(data.date_run),
+                    to_sql(data.date_recently_changed),
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(self.items.len())
+    }
+
+    /// Load the results written by [`OwnedResults::write_sqlite`] back, in the order of `id`.
+    ///
+    /// The request flags are of the columns not all `NULL`, and the options of the query are
+    /// not saved, so they are the defaults.
+    pub fn read_sqlite(path: impl AsRef<Path>, table: &str) -> rusqlite::Result<OwnedResults> {
+        let connection = Connection::open(path)?;
+        let mut select = connection.prepare(&format!(
+            "SELECT {COLUMNS} FROM {} ORDER BY id",
+            quote(table)
+        ))?;
+        let mut items = select
+            .query_map([], ItemData::from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let request_flags = items.iter().fold(RequestFlags::empty(), |flags, data| {
+            flags | data.request_flags
+        });
+        for data in &mut items {
+            data.request_flags = request_flags;
+        }
+        let total = items.len() as u32;
+        Ok(OwnedResults::from_parts(
+            items,
+            request_flags,
+            SortType::default(),
+            total,
+            SearchOptions::default(),
+        ))
+    }
+}
+
+impl ItemData {
+    /// The item of a row of [`COLUMNS`], with the request flags of the columns not `NULL`.
+    fn from_row(row: &Row<'_>) -> rusqlite::Result<ItemData> {
+        let kind = match row.get::<_, String>(1)?.as_str() {
+            "folder" => ItemKind::Folder,
+            "volume" => ItemKind::Volume,
+            _ => ItemKind::File,
+        };
+        let full_path = row.get::<_, Option<String>>(2)?.map(PathBuf::from);
+        let path = row.get::<_, Option<String>>(3)?.map(PathBuf::from);
+        let filename = row.get::<_, Option<String>>(4)?.map(OsString::from);
+        let mut data = ItemData {
+            index: row.get(0)?,
+            kind,
+            full_path,
+            path,
+            filename,
+            extension: row.get::<_, Option<String>>(5)?.map(OsString::from),
+            size: from_sql(row.get(6)?),
+            date_created: from_sql(row.get(7)?),
+            date_modified: from_sql(row.get(8)?),
+            date_accessed: from_sql(row.get(9)?),
+            attributes: row.get(10)?,
+            file_list_filename: row.get::<_, Option<String>>(11)?.map(OsString::from),
+            run_count: row.get(12)?,
+            date_run: from_sql(row.get(13)?),
+            date_recently_changed: from_sql(row.get(14)?),
+            ..Default::default()
+        };
+        let flags = [
+            (
+                data.full_path.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+            ),
+            (data.path.is_some(), RequestFlags::EVERYTHING_REQUEST_PATH),
+            (
+                data.filename.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            ),
+            (
+                data.extension.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_EXTENSION,
+            ),
+            (data.size.is_some(), RequestFlags::EVERYTHING_REQUEST_SIZE),
+            (
+                data.date_created.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_DATE_CREATED,
+            ),
+            (
+                data.date_modified.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED,
+            ),
+            (
+                data.date_accessed.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED,
+            ),
+            (
+                data.attributes.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES,
+            ),
+            (
+                data.file_list_filename.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME,
+            ),
+            (
+                data.run_count.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_RUN_COUNT,
+            ),
+            (
+                data.date_run.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_DATE_RUN,
+            ),
+            (
+                data.date_recently_changed.is_some(),
+                RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED,
+            ),
+        ];
+        data.request_flags = flags
+            .into_iter()
+            .filter(|(available, _)| *available)
+            .fold(RequestFlags::empty(), |all, (_, flag)| all | flag);
+        Ok(data)
+    }
+}
@@ -0,0 +1,203 @@
+//! Statistics over the snapshots, for the disk usage dashboards.
+//!
+//! The size statistics count the files only, as the size of a folder (if indexed) is the sum
+//! of the files in it, which are usually in the results too.
+//!
+//! ```no_run
+//! use everything_sdk::*;
+//!
+//! let mut options = SearchOptions::new("D:\\");
+//! options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+//!     | RequestFlags::EVERYTHING_REQUEST_SIZE
+//!     | RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED
+//!     | RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES;
+//! let results = spawn_query(options).join().unwrap().unwrap();
+//! let stats = stats::Stats::of(&results, 10);
+//! for bucket in &stats.sizes {
+//!     println!("{:?}: {} files, {} bytes", bucket.group, bucket.count, bucket.size);
+//! }
+//! for data in &stats.largest {
+//!     println!("{:?} {:?}", data.size, data.full_path);
+//! }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+use chrono::Datelike;
+
+use super::{FileTime, ItemData, OwnedResults};
+
+/// The size groups of the `size:` search in Everything, like `size:huge`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum SizeGroup {
+    /// 0 bytes.
+    Empty,
+    /// Up to 10 KB.
+    Tiny,
+    /// 10 KB to 100 KB.
+    Small,
+    /// 100 KB to 1 MB.
+    Medium,
+    /// 1 MB to 16 MB.
+    Large,
+    /// 16 MB to 128 MB.
+    Huge,
+    /// Over 128 MB.
+    Gigantic,
+}
+
+impl SizeGroup {
+    pub const ALL: [SizeGroup; 7] = [
+        SizeGroup::Empty,
+        SizeGroup::Tiny,
+        SizeGroup::Small,
+        SizeGroup::Medium,
+        SizeGroup::Large,
+        SizeGroup::Huge,
+        SizeGroup::Gigantic,
+    ];
+
+    pub fn of(size: u64) -> Self {
+        const KB: u64 = 1 << 10;
+        const MB: u64 = 1 << 20;
+        match size {
+            0 => SizeGroup::Empty,
+            _ if size <= 10 * KB => SizeGroup::Tiny,
+            _ if size <= 100 * KB => SizeGroup::Small,
+            _ if size <= MB => SizeGroup::Medium,
+            _ if size <= 16 * MB => SizeGroup::Large,
+            _ if size <= 128 * MB => SizeGroup::Huge,
+            _ => SizeGroup::Gigantic,
+        }
+    }
+}
+
+/// The number and the total size of the files.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Count {
+    pub count: u64,
+    pub size: u64,
+}
+
+impl Count {
+    fn add(&mut self, size: u64) {
+        self.count += 1;
+        self.size += size;
+    }
+}
+
+/// A bucket of [`size_histogram`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizeBucket {
+    pub group: SizeGroup,
+    pub count: u64,
+    pub size: u64,
+}
+
+/// The files with the size, in the size groups of Everything, from empty to gigantic (the
+/// empty groups included).
+pub fn size_histogram(results: &OwnedResults) -> Vec<SizeBucket> {
+    let mut counts = [Count::default(); SizeGroup::ALL.len()];
+    for size in files(results).filter_map(|data| data.size) {
+        counts[SizeGroup::of(size) as usize].add(size);
+    }
+    SizeGroup::ALL
+        .into_iter()
+        .zip(counts)
+        .map(|(group, Count { count, size })| SizeBucket { group, count, size })
+        .collect()
+}
+
+/// The files by their extensions in lowercase (empty for none), by
+/// [`ItemData::extension`] or the extension of [`ItemData::filename`].
+///
+/// The files without the size are counted, with the size of zero.
+pub fn by_extension(results: &OwnedResults) -> HashMap<OsString, Count> {
+    let mut counts: HashMap<OsString, Count> = HashMap::new();
+    for data in files(results) {
+        let mut extension = match (&data.extension, &data.filename) {
+            (Some(extension), _) => extension.clone(),
+            (None, Some(filename)) => Path::new(filename)
+                .extension()
+                .map(OsStr::to_os_string)
+                .unwrap_or_default(),
+            (None, None) => continue,
+        };
+        extension.make_ascii_lowercase();
+        counts
+            .entry(extension)
+            .or_default()
+            .add(data.size.unwrap_or_default());
+    }
+    counts
+}
+
+/// The files by the year (in local time) of the date modified, the years without any file are
+/// not included.
+pub fn modified_by_year(results: &OwnedResults) -> BTreeMap<i32, Count> {
+    let mut counts: BTreeMap<i32, Count> = BTreeMap::new();
+    for data in files(results) {
+        let Some(time) = data
+            .date_modified
+            .and_then(|date| FileTime::new(date).to_system_time())
+        else {
+            continue;
+        };
+        let year = chrono::DateTime::<chrono::Local>::from(time).year();
+        counts
+            .entry(year)
+            .or_default()
+            .add(data.size.unwrap_or_default());
+    }
+    counts
+}
+
+/// The `n` largest files, the largest first.
+pub fn largest(results: &OwnedResults, n: usize) -> Vec<ItemData> {
+    let mut sized: Vec<_> = files(results).filter(|data| data.size.is_some()).collect();
+    // Stable, so the files of the same size keep the order of the results.
+    sized.sort_by_key(|data| Reverse(data.size));
+    sized.into_iter().take(n).cloned().collect()
+}
+
+/// All the statistics at once.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// All the files, and the total size of the ones with the size.
+    pub files: Count,
+    pub folders: u64,
+    pub sizes: Vec<SizeBucket>,
+    pub extensions: HashMap<OsString, Count>,
+    pub modified_years: BTreeMap<i32, Count>,
+    /// The largest files, the largest first.
+    pub largest: Vec<ItemData>,
+}
+
+impl Stats {
+    /// The statistics of the `results`, with the `largest_n` largest files.
+    pub fn of(results: &OwnedResults, largest_n: usize) -> Self {
+        let mut files = Count::default();
+        for data in self::files(results) {
+            files.add(data.size.unwrap_or_default());
+        }
+        Stats {
+            files,
+            folders: results.iter().filter(|data| data.is_folder()).count() as u64,
+            sizes: size_histogram(results),
+            extensions: by_extension(results),
+            modified_years: modified_by_year(results),
+            largest: largest(results, largest_n),
+        }
+    }
+}
+
+fn files(results: &OwnedResults) -> impl Iterator<Item = &ItemData> {
+    results.iter().filter(|data| data.is_file())
+}
@@ -0,0 +1,136 @@
+//! Query in chunks by the offset and the max, instead of one massive IPC reply.
+//!
+//! A heavy search like `"a"` with `set_max(u32::MAX)` (see `examples/heavy.rs`) makes
+//! Everything copy all the millions of results in one reply, and the SDK holds all of them at
+//! once. [`EverythingSearcher::query_streamed`] re-issues the query page by page and yields
+//! each page as the owned items, so the memory is bounded by the chunk size.
+//!
+//! Each chunk is a new query, the files changed between the chunks may be missed or yielded
+//! twice. The offset and the max of the searcher are left at the ones of the last chunk.
+
+use super::{EverythingResults, EverythingSearcher, ItemData};
+
+/// The paging state shared by the sync and the async versions.
+struct Pager {
+    chunk_size: u32,
+    /// The offset set by the user, where the first chunk starts.
+    start: u32,
+    next: u32,
+    /// The end by the max set by the user.
+    end: u32,
+    done: bool,
+}
+
+impl Pager {
+    fn new(searcher: &EverythingSearcher<'_>, chunk_size: u32) -> Self {
+        let start = searcher.get_offset();
+        Pager {
+            chunk_size: chunk_size.max(1),
+            start,
+            next: start,
+            end: start.saturating_add(searcher.get_max()),
+            done: false,
+        }
+    }
+
+    /// Set the offset and the max for the next chunk, `false` if no more.
+    fn prepare(&self, searcher: &mut EverythingSearcher<'_>) -> bool {
+        if self.done || self.next >= self.end {
+            return false;
+        }
+        searcher
+            .set_offset(self.next)
+            .set_max(self.chunk_size.min(self.end - self.next));
+        true
+    }
+
+    /// Copy the chunk out, with the index counted from the first chunk.
+    fn take(&mut self, results: &EverythingResults<'_>) -> Option<Vec<ItemData>> {
+        let mut items = Vec::new();
+        results.fill_data(&mut items);
+        if items.is_empty() {
+            self.done = true;
+            return None;
+        }
+        let base = self.next - self.start;
+        for item in &mut items {
+            item.index += base;
+        }
+        self.next += items.len() as u32;
+        if self.next >= results.total() {
+            self.done = true;
+        }
+        Some(items)
+    }
+}
+
+/// The iterator of the chunks, see [`EverythingSearcher::query_streamed`].
+#[cfg(not(feature = "async"))]
+pub struct Chunks<'s, 'a> {
+    searcher: &'s mut EverythingSearcher<'a>,
+    pager: Pager,
+}
+
+#[cfg(not(feature = "async"))]
+impl Iterator for Chunks<'_, '_> {
+    type Item = Vec<ItemData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.pager.prepare(self.searcher) {
+            return None;
+        }
+        let results = self.searcher.query();
+        self.pager.take(&results)
+    }
+}
+
+impl<'a> EverythingSearcher<'a> {
+    /// Query in chunks of `chunk_size` items, with the current search state.
+    ///
+    /// The offset and the max of the searcher are respected, as the start and the limit of all
+    /// the chunks. The [`ItemData::index`] is counted from the first chunk.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut everything = global().lock().unwrap();
+    /// let mut searcher = everything.searcher();
+    /// searcher.set_search("a").set_max(u32::MAX);
+    /// let mut count = 0;
+    /// for chunk in searcher.query_streamed(100_000) {
+    ///     count += chunk.len();
+    /// }
+    /// println!("{count} results");
+    /// ```
+    #[cfg(not(feature = "async"))]
+    pub fn query_streamed(&mut self, chunk_size: u32) -> Chunks<'_, 'a> {
+        let pager = Pager::new(self, chunk_size);
+        Chunks {
+            searcher: self,
+            pager,
+        }
+    }
+
+    /// Query in chunks of `chunk_size` items, with the current search state.
+    ///
+    /// The offset and the max of the searcher are respected, as the start and the limit of all
+    /// the chunks. The [`ItemData::index`] is counted from the first chunk.
+    #[cfg(feature = "async")]
+    pub fn query_streamed(
+        &mut self,
+        chunk_size: u32,
+    ) -> futures::stream::LocalBoxStream<'_, Vec<ItemData>> {
+        use futures::StreamExt;
+
+        let pager = Pager::new(self, chunk_size);
+        futures::stream::unfold((self, pager), |(searcher, mut pager)| async move {
+            if !pager.prepare(searcher) {
+                return None;
+            }
+            let chunk = pager.take(&searcher.query().await)?;
+            Some((chunk, (searcher, pager)))
+        })
+        .boxed_local()
+    }
+}
@@ -0,0 +1,265 @@
+//! The timestamp type of the date columns.
+
+use std::fmt::{self, Debug, Display};
+use std::time::{Duration, SystemTime};
+
+use windows::Win32::Foundation::FILETIME;
+
+use super::helper::{filetime_age, FILETIME_UNIX_EPOCH};
+use super::{EverythingItem, Result};
+
+const INTERVALS_PER_SEC: u64 = 10_000_000;
+/// The seconds between 1601-01-01 and 1970-01-01.
+const UNIX_EPOCH_SECS: i64 = (FILETIME_UNIX_EPOCH / INTERVALS_PER_SEC) as i64;
+
+/// The FILETIME as u64, that is the number of 100-nanosecond intervals since 1601-01-01 UTC,
+/// which is returned by the date getters like [`EverythingItem::date_modified`].
+///
+/// It is displayed in ISO-8601 UTC, such as `2024-01-02T03:04:05.5Z`, so the debug prints
+/// and logs are readable without any date library.
+///
+/// [`EverythingItem::date_modified`]: super::EverythingItem::date_modified
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FileTime(u64);
+
+impl FileTime {
+    /// 1601-01-01T00:00:00Z, the epoch of FILETIME.
+    pub const ZERO: FileTime = FileTime(0);
+    /// 1970-01-01T00:00:00Z, the UNIX epoch.
+    pub const UNIX_EPOCH: FileTime = FileTime(FILETIME_UNIX_EPOCH);
+
+    pub const fn new(filetime: u64) -> Self {
+        FileTime(filetime)
+    }
+
+    /// The raw u64 value, in 100-nanosecond intervals since 1601-01-01 UTC.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// The time elapsed since this time until now, zero if it is in the future.
+    pub fn age(self) -> Duration {
+        filetime_age(self.0)
+    }
+
+    /// The seconds since 1970-01-01 UTC (negative if before it), rounded down to the second.
+    pub const fn to_unix_secs(self) -> i64 {
+        // never overflow, the u64::MAX intervals is less than 2^41 seconds.
+        (self.0 / INTERVALS_PER_SEC) as i64 - UNIX_EPOCH_SECS
+    }
+
+    /// It is `None` if the time is before 1601-01-01 UTC or too far in the future.
+    pub const fn from_unix_secs(secs: i64) -> Option<Self> {
+        match secs.checked_add(UNIX_EPOCH_SECS) {
+            Some(secs) if secs >= 0 => match (secs as u64).checked_mul(INTERVALS_PER_SEC) {
+                Some(intervals) => Some(FileTime(intervals)),
+                None => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The nanoseconds since 1970-01-01 UTC (negative if before it).
+    pub const fn to_unix_nanos(self) -> i128 {
+        (self.0 as i128 - FILETIME_UNIX_EPOCH as i128) * 100
+    }
+
+    /// It is `None` if out of range, and the precision less than 100 nanoseconds is truncated
+    /// (rounded down).
+    pub fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        let intervals = nanos.div_euclid(100) + FILETIME_UNIX_EPOCH as i128;
+        u64::try_from(intervals).ok().map(FileTime)
+    }
+
+    /// It is `None` if the time is out of the range of [`SystemTime`].
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        let since_unix_epoch = |intervals: u64| Duration::from_nanos(intervals.saturating_mul(100));
+        match self.0.checked_sub(FILETIME_UNIX_EPOCH) {
+            Some(after) => SystemTime::UNIX_EPOCH.checked_add(since_unix_epoch(after)),
+            None => {
+                SystemTime::UNIX_EPOCH.checked_sub(since_unix_epoch(FILETIME_UNIX_EPOCH - self.0))
+            }
+        }
+    }
+
+    /// It is `None` if the time is out of the range of [`chrono::DateTime`].
+    pub fn to_chrono(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let nanos = (self.0 % INTERVALS_PER_SEC * 100) as u32;
+        chrono::DateTime::from_timestamp(self.to_unix_secs(), nanos)
+    }
+
+    /// It is `None` if the time is out of the range of [`time::OffsetDateTime`]. (feature
+    /// `time`)
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(self) -> Option<::time::OffsetDateTime> {
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(self.to_unix_nanos()).ok()
+    }
+
+    /// It is `None` if the time is out of the range of [`jiff::Timestamp`]. (feature `jiff`)
+    #[cfg(feature = "jiff")]
+    pub fn to_jiff(self) -> Option<jiff::Timestamp> {
+        jiff::Timestamp::from_nanosecond(self.to_unix_nanos()).ok()
+    }
+}
+
+/// The date is unknown if it is `u64::MAX` (-1 in FILETIME), like the date run of a file
+/// never run.
+fn known(filetime: u64) -> Option<FileTime> {
+    (filetime != u64::MAX).then_some(FileTime(filetime))
+}
+
+/// The date getters typed, `Ok(None)` if the date is unknown or out of the range.
+///
+/// For the date libraries, convert by [`FileTime`] like
+/// `FileTime::from(item.date_modified()?).to_chrono()`.
+impl EverythingItem<'_> {
+    pub fn date_created_systemtime(&self) -> Result<Option<SystemTime>> {
+        Ok(known(self.date_created()?).and_then(FileTime::to_system_time))
+    }
+
+    pub fn date_modified_systemtime(&self) -> Result<Option<SystemTime>> {
+        Ok(known(self.date_modified()?).and_then(FileTime::to_system_time))
+    }
+
+    pub fn date_accessed_systemtime(&self) -> Result<Option<SystemTime>> {
+        Ok(known(self.date_accessed()?).and_then(FileTime::to_system_time))
+    }
+
+    pub fn date_run_systemtime(&self) -> Result<Option<SystemTime>> {
+        Ok(known(self.date_run()?).and_then(FileTime::to_system_time))
+    }
+
+    pub fn date_recently_changed_systemtime(&self) -> Result<Option<SystemTime>> {
+        Ok(known(self.date_recently_changed()?).and_then(FileTime::to_system_time))
+    }
+}
+
+impl From<u64> for FileTime {
+    fn from(filetime: u64) -> Self {
+        FileTime(filetime)
+    }
+}
+
+impl From<FileTime> for u64 {
+    fn from(filetime: FileTime) -> Self {
+        filetime.0
+    }
+}
+
+impl From<FILETIME> for FileTime {
+    fn from(filetime: FILETIME) -> Self {
+        FileTime(((filetime.dwHighDateTime as u64) << 32) | filetime.dwLowDateTime as u64)
+    }
+}
+
+impl From<FileTime> for FILETIME {
+    fn from(filetime: FileTime) -> Self {
+        FILETIME {
+            dwLowDateTime: filetime.0 as u32,
+            dwHighDateTime: (filetime.0 >> 32) as u32,
+        }
+    }
+}
+
+/// Saturating to [`FileTime::ZERO`] if it is before 1601.
+impl From<SystemTime> for FileTime {
+    fn from(time: SystemTime) -> Self {
+        let intervals = |d: Duration| u64::try_from(d.as_nanos() / 100).unwrap_or(u64::MAX);
+        match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => FileTime(FILETIME_UNIX_EPOCH.saturating_add(intervals(after))),
+            Err(before) => {
+                FileTime(FILETIME_UNIX_EPOCH.saturating_sub(intervals(before.duration())))
+            }
+        }
+    }
+}
+
+impl Display for FileTime {
+    /// ISO-8601 in UTC, the fraction of second (up to 7 digits) is shown only if not zero.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0 / INTERVALS_PER_SEC;
+        let fraction = self.0 % INTERVALS_PER_SEC;
+        let (days, secs_of_day) = (secs / 86400, secs % 86400);
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )?;
+        if fraction != 0 {
+            let digits = format!("{fraction:07}");
+            write!(f, ".{}", digits.trim_end_matches('0'))?;
+        }
+        f.write_str("Z")
+    }
+}
+
+impl Debug for FileTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FileTime({self})")
+    }
+}
+
+/// The (year, month, day) of the `days` since 1601-01-01 in the proleptic Gregorian calendar.
+///
+/// (Ref: <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>, but the 1601 is
+/// the first year of a 400-year cycle, so we can start from it without the negative days)
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // shift the epoch to 1600-03-01, so the leap day is the last day of a "year".
+    let days = days + 306;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = 1600 + era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVALS_PER_DAY: u64 = 86400 * INTERVALS_PER_SEC;
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_from_days(0), (1601, 1, 1));
+        assert_eq!(civil_from_days(134_774), (1970, 1, 1));
+        assert_eq!(civil_from_days(145_790), (2000, 2, 29));
+        assert_eq!(civil_from_days(182_314), (2100, 2, 28));
+        assert_eq!(civil_from_days(182_315), (2100, 3, 1));
+    }
+
+    #[test]
+    fn display_dates() {
+        assert_eq!(FileTime::ZERO.to_string(), "1601-01-01T00:00:00Z");
+        assert_eq!(FileTime::UNIX_EPOCH.to_string(), "1970-01-01T00:00:00Z");
+        let leap_day = FileTime::new(145_790 * INTERVALS_PER_DAY + 3_723 * INTERVALS_PER_SEC);
+        assert_eq!(leap_day.to_string(), "2000-02-29T01:02:03Z");
+        let not_leap = FileTime::new(182_315 * INTERVALS_PER_DAY - 1);
+        assert_eq!(not_leap.to_string(), "2100-02-28T23:59:59.9999999Z");
+        assert_eq!(
+            FileTime::new(182_315 * INTERVALS_PER_DAY).to_string(),
+            "2100-03-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn display_trims_the_fraction() {
+        let at = |fraction: u64| FileTime::new(FILETIME_UNIX_EPOCH + fraction).to_string();
+        assert_eq!(at(5_000_000), "1970-01-01T00:00:00.5Z");
+        assert_eq!(at(1_230_000), "1970-01-01T00:00:00.123Z");
+        assert_eq!(at(1), "1970-01-01T00:00:00.0000001Z");
+        assert_eq!(at(10_000_000), "1970-01-01T00:00:01Z");
+    }
+}
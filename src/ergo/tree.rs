@@ -0,0 +1,252 @@
+//! The directory tree built from the flat results, for tree views and ncdu-style size explorers.
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf};
+
+use super::{ItemData, OwnedResults};
+
+/// The id of a [`Node`] in its [`Tree`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NodeId(usize);
+
+/// The directory tree of the items, see [`OwnedResults::to_tree`] and
+/// [`OwnedResults::into_tree`].
+///
+/// The nodes are stored in a flat arena and refer to each other by [`NodeId`], the parent
+/// always comes before its children.
+#[derive(Clone, Debug, Default)]
+pub struct Tree {
+    nodes: Vec<Node>,
+    roots: Vec<NodeId>,
+    index: HashMap<PathBuf, NodeId>,
+}
+
+/// A file or folder in the [`Tree`].
+///
+/// The folders between the roots and the items are filled in even if they are not in the
+/// results, and they have no [`Node::data`].
+#[derive(Clone, Debug)]
+pub struct Node {
+    name: OsString,
+    path: PathBuf,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    data: Option<ItemData>,
+    size: u64,
+    files: u32,
+    folders: u32,
+}
+
+impl OwnedResults {
+    /// Build the directory tree of the results, with the sizes and counts aggregated.
+    ///
+    /// It needs the full path of the items (`EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`, or
+    /// both of `EVERYTHING_REQUEST_PATH` and `EVERYTHING_REQUEST_FILE_NAME`), the items
+    /// without it are skipped. And request `EVERYTHING_REQUEST_SIZE` for the sizes.
+    pub fn to_tree(&self) -> Tree {
+        self.iter().collect()
+    }
+
+    /// Like [`OwnedResults::to_tree`], but move the items into the nodes rather than cloning
+    /// them, for the large results.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::*;
+    ///
+    /// let mut options = SearchOptions::new(r"C:\Users\");
+    /// options.request_flags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+    ///     | RequestFlags::EVERYTHING_REQUEST_SIZE;
+    /// let tree = spawn_query(options).join().unwrap()?.into_tree();
+    /// for &root in tree.roots() {
+    ///     let node = tree.node(root);
+    ///     println!("{}: {} bytes in {} files", node.path().display(), node.size(), node.files());
+    /// }
+    /// # Ok::<(), EverythingError>(())
+    /// ```
+    pub fn into_tree(self) -> Tree {
+        self.into_items().into_iter().collect()
+    }
+}
+
+impl Tree {
+    /// The number of all the nodes, including the filled-in folders.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The top nodes, like `C:\` and `\\server\share\`.
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    /// Find the node by the path, which should be in the same form as the full path from
+    /// Everything, e.g. `C:\Windows` rather than `c:/windows/`.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<NodeId> {
+        self.index.get(path.as_ref()).copied()
+    }
+
+    /// All the nodes, the parents before their children.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &Node)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (NodeId(i), node))
+    }
+
+    /// Get the node of `path`, and create it (and its missing ancestors) if not exists.
+    fn get_or_insert(&mut self, path: &Path) -> Option<NodeId> {
+        if let Some(id) = self.get(path) {
+            return Some(id);
+        }
+        let mut components = path.components();
+        let mut current = match components.next()? {
+            Component::Prefix(prefix) => {
+                // `C:\` rather than `C:`, the latter one is relative to the current directory.
+                let mut root = prefix.as_os_str().to_os_string();
+                root.push("\\");
+                self.insert_child(None, prefix.as_os_str(), root.into())
+            }
+            Component::RootDir => self.insert_child(None, OsStr::new("\\"), PathBuf::from("\\")),
+            Component::Normal(name) => self.insert_child(None, name, PathBuf::from(name)),
+            Component::CurDir | Component::ParentDir => return None,
+        };
+        for component in components {
+            match component {
+                Component::RootDir | Component::CurDir => {}
+                Component::Normal(name) => {
+                    let path = self.nodes[current.0].path.join(name);
+                    current = self.insert_child(Some(current), name, path);
+                }
+                Component::Prefix(_) | Component::ParentDir => return None,
+            }
+        }
+        Some(current)
+    }
+
+    fn insert_child(&mut self, parent: Option<NodeId>, name: &OsStr, path: PathBuf) -> NodeId {
+        if let Some(id) = self.get(&path) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len());
+        self.index.insert(path.clone(), id);
+        self.nodes.push(Node {
+            name: name.to_os_string(),
+            path,
+            parent,
+            children: Vec::new(),
+            data: None,
+            size: 0,
+            files: 0,
+            folders: 0,
+        });
+        match parent {
+            Some(parent) => self.nodes[parent.0].children.push(id),
+            None => self.roots.push(id),
+        }
+        id
+    }
+
+    /// Sum up the sizes and counts from the leaves to the roots.
+    fn aggregate(&mut self) {
+        // the parent is always before its children, so the reverse order is bottom-up.
+        for i in (0..self.nodes.len()).rev() {
+            let node = &mut self.nodes[i];
+            match &node.data {
+                Some(data) if data.is_file() => node.size = data.size.unwrap_or(0),
+                Some(data) if node.children.is_empty() => node.size = data.size.unwrap_or(0),
+                _ => {}
+            }
+            let (size, files, folders) = (node.size, node.files, node.folders);
+            let (is_file, is_folder) = match &node.data {
+                Some(data) => (data.is_file(), data.is_folder()),
+                None => (false, false),
+            };
+            if let Some(parent) = node.parent {
+                let parent = &mut self.nodes[parent.0];
+                parent.size += size;
+                parent.files += files + u32::from(is_file);
+                parent.folders += folders + u32::from(is_folder);
+            }
+        }
+    }
+}
+
+impl FromIterator<ItemData> for Tree {
+    fn from_iter<T: IntoIterator<Item = ItemData>>(iter: T) -> Self {
+        let mut tree = Tree::default();
+        for data in iter {
+            let Some(path) = &data.full_path else {
+                continue;
+            };
+            if let Some(id) = tree.get_or_insert(path) {
+                tree.nodes[id.0].data = Some(data);
+            }
+        }
+        tree.aggregate();
+        tree
+    }
+}
+
+impl<'a> FromIterator<&'a ItemData> for Tree {
+    fn from_iter<T: IntoIterator<Item = &'a ItemData>>(iter: T) -> Self {
+        iter.into_iter().cloned().collect()
+    }
+}
+
+impl Node {
+    /// The last component of the path, or the root like `C:` (without the trailing `\`).
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// The children in the order they first appeared in the results.
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    /// The item data if this node is in the results.
+    pub fn data(&self) -> Option<&ItemData> {
+        self.data.as_ref()
+    }
+
+    /// Whether it is a leaf file, the others (including the filled-in ones) are folders.
+    pub fn is_file(&self) -> bool {
+        self.data.as_ref().is_some_and(ItemData::is_file)
+    }
+
+    /// The total size of the files under this node (or of itself for a file).
+    ///
+    /// For a folder in the results without any child in the results, it is the size of the
+    /// folder itself from the index (zero if not indexed).
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The number of the files in the results under this node, not including itself.
+    pub fn files(&self) -> u32 {
+        self.files
+    }
+
+    /// The number of the folders in the results under this node, not including itself.
+    pub fn folders(&self) -> u32 {
+        self.folders
+    }
+}
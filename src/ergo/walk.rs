@@ -0,0 +1,121 @@
+//! A walkdir-style adapter for the owned results.
+//!
+//! The tools written against [walkdir](https://docs.rs/walkdir) mostly only touch
+//! `entry.path()`, `entry.file_name()`, `entry.file_type()` and `entry.metadata()`, so
+//! the [`DirEntry`] trait here is in the same shape, and it is implemented for [`Entry`]
+//! over [`ItemData`]. Switching the backend to Everything search is just replacing the
+//! `WalkDir::new(root)` with a query like `"C:\Projects\" *.rs`.
+
+use std::ffi::OsStr;
+use std::fs::Metadata;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{InvalidRequestError, ItemData, ItemKind, RequestFlags};
+
+/// The file type of an [`Entry`], decided by the index data without touching the disk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct FileType {
+    kind: ItemKind,
+    reparse_point: bool,
+}
+
+impl FileType {
+    pub fn is_dir(&self) -> bool {
+        self.kind.is_folder()
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.kind.is_file()
+    }
+
+    /// Only known when `EVERYTHING_REQUEST_ATTRIBUTES` is requested, `false` otherwise.
+    pub fn is_symlink(&self) -> bool {
+        self.reparse_point
+    }
+}
+
+/// The walkdir-shaped interface for a directory entry.
+pub trait DirEntry {
+    /// The full path of this entry.
+    fn path(&self) -> &Path;
+
+    /// The file name of this entry, the same as the last component of `self.path()`.
+    fn file_name(&self) -> &OsStr;
+
+    fn file_type(&self) -> FileType;
+
+    /// Query the metadata from the file system (NOT from the Everything index).
+    fn metadata(&self) -> io::Result<Metadata> {
+        if self.file_type().is_symlink() {
+            std::fs::symlink_metadata(self.path())
+        } else {
+            std::fs::metadata(self.path())
+        }
+    }
+
+    fn path_is_symlink(&self) -> bool {
+        self.file_type().is_symlink()
+    }
+}
+
+/// The [`DirEntry`] over an [`ItemData`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Entry {
+    data: ItemData,
+    path: PathBuf,
+}
+
+impl Entry {
+    /// Get the item data back.
+    pub fn data(&self) -> &ItemData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> ItemData {
+        self.data
+    }
+
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+}
+
+impl TryFrom<ItemData> for Entry {
+    type Error = InvalidRequestError;
+
+    /// Need the full path of the item, that is `EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME`
+    /// or both of `EVERYTHING_REQUEST_PATH` and `EVERYTHING_REQUEST_FILE_NAME`.
+    fn try_from(data: ItemData) -> Result<Self, Self::Error> {
+        match data.full_path.clone() {
+            Some(path) => Ok(Entry { data, path }),
+            None => Err(InvalidRequestError::RequestFlagsNotSet(
+                RequestFlags::EVERYTHING_REQUEST_PATH | RequestFlags::EVERYTHING_REQUEST_FILE_NAME,
+            )),
+        }
+    }
+}
+
+impl DirEntry for Entry {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn file_name(&self) -> &OsStr {
+        // `C:` has no file name component, the whole path is its name.
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    fn file_type(&self) -> FileType {
+        FileType {
+            kind: self.data.kind,
+            reparse_point: self.data.is_reparse_point(),
+        }
+    }
+}
+
+impl AsRef<Path> for Entry {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,158 @@
+//! Watch the results of a query by polling Everything, and report what changed between polls.
+//!
+//! Everything has no change notification over IPC, but the index is always up to date, so
+//! re-running a query and diffing the snapshots is cheap even for huge trees.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::{ItemData, OwnedResults, RequestFlags, Result, SearchOptions};
+
+#[cfg(feature = "notify")]
+mod notify;
+#[cfg(feature = "notify")]
+pub use self::notify::EverythingWatcher;
+mod recent;
+#[cfg(feature = "async")]
+pub use self::recent::WatchStream;
+pub use self::recent::Watcher;
+
+/// What happened to an item between two polls.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ChangeKind {
+    Created,
+    /// The size or the date modified changed.
+    Modified,
+    Removed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The latest data, or the last seen data for [`ChangeKind::Removed`].
+    pub data: ItemData,
+}
+
+/// The diff between the snapshots of one query, step by step.
+#[derive(Clone, Debug)]
+pub struct Poller {
+    options: SearchOptions,
+    last: Option<HashMap<PathBuf, ItemData>>,
+}
+
+impl Poller {
+    /// The request flags always added to the options, to identify and compare the items.
+    pub const REQUIRED_FLAGS: RequestFlags =
+        RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+            .union(RequestFlags::EVERYTHING_REQUEST_SIZE)
+            .union(RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED);
+
+    pub fn new(mut options: SearchOptions) -> Self {
+        options.request_flags |= Self::REQUIRED_FLAGS;
+        Poller {
+            options,
+            last: None,
+        }
+    }
+
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// Query (taking the [`global`](super::global) lock) and diff with the last poll.
+    ///
+    /// The first poll is the baseline, so it reports no changes.
+    pub fn poll(&mut self) -> Result<Vec<Change>> {
+        let snapshot = self.options.query_snapshot()?;
+        Ok(self.diff(snapshot))
+    }
+
+    /// Diff the `snapshot` of the same query (made by yourself) with the last one.
+    pub fn diff(&mut self, snapshot: OwnedResults) -> Vec<Change> {
+        let current: HashMap<PathBuf, ItemData> = snapshot
+            .into_items()
+            .into_iter()
+            .filter_map(|data| Some((data.full_path.clone()?, data)))
+            .collect();
+        let Some(mut last) = self.last.replace(current) else {
+            return Vec::new();
+        };
+        let current = self.last.as_ref().expect("just replaced");
+
+        let mut changes = Vec::new();
+        for (path, data) in current {
+            let kind = match last.remove(path) {
+                None => ChangeKind::Created,
+                Some(old) if old.size != data.size || old.date_modified != data.date_modified => {
+                    ChangeKind::Modified
+                }
+                Some(_) => continue,
+            };
+            changes.push(Change {
+                kind,
+                data: data.clone(),
+            });
+        }
+        changes.extend(last.into_values().map(|data| Change {
+            kind: ChangeKind::Removed,
+            data,
+        }));
+        changes
+    }
+}
+
+/// Poll a query in a background thread, and call the handler on changes or errors.
+///
+/// The thread is stopped when it is dropped.
+#[derive(Debug)]
+pub struct PollWatcher {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PollWatcher {
+    /// Poll the query of `options` every `interval`, the first poll is the baseline.
+    ///
+    /// The `handler` is called with the changes (never empty) or the error of each poll.
+    pub fn spawn<F>(options: SearchOptions, interval: Duration, mut handler: F) -> Self
+    where
+        F: FnMut(Result<Vec<Change>>) + Send + 'static,
+    {
+        let mut poller = Poller::new(options);
+        Self::spawn_with(interval, move || match poller.poll() {
+            Ok(changes) if changes.is_empty() => {}
+            result => handler(result),
+        })
+    }
+
+    /// Call the `tick` every `interval` in the background thread, until dropped.
+    fn spawn_with(interval: Duration, mut tick: impl FnMut() + Send + 'static) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("everything-sdk-watch".to_string())
+            .spawn(move || loop {
+                tick();
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            })
+            .expect("failed to spawn the watch thread");
+        PollWatcher {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for PollWatcher {
+    fn drop(&mut self) {
+        drop(self.stop.take()); // disconnect to wake up the thread
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! The [`notify::Watcher`] backed by the Everything index. (feature `notify`)
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use ::notify::event::{CreateKind, ModifyKind, RemoveKind};
+use ::notify::{Config, Event, EventHandler, EventKind, RecursiveMode, WatcherKind};
+
+use super::recent::RecentPoller;
+use super::{ChangeKind, PollWatcher};
+use crate::query::{Builder, Scope};
+use crate::SearchOptions;
+
+/// The polling interval if it is not set in [`Config`].
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watch the folders by polling Everything, for the apps already built around `notify`.
+///
+/// It is much cheaper than `ReadDirectoryChangesW` on the huge trees, as each poll queries
+/// only the items recently changed (`rc:`) under the watched folders, see
+/// [`Watcher`](super::Watcher). But only the changes indexed by Everything are seen, and only
+/// the create, modify and remove events are reported, without the details like renames. A
+/// folder just watched is the baseline, so no events for the items already in it.
+///
+/// The polling interval is [`Config::poll_interval_v2`], 2 seconds if not set.
+///
+/// ```no_run
+/// use everything_sdk::watch::EverythingWatcher;
+/// use notify::{RecursiveMode, Watcher};
+///
+/// let mut watcher = EverythingWatcher::new(
+///     |event| println!("{event:?}"),
+///     notify::Config::default(),
+/// )?;
+/// watcher.watch("C:\\Projects".as_ref(), RecursiveMode::Recursive)?;
+/// # Ok::<(), notify::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct EverythingWatcher {
+    pollers: Arc<Mutex<HashMap<PathBuf, RecentPoller>>>,
+    _watcher: PollWatcher,
+}
+
+impl EverythingWatcher {
+    /// The query of the items under `path`, which is absolute as Everything knows no current
+    /// directory.
+    fn options(path: &Path, recursive_mode: RecursiveMode) -> SearchOptions {
+        let search = match recursive_mode {
+            RecursiveMode::Recursive => Scope::folder(path).to_string(),
+            RecursiveMode::NonRecursive => Builder::new().parent(path).to_string(),
+        };
+        SearchOptions::new(search)
+    }
+}
+
+impl ::notify::Watcher for EverythingWatcher {
+    fn new<F: EventHandler>(mut event_handler: F, config: Config) -> ::notify::Result<Self> {
+        let interval = config.poll_interval_v2().unwrap_or(DEFAULT_INTERVAL);
+        let pollers = Arc::new(Mutex::new(HashMap::<PathBuf, RecentPoller>::new()));
+        let shared = Arc::clone(&pollers);
+        let watcher = PollWatcher::spawn_with(interval, move || {
+            let mut events = Vec::new();
+            let mut pollers = shared.lock().unwrap_or_else(PoisonError::into_inner);
+            for poller in pollers.values_mut() {
+                let changes = match poller.poll() {
+                    Ok(changes) => changes,
+                    Err(err) => {
+                        events.push(Err(::notify::Error::generic(&err.to_string())));
+                        continue;
+                    }
+                };
+                for change in changes {
+                    let Some(path) = change.data.full_path else {
+                        continue;
+                    };
+                    let kind = match change.kind {
+                        ChangeKind::Created => EventKind::Create(CreateKind::Any),
+                        ChangeKind::Modified => EventKind::Modify(ModifyKind::Any),
+                        ChangeKind::Removed => EventKind::Remove(RemoveKind::Any),
+                    };
+                    events.push(Ok(Event::new(kind).add_path(path)));
+                }
+            }
+            // The handler may watch or unwatch (by another thread), which needs the lock.
+            drop(pollers);
+            for event in events {
+                event_handler.handle_event(event);
+            }
+        });
+        Ok(EverythingWatcher {
+            pollers,
+            _watcher: watcher,
+        })
+    }
+
+    fn watch(&mut self, path: &Path, recursive_mode: RecursiveMode) -> ::notify::Result<()> {
+        if !path.is_dir() {
+            return Err(::notify::Error::path_not_found().add_path(path.to_path_buf()));
+        }
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .map_err(::notify::Error::io)?
+                .join(path)
+        };
+        let poller = RecentPoller::new(Self::options(&absolute, recursive_mode));
+        self.pollers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(path.to_path_buf(), poller);
+        Ok(())
+    }
+
+    fn unwatch(&mut self, path: &Path) -> ::notify::Result<()> {
+        self.pollers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| ::notify::Error::watch_not_found().add_path(path.to_path_buf()))
+    }
+
+    fn kind() -> WatcherKind {
+        WatcherKind::PollWatcher
+    }
+}
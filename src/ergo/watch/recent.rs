@@ -0,0 +1,246 @@
+//! Watch a query by the date recently changed (`rc:`), without the snapshot of all the items
+//! on each poll.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use super::{Change, ChangeKind, PollWatcher};
+use crate::{FileTime, ItemData, RequestFlags, Result, SearchOptions};
+
+/// The extra seconds of the `rc:` window, for the clock skew and the rounding to seconds. The
+/// items seen twice are filtered out by their date recently changed.
+const WINDOW_MARGIN_SECS: u64 = 2;
+
+/// The query only for the items changed in the last `secs` seconds.
+fn recent_search(search: &str, secs: u64) -> String {
+    if search.trim().is_empty() {
+        format!("rc:last{secs}secs")
+    } else {
+        format!("<{search}> rc:last{secs}secs")
+    }
+}
+
+/// The diff of one query by the date recently changed, step by step.
+///
+/// The first poll lists the paths (only) as the baseline. Then each poll asks for the items
+/// changed since the last poll, which are created if not seen before, otherwise modified. The
+/// removed items are never changed, so it counts the results on each poll (without the items),
+/// and lists the paths again only if there are fewer than known.
+#[derive(Clone, Debug)]
+pub(super) struct RecentPoller {
+    options: SearchOptions,
+    /// The date recently changed of the known items.
+    known: Option<HashMap<PathBuf, Option<u64>>>,
+    since: FileTime,
+}
+
+impl RecentPoller {
+    /// The request flags always added to the options, to identify and compare the items.
+    const REQUIRED_FLAGS: RequestFlags = RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME
+        .union(RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED);
+
+    pub(super) fn new(mut options: SearchOptions) -> Self {
+        options.request_flags |= Self::REQUIRED_FLAGS;
+        options.offset = 0;
+        options.max = u32::MAX;
+        RecentPoller {
+            options,
+            known: None,
+            since: FileTime::ZERO,
+        }
+    }
+
+    pub(super) fn poll(&mut self) -> Result<Vec<Change>> {
+        let now = FileTime::now();
+        let Some(known) = &mut self.known else {
+            self.known = Some(self.list()?);
+            self.since = now;
+            return Ok(Vec::new());
+        };
+
+        let mut recent = self.options.clone();
+        let secs = self.since.age().as_secs() + WINDOW_MARGIN_SECS;
+        recent.search = recent_search(&self.options.search, secs);
+        let mut changes = Vec::new();
+        for data in recent.query_snapshot()?.into_items() {
+            let Some(path) = data.full_path.clone() else {
+                continue;
+            };
+            let kind = match known.insert(path, data.date_recently_changed) {
+                None => ChangeKind::Created,
+                Some(old) if old != data.date_recently_changed => ChangeKind::Modified,
+                Some(_) => continue,
+            };
+            changes.push(Change { kind, data });
+        }
+        self.since = now;
+
+        let mut count = self.options.clone();
+        count.request_flags = RequestFlags::EVERYTHING_REQUEST_FILE_NAME;
+        count.max = 0;
+        if (count.query_snapshot()?.total() as usize) < known.len() {
+            let current = self.list()?;
+            let known = self.known.as_mut().expect("checked above");
+            known.retain(|path, _| {
+                let kept = current.contains_key(path);
+                if !kept {
+                    let data = ItemData {
+                        request_flags: RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME,
+                        full_path: Some(path.clone()),
+                        ..Default::default()
+                    };
+                    changes.push(Change {
+                        kind: ChangeKind::Removed,
+                        data,
+                    });
+                }
+                kept
+            });
+        }
+        Ok(changes)
+    }
+
+    /// All the paths of the query, with their date recently changed.
+    fn list(&self) -> Result<HashMap<PathBuf, Option<u64>>> {
+        let mut options = self.options.clone();
+        options.request_flags = Self::REQUIRED_FLAGS;
+        Ok(options
+            .query_snapshot()?
+            .into_items()
+            .into_iter()
+            .filter_map(|data| Some((data.full_path?, data.date_recently_changed)))
+            .collect())
+    }
+
+    /// Poll every `interval` in the background thread, and `send` each change or error.
+    fn spawn(
+        mut self,
+        interval: Duration,
+        mut send: impl FnMut(Result<Change>) + Send + 'static,
+    ) -> PollWatcher {
+        PollWatcher::spawn_with(interval, move || match self.poll() {
+            Ok(changes) => changes.into_iter().map(Ok).for_each(&mut send),
+            Err(err) => send(Err(err)),
+        })
+    }
+}
+
+/// Watch the changes of a query, received from a channel.
+///
+/// It is much cheaper than [`PollWatcher`] on the large results, as only the items changed
+/// since the last poll are queried (by `rc:`, the date recently changed), but the removed
+/// items have only [`ItemData::full_path`]. It is stopped when dropped.
+///
+/// The first poll is the baseline, so no changes for the items already there.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use everything_sdk::watch::Watcher;
+///
+/// let watcher = Watcher::new("C:\\Projects\\ ext:rs", Duration::from_secs(1));
+/// for change in watcher.iter() {
+///     let change = change?;
+///     println!("{:?} {:?}", change.kind, change.data.full_path);
+/// }
+/// # Ok::<(), everything_sdk::EverythingError>(())
+/// ```
+#[derive(Debug)]
+pub struct Watcher {
+    receiver: Receiver<Result<Change>>,
+    _watcher: PollWatcher,
+}
+
+impl Watcher {
+    /// Watch the `query` every `interval`.
+    pub fn new(query: impl Into<String>, interval: Duration) -> Self {
+        Self::with_options(SearchOptions::new(query), interval)
+    }
+
+    /// Watch the query of `options` every `interval`, the `offset` and the `max` are ignored.
+    pub fn with_options(options: SearchOptions, interval: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let watcher = RecentPoller::new(options).spawn(interval, move |change| {
+            let _ = sender.send(change);
+        });
+        Watcher {
+            receiver,
+            _watcher: watcher,
+        }
+    }
+
+    /// Wait for the next change, `None` only if the watch thread panicked.
+    pub fn recv(&self) -> Option<Result<Change>> {
+        self.receiver.recv().ok()
+    }
+
+    /// The next change if any, without waiting.
+    pub fn try_recv(&self) -> Option<Result<Change>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Wait for the next change up to `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<Change>> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+
+    /// Block on the changes one by one.
+    pub fn iter(&self) -> mpsc::Iter<'_, Result<Change>> {
+        self.receiver.iter()
+    }
+}
+
+/// The [`Watcher`] as a [`Stream`](futures::Stream). (feature `async`)
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use everything_sdk::watch::WatchStream;
+/// use futures::StreamExt;
+///
+/// # futures::executor::block_on(async {
+/// let mut changes = WatchStream::new("D:\\Downloads\\", Duration::from_secs(1));
+/// while let Some(change) = changes.next().await {
+///     println!("{:?}", change);
+/// }
+/// # });
+/// ```
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct WatchStream {
+    receiver: futures::channel::mpsc::UnboundedReceiver<Result<Change>>,
+    _watcher: PollWatcher,
+}
+
+#[cfg(feature = "async")]
+impl WatchStream {
+    /// Watch the `query` every `interval`.
+    pub fn new(query: impl Into<String>, interval: Duration) -> Self {
+        Self::with_options(SearchOptions::new(query), interval)
+    }
+
+    /// Watch the query of `options` every `interval`, the `offset` and the `max` are ignored.
+    pub fn with_options(options: SearchOptions, interval: Duration) -> Self {
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let watcher = RecentPoller::new(options).spawn(interval, move |change| {
+            let _ = sender.unbounded_send(change);
+        });
+        WatchStream {
+            receiver,
+            _watcher: watcher,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures::Stream for WatchStream {
+    type Item = Result<Change>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use futures::StreamExt;
+        self.receiver.poll_next_unpin(cx)
+    }
+}
@@ -0,0 +1,554 @@
+//! The ergonomic wrapper of the SDK 3 of Everything 1.5, by the `sdk3` feature.
+//!
+//! The SDK 3 keeps its states in the objects it creates, so here a [`Client`] is a connection
+//! of its own, a [`Search`] is the search state, and the [`ResultList`] owns the results. No
+//! global lock is needed, and any number of clients can search at the same time, each from
+//! its own thread.
+//!
+//! It only works with Everything 1.5, and needs the library of the SDK 3 to link, see
+//! `EVERYTHING3_LIB_DIR` in the `build.rs` of `everything-sdk-sys`.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::ergo3::{Client, Search};
+//!
+//! let client = Client::connect(None)?;
+//! let results = client.search(Search::new("*.rs").max(10))?;
+//! for index in 0..results.len() {
+//!     println!("{:?}", results.full_path(index)?);
+//! }
+//! # Ok::<(), everything_sdk::ergo3::Everything3Error>(())
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::ffi::OsString;
+use std::fmt;
+use std::ptr::NonNull;
+
+use everything_sdk_sys::sdk3 as sys;
+use thiserror::Error as ThisError;
+use widestring::{U16CStr, U16CString};
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::Foundation::{SetLastError, BOOL, FALSE, TRUE, WIN32_ERROR};
+
+use crate::raw::FileAttributes;
+
+mod property;
+
+pub use property::{Item, Property, PropertyValue};
+
+/// `(major, minor, revision, build)` of Everything, see [`Client::version`].
+pub type Version = (u32, u32, u32, u32);
+
+/// The errors of the SDK 3, by `Everything3_GetLastError`.
+#[non_exhaustive]
+#[derive(ThisError, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Everything3Error {
+    #[error("out of memory.")]
+    OutOfMemory,
+    #[error("Everything 1.5 is not running (the IPC pipe is not found).")]
+    PipeNotFound,
+    #[error("disconnected from Everything.")]
+    Disconnected,
+    #[error("bad parameter.")]
+    InvalidParameter,
+    #[error("bad request.")]
+    BadRequest,
+    #[error("cancelled.")]
+    Cancelled,
+    #[error("the property is not found.")]
+    PropertyNotFound,
+    #[error("the server failed.")]
+    Server,
+    #[error("invalid command.")]
+    InvalidCommand,
+    #[error("bad response from Everything.")]
+    BadResponse,
+    #[error("the buffer is too small.")]
+    InsufficientBuffer,
+    #[error("Everything is shutting down.")]
+    Shutdown,
+    #[error("the property value is not of the type asked.")]
+    InvalidPropertyValueType,
+    #[error("the property {0} is not requested by the search.")]
+    NotRequested(Property),
+    #[error("the property {0} needs Everything 1.5, but the version is {1:?}.")]
+    Unsupported(Property, Version),
+    #[error("unknown error code {0:#x} of the SDK 3.")]
+    Unknown(u32),
+}
+
+impl Everything3Error {
+    /// The last error of the SDK 3 on this thread.
+    fn last() -> Self {
+        match unsafe { sys::Everything3_GetLastError() } {
+            sys::EVERYTHING3_ERROR_OUT_OF_MEMORY => Everything3Error::OutOfMemory,
+            sys::EVERYTHING3_ERROR_IPC_PIPE_NOT_FOUND => Everything3Error::PipeNotFound,
+            sys::EVERYTHING3_ERROR_DISCONNECTED => Everything3Error::Disconnected,
+            sys::EVERYTHING3_ERROR_INVALID_PARAMETER => Everything3Error::InvalidParameter,
+            sys::EVERYTHING3_ERROR_BAD_REQUEST => Everything3Error::BadRequest,
+            sys::EVERYTHING3_ERROR_CANCELLED => Everything3Error::Cancelled,
+            sys::EVERYTHING3_ERROR_PROPERTY_NOT_FOUND => Everything3Error::PropertyNotFound,
+            sys::EVERYTHING3_ERROR_SERVER => Everything3Error::Server,
+            sys::EVERYTHING3_ERROR_INVALID_COMMAND => Everything3Error::InvalidCommand,
+            sys::EVERYTHING3_ERROR_BAD_RESPONSE => Everything3Error::BadResponse,
+            sys::EVERYTHING3_ERROR_INSUFFICIENT_BUFFER => Everything3Error::InsufficientBuffer,
+            sys::EVERYTHING3_ERROR_SHUTDOWN => Everything3Error::Shutdown,
+            sys::EVERYTHING3_ERROR_INVALID_PROPERTY_VALUE_TYPE => {
+                Everything3Error::InvalidPropertyValueType
+            }
+            code => Everything3Error::Unknown(code),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Everything3Error>;
+
+fn check(success: BOOL) -> Result<()> {
+    if success != FALSE {
+        Ok(())
+    } else {
+        Err(Everything3Error::last())
+    }
+}
+
+/// The name passed to the SDK 3, which can not have a nul in it.
+fn wide_name(name: &str) -> Result<U16CString> {
+    U16CString::from_str(name).map_err(|_| Everything3Error::InvalidParameter)
+}
+
+fn to_bool(b: bool) -> BOOL {
+    if b {
+        TRUE
+    } else {
+        FALSE
+    }
+}
+
+/// The property ID in the SDK 3, by [`Client::find_property`] or the well-known ones.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PropertyId(pub u32);
+
+impl PropertyId {
+    pub const NAME: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_NAME);
+    pub const PATH: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_PATH);
+    pub const SIZE: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_SIZE);
+    pub const EXTENSION: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_EXTENSION);
+    pub const TYPE: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_TYPE);
+    pub const DATE_MODIFIED: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_DATE_MODIFIED);
+    pub const DATE_CREATED: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_DATE_CREATED);
+    pub const DATE_ACCESSED: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_DATE_ACCESSED);
+    pub const ATTRIBUTES: Self = PropertyId(sys::EVERYTHING3_PROPERTY_ID_ATTRIBUTES);
+}
+
+/// A connection to an Everything 1.5 instance.
+///
+/// It is `Send`, so move it to another thread if needed, but not `Sync`.
+pub struct Client {
+    ptr: NonNull<sys::EVERYTHING3_CLIENT>,
+}
+
+// SAFETY: the client of the SDK 3 is not bound to the thread creating it.
+unsafe impl Send for Client {}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        unsafe { sys::Everything3_DestroyClient(self.ptr.as_ptr()) };
+    }
+}
+
+impl Client {
+    /// Connect to the instance of `name`, or the default one of Everything 1.5 (`1.5a`) if
+    /// `None`. Fail with [`Everything3Error::PipeNotFound`] if it is not running, or
+    /// [`Everything3Error::InvalidParameter`] if the name has a nul.
+    pub fn connect(name: Option<&str>) -> Result<Self> {
+        let name = name.map(wide_name).transpose()?;
+        let name = name
+            .as_ref()
+            .map_or(PCWSTR::null(), |name| PCWSTR(name.as_ptr()));
+        let ptr = unsafe { sys::Everything3_ConnectW(name) };
+        NonNull::new(ptr)
+            .map(|ptr| Client { ptr })
+            .ok_or_else(Everything3Error::last)
+    }
+
+    fn as_ptr(&self) -> *mut sys::EVERYTHING3_CLIENT {
+        self.ptr.as_ptr()
+    }
+
+    /// `(major, minor, revision, build)`
+    pub fn version(&self) -> Result<Version> {
+        let major = unsafe { sys::Everything3_GetMajorVersion(self.as_ptr()) };
+        if major == 0 {
+            return Err(Everything3Error::last());
+        }
+        unsafe {
+            Ok((
+                major,
+                sys::Everything3_GetMinorVersion(self.as_ptr()),
+                sys::Everything3_GetRevision(self.as_ptr()),
+                sys::Everything3_GetBuildNumber(self.as_ptr()),
+            ))
+        }
+    }
+
+    /// Check if the database is loaded, nothing is found while loading.
+    pub fn is_db_loaded(&self) -> bool {
+        unsafe { sys::Everything3_IsDBLoaded(self.as_ptr()) != FALSE }
+    }
+
+    /// The ID of the property by its canonical name, like `"Size"` or `"Width"`.
+    ///
+    /// Fail with [`Everything3Error::InvalidParameter`] if the name has a nul.
+    pub fn find_property(&self, canonical_name: &str) -> Result<PropertyId> {
+        let name = wide_name(canonical_name)?;
+        let id = unsafe { sys::Everything3_FindPropertyW(self.as_ptr(), PCWSTR(name.as_ptr())) };
+        if id == sys::EVERYTHING3_INVALID_PROPERTY_ID {
+            Err(Everything3Error::PropertyNotFound)
+        } else {
+            Ok(PropertyId(id))
+        }
+    }
+
+    /// Search and wait for the results.
+    ///
+    /// With the typed properties requested, the version of Everything is asked too, so that
+    /// [`Item::property`] fails on an Everything not supporting them.
+    pub fn search(&self, search: &Search) -> Result<ResultList> {
+        let version = if search.properties.is_empty() {
+            None
+        } else {
+            Some(self.version()?)
+        };
+        // Not found by an older Everything, and then failed by `Item::property` instead.
+        let supported = version.map_or(true, |version| version >= Property::MIN_VERSION);
+        search.resolve_properties(self, supported)?;
+        let ptr = unsafe { sys::Everything3_Search(self.as_ptr(), search.ptr.as_ptr()) };
+        NonNull::new(ptr)
+            .map(|ptr| ResultList {
+                ptr,
+                properties: search.resolved.borrow().clone(),
+                version,
+            })
+            .ok_or_else(Everything3Error::last)
+    }
+}
+
+/// The search state, which can be reused for many searches.
+///
+/// The setters panic only if the SDK 3 is out of memory.
+pub struct Search {
+    ptr: NonNull<sys::EVERYTHING3_SEARCH_STATE>,
+    /// By [`Search::request_properties`], their IDs are found by the first client searching.
+    properties: Vec<Property>,
+    resolved: RefCell<Vec<(Property, PropertyId)>>,
+    /// By [`Search::sort_by`], resolved like the properties.
+    sorts: Vec<(Property, bool)>,
+    sorts_resolved: Cell<usize>,
+}
+
+// SAFETY: the search state is plain data owned by this value.
+unsafe impl Send for Search {}
+
+impl fmt::Debug for Search {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Search").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        unsafe { sys::Everything3_DestroySearchState(self.ptr.as_ptr()) };
+    }
+}
+
+impl Search {
+    /// The search state of the `text`, with all the others default.
+    pub fn new(text: &str) -> Self {
+        let ptr = unsafe { sys::Everything3_CreateSearchState() };
+        let mut search = Search {
+            ptr: NonNull::new(ptr).expect("out of memory"),
+            properties: Vec::new(),
+            resolved: RefCell::new(Vec::new()),
+            sorts: Vec::new(),
+            sorts_resolved: Cell::new(0),
+        };
+        search.set_text(text);
+        search
+    }
+
+    fn set(&mut self, f: impl FnOnce(*mut sys::EVERYTHING3_SEARCH_STATE) -> BOOL) -> &mut Self {
+        check(f(self.ptr.as_ptr())).expect("the search state is valid");
+        self
+    }
+
+    pub fn set_text(&mut self, text: &str) -> &mut Self {
+        let text = U16CString::from_str_truncate(text);
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchTextW(ptr, PCWSTR(text.as_ptr())) })
+    }
+
+    pub fn match_case(&mut self, enabled: bool) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchMatchCase(ptr, to_bool(enabled)) })
+    }
+
+    pub fn match_path(&mut self, enabled: bool) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchMatchPath(ptr, to_bool(enabled)) })
+    }
+
+    pub fn match_whole_words(&mut self, enabled: bool) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchMatchWholeWords(ptr, to_bool(enabled)) })
+    }
+
+    pub fn regex(&mut self, enabled: bool) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchRegex(ptr, to_bool(enabled)) })
+    }
+
+    /// Skip the first `offset` results.
+    pub fn offset(&mut self, offset: usize) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchViewportOffset(ptr, offset) })
+    }
+
+    /// Return at most `max` results.
+    pub fn max(&mut self, max: usize) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_SetSearchViewportCount(ptr, max) })
+    }
+
+    /// Sort by the property, after the sorts added before.
+    pub fn sort(&mut self, property: PropertyId, ascending: bool) -> &mut Self {
+        self.set(|ptr| unsafe {
+            sys::Everything3_AddSearchSort(ptr, property.0, to_bool(ascending))
+        })
+    }
+
+    /// Sort by the typed property, after the sorts by [`Search::sort`].
+    pub fn sort_by(&mut self, property: Property, ascending: bool) -> &mut Self {
+        self.sorts.push((property, ascending));
+        self
+    }
+
+    /// Request the property of the results, see [`ResultList::property_text`] and so on.
+    pub fn request_property(&mut self, property: PropertyId) -> &mut Self {
+        self.set(|ptr| unsafe { sys::Everything3_AddSearchPropertyRequest(ptr, property.0) })
+    }
+
+    /// Request the typed properties, then get them by [`Item::property`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use everything_sdk::ergo3::{Client, Property, Search};
+    ///
+    /// let client = Client::connect(None)?;
+    /// let mut search = Search::new("*.mp4");
+    /// search.request_properties(&[Property::Dimensions, Property::Length]);
+    /// let results = client.search(&search)?;
+    /// for item in results.iter() {
+    ///     println!(
+    ///         "{:?} {:?}",
+    ///         item.property(Property::Dimensions)?,
+    ///         item.property(Property::Length)?
+    ///     );
+    /// }
+    /// # Ok::<(), everything_sdk::ergo3::Everything3Error>(())
+    /// ```
+    pub fn request_properties(&mut self, properties: &[Property]) -> &mut Self {
+        for property in properties {
+            if !self.properties.contains(property) {
+                self.properties.push(*property);
+            }
+        }
+        self
+    }
+
+    /// Find the IDs of the properties not added to the search state yet, and add them, but
+    /// only the sorts if the typed properties are not `supported`.
+    fn resolve_properties(&self, client: &Client, supported: bool) -> Result<()> {
+        let mut resolved = self.resolved.borrow_mut();
+        let properties = if supported {
+            &self.properties[resolved.len()..]
+        } else {
+            &[]
+        };
+        for &property in properties {
+            let id = client.property_id(property)?;
+            check(unsafe { sys::Everything3_AddSearchPropertyRequest(self.ptr.as_ptr(), id.0) })?;
+            resolved.push((property, id));
+        }
+        for &(property, ascending) in &self.sorts[self.sorts_resolved.get()..] {
+            let id = client.property_id(property)?;
+            check(unsafe {
+                sys::Everything3_AddSearchSort(self.ptr.as_ptr(), id.0, to_bool(ascending))
+            })?;
+            self.sorts_resolved.set(self.sorts_resolved.get() + 1);
+        }
+        Ok(())
+    }
+}
+
+/// The results of a search, owned by this value.
+pub struct ResultList {
+    ptr: NonNull<sys::EVERYTHING3_RESULT_LIST>,
+    properties: Vec<(Property, PropertyId)>,
+    /// The version of Everything searched, if the typed properties are requested.
+    version: Option<Version>,
+}
+
+// SAFETY: the result list is plain data owned by this value.
+unsafe impl Send for ResultList {}
+
+impl fmt::Debug for ResultList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultList")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ResultList {
+    fn drop(&mut self) {
+        unsafe { sys::Everything3_DestroyResultList(self.ptr.as_ptr()) };
+    }
+}
+
+impl ResultList {
+    fn as_ptr(&self) -> *mut sys::EVERYTHING3_RESULT_LIST {
+        self.ptr.as_ptr()
+    }
+
+    /// The number of the results in the list (the viewport).
+    pub fn len(&self) -> usize {
+        unsafe { sys::Everything3_GetResultListViewportCount(self.as_ptr()) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The result at the `index`, `None` if out of the list.
+    pub fn get(&self, index: usize) -> Option<Item<'_>> {
+        (index < self.len()).then(|| Item::new(self, index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Item<'_>> {
+        (0..self.len()).map(move |index| Item::new(self, index))
+    }
+
+    /// The ID of the `property` requested by [`Search::request_properties`].
+    fn property_id(&self, property: Property) -> Option<PropertyId> {
+        self.properties
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|&(_, id)| id)
+    }
+
+    /// The number of all the folders found, not only the ones in the list.
+    pub fn folder_count(&self) -> usize {
+        unsafe { sys::Everything3_GetResultListFolderCount(self.as_ptr()) }
+    }
+
+    /// The number of all the files found, not only the ones in the list.
+    pub fn file_count(&self) -> usize {
+        unsafe { sys::Everything3_GetResultListFileCount(self.as_ptr()) }
+    }
+
+    /// The total size of all the files found, `None` if the size is not indexed.
+    pub fn total_size(&self) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultListTotalSize(self.as_ptr()) })
+    }
+
+    pub fn is_folder(&self, index: usize) -> bool {
+        unsafe { sys::Everything3_IsFolderResult(self.as_ptr(), index) != FALSE }
+    }
+
+    /// Read the text by `get(buf, len)`, which returns the length without the nul.
+    fn text(&self, get: impl Fn(PWSTR, usize) -> usize) -> Result<OsString> {
+        // The last error is the one of the thread, which is left as is on success, so clear it
+        // to tell the empty text from the failure of this call.
+        unsafe { SetLastError(WIN32_ERROR(sys::EVERYTHING3_OK)) };
+        // The length is asked first by the null buffer.
+        let len = get(PWSTR::null(), 0);
+        if len == 0 {
+            return match Everything3Error::last() {
+                Everything3Error::Unknown(sys::EVERYTHING3_OK) => Ok(OsString::new()),
+                e => Err(e),
+            };
+        }
+        let mut buf = vec![0u16; len + 1];
+        get(PWSTR(buf.as_mut_ptr()), buf.len());
+        Ok(U16CStr::from_slice_truncate(&buf)
+            .expect("nul terminated")
+            .to_os_string())
+    }
+
+    pub fn name(&self, index: usize) -> Result<OsString> {
+        self.text(|buf, len| unsafe {
+            sys::Everything3_GetResultNameW(self.as_ptr(), index, buf, len)
+        })
+    }
+
+    pub fn path(&self, index: usize) -> Result<OsString> {
+        self.text(|buf, len| unsafe {
+            sys::Everything3_GetResultPathW(self.as_ptr(), index, buf, len)
+        })
+    }
+
+    pub fn full_path(&self, index: usize) -> Result<OsString> {
+        self.text(|buf, len| unsafe {
+            sys::Everything3_GetResultFullPathNameW(self.as_ptr(), index, buf, len)
+        })
+    }
+
+    /// `None` if the size is unknown, e.g. of the folders not indexed.
+    pub fn size(&self, index: usize) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultSize(self.as_ptr(), index) })
+    }
+
+    /// In FILETIME, `None` if unknown.
+    pub fn date_modified(&self, index: usize) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultDateModified(self.as_ptr(), index) })
+    }
+
+    /// In FILETIME, `None` if unknown.
+    pub fn date_created(&self, index: usize) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultDateCreated(self.as_ptr(), index) })
+    }
+
+    /// In FILETIME, `None` if unknown.
+    pub fn date_accessed(&self, index: usize) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultDateAccessed(self.as_ptr(), index) })
+    }
+
+    pub fn attributes(&self, index: usize) -> Option<FileAttributes> {
+        let attributes = unsafe { sys::Everything3_GetResultAttributes(self.as_ptr(), index) };
+        (attributes != sys::EVERYTHING3_DWORD_UNKNOWN)
+            .then(|| FileAttributes::from_bits_retain(attributes))
+    }
+
+    /// The property requested by [`Search::request_property`], formatted as text.
+    pub fn property_text(&self, index: usize, property: PropertyId) -> Result<OsString> {
+        self.text(|buf, len| unsafe {
+            sys::Everything3_GetResultPropertyTextW(self.as_ptr(), index, property.0, buf, len)
+        })
+    }
+
+    /// The property of a 32-bit value, `None` if unknown.
+    pub fn property_u32(&self, index: usize, property: PropertyId) -> Option<u32> {
+        let value =
+            unsafe { sys::Everything3_GetResultPropertyDWORD(self.as_ptr(), index, property.0) };
+        (value != sys::EVERYTHING3_DWORD_UNKNOWN).then_some(value)
+    }
+
+    /// The property of a 64-bit value, `None` if unknown.
+    pub fn property_u64(&self, index: usize, property: PropertyId) -> Option<u64> {
+        known(unsafe { sys::Everything3_GetResultPropertyUINT64(self.as_ptr(), index, property.0) })
+    }
+}
+
+fn known(value: u64) -> Option<u64> {
+    (value != sys::EVERYTHING3_UINT64_UNKNOWN).then_some(value)
+}
@@ -0,0 +1,236 @@
+//! The properties of Everything 1.5, typed.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::time::Duration;
+
+use super::{Client, Everything3Error, PropertyId, Result, ResultList, Version};
+use crate::raw::FileAttributes;
+
+/// The properties to request by [`Search::request_properties`](super::Search::request_properties)
+/// and to get by [`Item::property`].
+///
+/// Everything indexes a few of them by default, the others are read from the files while
+/// searching, which is slow (see Tools > Options > Indexes > Properties).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Property {
+    Name,
+    Path,
+    Size,
+    Extension,
+    Type,
+    DateModified,
+    DateCreated,
+    DateAccessed,
+    Attributes,
+    DateRecentlyChanged,
+    RunCount,
+    DateRun,
+    FileListFileName,
+    /// The owner of the file, like `DOMAIN\user`.
+    Owner,
+    /// The width of the images and the videos, in pixels.
+    Width,
+    /// The height of the images and the videos, in pixels.
+    Height,
+    /// The `width x height` text.
+    Dimensions,
+    /// The duration of the audios and the videos.
+    Length,
+    /// In kbps.
+    BitRate,
+    Title,
+    Artist,
+    Album,
+    Comment,
+    /// When the photo was taken, by its EXIF.
+    DateTaken,
+    /// The text content of the file.
+    Content,
+}
+
+/// How the value of a property is read.
+#[derive(Clone, Copy)]
+enum Kind {
+    Text,
+    U32,
+    U64,
+    FileTime,
+    Duration,
+    Attributes,
+}
+
+impl Property {
+    /// The first version of Everything supporting the typed properties.
+    pub const MIN_VERSION: Version = (1, 5, 0, 0);
+
+    /// The canonical name for [`Client::find_property`].
+    pub fn canonical_name(&self) -> &'static str {
+        match self {
+            Property::Name => "Name",
+            Property::Path => "Path",
+            Property::Size => "Size",
+            Property::Extension => "Extension",
+            Property::Type => "Type",
+            Property::DateModified => "Date Modified",
+            Property::DateCreated => "Date Created",
+            Property::DateAccessed => "Date Accessed",
+            Property::Attributes => "Attributes",
+            Property::DateRecentlyChanged => "Date Recently Changed",
+            Property::RunCount => "Run Count",
+            Property::DateRun => "Date Run",
+            Property::FileListFileName => "File List Filename",
+            Property::Owner => "Owner",
+            Property::Width => "Width",
+            Property::Height => "Height",
+            Property::Dimensions => "Dimensions",
+            Property::Length => "Length",
+            Property::BitRate => "Bit Rate",
+            Property::Title => "Title",
+            Property::Artist => "Artist",
+            Property::Album => "Album",
+            Property::Comment => "Comment",
+            Property::DateTaken => "Date Taken",
+            Property::Content => "Content",
+        }
+    }
+
+    /// The IDs fixed in `Everything3.h`, the others are found by the name.
+    fn fixed_id(&self) -> Option<PropertyId> {
+        let id = match self {
+            Property::Name => PropertyId::NAME,
+            Property::Path => PropertyId::PATH,
+            Property::Size => PropertyId::SIZE,
+            Property::Extension => PropertyId::EXTENSION,
+            Property::Type => PropertyId::TYPE,
+            Property::DateModified => PropertyId::DATE_MODIFIED,
+            Property::DateCreated => PropertyId::DATE_CREATED,
+            Property::DateAccessed => PropertyId::DATE_ACCESSED,
+            Property::Attributes => PropertyId::ATTRIBUTES,
+            _ => return None,
+        };
+        Some(id)
+    }
+
+    fn kind(&self) -> Kind {
+        match self {
+            Property::Size => Kind::U64,
+            Property::DateModified
+            | Property::DateCreated
+            | Property::DateAccessed
+            | Property::DateRecentlyChanged
+            | Property::DateRun
+            | Property::DateTaken => Kind::FileTime,
+            Property::Attributes => Kind::Attributes,
+            Property::RunCount | Property::Width | Property::Height | Property::BitRate => {
+                Kind::U32
+            }
+            Property::Length => Kind::Duration,
+            _ => Kind::Text,
+        }
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.canonical_name())
+    }
+}
+
+/// The value of a [`Property`], in the type of the property.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Text(OsString),
+    U32(u32),
+    U64(u64),
+    /// In FILETIME, see `FileTime` without the `raw` feature.
+    FileTime(u64),
+    Duration(Duration),
+    Attributes(FileAttributes),
+}
+
+impl PropertyValue {
+    pub fn as_text(&self) -> Option<&OsString> {
+        match self {
+            PropertyValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The numeric values, including the FILETIME.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            PropertyValue::U32(n) => Some(n as u64),
+            PropertyValue::U64(n) | PropertyValue::FileTime(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_duration(&self) -> Option<Duration> {
+        match *self {
+            PropertyValue::Duration(duration) => Some(duration),
+            _ => None,
+        }
+    }
+}
+
+impl Client {
+    /// The ID of the `property` in this Everything.
+    pub fn property_id(&self, property: Property) -> Result<PropertyId> {
+        match property.fixed_id() {
+            Some(id) => Ok(id),
+            None => self.find_property(property.canonical_name()),
+        }
+    }
+}
+
+/// The result at an index of a [`ResultList`].
+#[derive(Clone, Copy, Debug)]
+pub struct Item<'a> {
+    list: &'a ResultList,
+    index: usize,
+}
+
+impl<'a> Item<'a> {
+    pub(super) fn new(list: &'a ResultList, index: usize) -> Self {
+        Item { list, index }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The value of the `property` requested, `None` if unknown for this result.
+    ///
+    /// Fail with [`Everything3Error::NotRequested`] if it was not requested by the search, or
+    /// [`Everything3Error::Unsupported`] if the Everything searched is older than
+    /// [`Property::MIN_VERSION`].
+    pub fn property(&self, property: Property) -> Result<Option<PropertyValue>> {
+        match self.list.version {
+            Some(version) if version < Property::MIN_VERSION => {
+                return Err(Everything3Error::Unsupported(property, version))
+            }
+            _ => {}
+        }
+        let id = self
+            .list
+            .property_id(property)
+            .ok_or(Everything3Error::NotRequested(property))?;
+        let (list, index) = (self.list, self.index);
+        let value = match property.kind() {
+            Kind::Text => Some(PropertyValue::Text(list.property_text(index, id)?)),
+            Kind::U32 => list.property_u32(index, id).map(PropertyValue::U32),
+            Kind::U64 => list.property_u64(index, id).map(PropertyValue::U64),
+            Kind::FileTime => list.property_u64(index, id).map(PropertyValue::FileTime),
+            // In 100 nanoseconds, like FILETIME.
+            Kind::Duration => list
+                .property_u64(index, id)
+                .map(|n| PropertyValue::Duration(Duration::from_nanos(n.saturating_mul(100)))),
+            Kind::Attributes => list
+                .property_u32(index, id)
+                .map(|bits| PropertyValue::Attributes(FileAttributes::from_bits_retain(bits))),
+        };
+        Ok(value)
+    }
+}
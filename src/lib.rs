@@ -15,3 +15,6 @@ pub use ergo::*;
 mod raw;
 #[cfg(feature = "raw")]
 pub mod raw;
+
+#[cfg(feature = "sdk3")]
+pub mod ergo3;
@@ -0,0 +1,194 @@
+//! The ANSI version functions ending in `A`, by the `ansi` feature.
+//!
+//! The texts are bytes in the ANSI code page of the system (not UTF-8), for interoperating
+//! with the legacy code pages or the byte-oriented pipelines. Use the Unicode ones otherwise.
+//!
+//! The SDK remembers whether the last query is sent by `Everything_QueryA` or
+//! `Everything_QueryW`, and the result getters of the other version fail with
+//! `EVERYTHING_ERROR_INVALIDCALL`. So after [`Everything_QueryA`], get the results by the
+//! functions here. The reply decoded by [`parse_query_reply`](super::parse_query_reply) is in
+//! Unicode only, so the non-blocking queries should be sent by `Everything_Query`.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::raw::*;
+//! use std::ffi::CString;
+//!
+//! let access = RawAccess::global().lock().unwrap();
+//! let search = CString::new("*.txt").unwrap();
+//! Everything_SetSearchA(&access, &search);
+//! Everything_QueryA(&access, true);
+//! for index in 0..Everything_GetNumResults(&access) {
+//!     if let Some(name) = Everything_GetResultFileNameA(&access, index) {
+//!         println!("{name:?}");
+//!     }
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "raw"), allow(dead_code))]
+
+use std::ffi::{CStr, CString};
+use std::num::NonZeroU32;
+
+use windows::core::{PCSTR, PSTR};
+use windows::Win32::Foundation::{FALSE, TRUE};
+
+use super::{
+    lower_bool, sdk_sys, zero_or_last_error, Everything_GetLastError, LastError, RawAccess,
+};
+
+/// Copy the null terminated string returned by the SDK, `None` if null.
+///
+/// # Safety
+/// The `ptr` is null, or a valid null terminated string.
+unsafe fn to_cstring(ptr: PCSTR) -> Option<CString> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr.as_ptr().cast()).to_owned())
+    }
+}
+
+// --- search state ---
+
+/// The ANSI version of [`Everything_SetSearch`](super::Everything_SetSearch).
+pub fn Everything_SetSearchA(_: &RawAccess, text: &CStr) {
+    unsafe { sdk_sys::Everything_SetSearchA(PCSTR(text.as_ptr().cast())) };
+}
+
+/// The ANSI version of [`Everything_GetSearch`](super::Everything_GetSearch).
+///
+/// The search text set by `Everything_SetSearch` is converted to the ANSI code page.
+pub fn Everything_GetSearchA(_: &RawAccess) -> CString {
+    let ptr = unsafe { sdk_sys::Everything_GetSearchA() };
+    assert!(!ptr.is_null());
+    // SAFETY: the SDK returns the null terminated search text.
+    unsafe { to_cstring(ptr) }.expect("non-null")
+}
+
+/// The ANSI version of [`Everything_Query`](super::Everything_Query), the results are then
+/// in ANSI, see the [module](self) docs.
+pub fn Everything_QueryA(_: &RawAccess, wait: bool) -> bool {
+    let wait = if wait { TRUE } else { FALSE };
+    let success = unsafe { sdk_sys::Everything_QueryA(wait) };
+    lower_bool(success)
+}
+
+// --- results ---
+
+macro_rules! result_text_a {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        pub fn $name(_: &RawAccess, index: u32) -> Option<CString> {
+            // SAFETY: the SDK returns null, or the null terminated string valid until the
+            // next query or reset, which is copied here.
+            unsafe { to_cstring(sdk_sys::$name(index)) }
+        }
+    };
+}
+
+result_text_a! {
+    /// The ANSI version of [`Everything_GetResultFileName`](super::Everything_GetResultFileName).
+    Everything_GetResultFileNameA
+}
+result_text_a! {
+    /// The ANSI version of [`Everything_GetResultPath`](super::Everything_GetResultPath).
+    Everything_GetResultPathA
+}
+result_text_a! {
+    /// The ANSI version of [`Everything_GetResultExtension`](super::Everything_GetResultExtension).
+    ///
+    /// Requires Everything 1.4.1 or later.
+    Everything_GetResultExtensionA
+}
+result_text_a! {
+    /// The ANSI version of
+    /// [`Everything_GetResultFileListFileName`](super::Everything_GetResultFileListFileName).
+    ///
+    /// Requires Everything 1.4.1 or later.
+    Everything_GetResultFileListFileNameA
+}
+result_text_a! {
+    /// The ANSI version of
+    /// [`Everything_GetResultHighlightedFileName`](super::Everything_GetResultHighlightedFileName).
+    ///
+    /// Requires Everything 1.4.1 or later.
+    Everything_GetResultHighlightedFileNameA
+}
+result_text_a! {
+    /// The ANSI version of
+    /// [`Everything_GetResultHighlightedPath`](super::Everything_GetResultHighlightedPath).
+    ///
+    /// Requires Everything 1.4.1 or later.
+    Everything_GetResultHighlightedPathA
+}
+result_text_a! {
+    /// The ANSI version of
+    /// [`Everything_GetResultHighlightedFullPathAndFileName`](super::Everything_GetResultHighlightedFullPathAndFileName).
+    ///
+    /// Requires Everything 1.4.1 or later.
+    Everything_GetResultHighlightedFullPathAndFileNameA
+}
+
+/// The ANSI version of
+/// [`Everything_GetResultFullPathName`](super::Everything_GetResultFullPathName).
+///
+/// Return the number of bytes copied into `out_buf`, excluding the null terminator. The text
+/// is truncated (and still null terminated) if the buffer is too small.
+pub fn Everything_GetResultFullPathNameA(
+    _: &RawAccess,
+    index: u32,
+    out_buf: &mut [u8],
+) -> Option<NonZeroU32> {
+    let buf_size = u32::try_from(out_buf.len()).expect("buf size should not be greater than u32");
+    let number_of_bytes_without_null_terminator = unsafe {
+        sdk_sys::Everything_GetResultFullPathNameA(index, PSTR(out_buf.as_mut_ptr()), buf_size)
+    };
+    NonZeroU32::new(number_of_bytes_without_null_terminator)
+}
+
+/// The buffer size (including the null terminator) for
+/// [`Everything_GetResultFullPathNameA`], like
+/// [`Everything_GetResultFullPathNameSizeHint`](super::Everything_GetResultFullPathNameSizeHint).
+pub fn Everything_GetResultFullPathNameSizeHintA(_: &RawAccess, index: u32) -> Option<NonZeroU32> {
+    let bytes_without_null_terminator =
+        unsafe { sdk_sys::Everything_GetResultFullPathNameA(index, PSTR::null(), 0) };
+    NonZeroU32::new(bytes_without_null_terminator).and_then(|n| n.checked_add(1))
+}
+
+// --- run history ---
+
+/// The ANSI version of
+/// [`Everything_GetRunCountFromFileName`](super::Everything_GetRunCountFromFileName).
+pub fn Everything_GetRunCountFromFileNameA(
+    access: &RawAccess,
+    file_name: &CStr,
+) -> Result<u32, LastError> {
+    let run_count =
+        unsafe { sdk_sys::Everything_GetRunCountFromFileNameA(PCSTR(file_name.as_ptr().cast())) };
+    zero_or_last_error(access, run_count)
+}
+
+/// The ANSI version of
+/// [`Everything_SetRunCountFromFileName`](super::Everything_SetRunCountFromFileName).
+pub fn Everything_SetRunCountFromFileNameA(
+    _: &RawAccess,
+    file_name: &CStr,
+    run_count: u32,
+) -> bool {
+    let success = unsafe {
+        sdk_sys::Everything_SetRunCountFromFileNameA(PCSTR(file_name.as_ptr().cast()), run_count)
+    };
+    lower_bool(success)
+}
+
+/// The ANSI version of
+/// [`Everything_IncRunCountFromFileName`](super::Everything_IncRunCountFromFileName).
+pub fn Everything_IncRunCountFromFileNameA(
+    access: &RawAccess,
+    file_name: &CStr,
+) -> Result<NonZeroU32, LastError> {
+    let new_run_count =
+        unsafe { sdk_sys::Everything_IncRunCountFromFileNameA(PCSTR(file_name.as_ptr().cast())) };
+    NonZeroU32::new(new_run_count).ok_or_else(|| Everything_GetLastError(access))
+}
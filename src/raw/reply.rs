@@ -0,0 +1,431 @@
+//! Decode the query reply received by your own window procedure.
+//!
+//! [`Everything_IsQueryReply`](super::Everything_IsQueryReply) copies the reply into the global
+//! shared states of Everything-SDK, which is not what you want if you own the message loop and
+//! handle the replies of many queries. Here the reply is decoded into owned values instead.
+//!
+//! The reply is in Unicode, as the queries are sent by `Everything_QueryW` in this crate.
+//!
+//! # Examples
+//! ```no_run
+//! use everything_sdk::raw::*;
+//! use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+//! use windows::Win32::UI::WindowsAndMessaging::{DefWindowProcW, WM_COPYDATA};
+//!
+//! const MY_REPLY_ID: u32 = 42;
+//!
+//! extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+//!     if msg == WM_COPYDATA {
+//!         // SAFETY: the `lparam` of WM_COPYDATA is a valid COPYDATASTRUCT during the call.
+//!         if let Some((MY_REPLY_ID, data)) = unsafe { copydata_of(lparam) } {
+//!             let reply = parse_query_reply(data, QueryVersion::V2);
+//!             println!("{reply:?}");
+//!             return LRESULT(1);
+//!         }
+//!     }
+//!     unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+//! }
+//! ```
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+
+use super::{RequestFlags, SortType};
+
+/// The version of the IPC query, which decides the layout of the reply.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum QueryVersion {
+    /// `EVERYTHING_IPC_LISTW`, only the file names and the paths.
+    V1,
+    /// `EVERYTHING_IPC_LIST2`, with the request flags and the sort. (Everything 1.4.1)
+    V2,
+}
+
+impl QueryVersion {
+    /// The version used by `Everything_Query` for the request flags and the sort, that is the
+    /// version 2 for any non-default one of them.
+    ///
+    /// Note the SDK falls back to the version 1 if the version 2 is not supported, which only
+    /// happens with the Everything before 1.4.1.
+    pub fn for_request(request_flags: RequestFlags, sort_type: SortType) -> Self {
+        if request_flags == RequestFlags::default() && sort_type == SortType::default() {
+            QueryVersion::V1
+        } else {
+            QueryVersion::V2
+        }
+    }
+}
+
+/// The decoded query reply.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct QueryReply {
+    /// The number of all the results.
+    pub total: u32,
+    /// The index offset of the first item.
+    pub offset: u32,
+    /// The available request flags, which are always file name and path in version 1.
+    pub request_flags: RequestFlags,
+    /// The actual sort, `None` in version 1.
+    pub sort_type: Option<SortType>,
+    pub items: Vec<ReplyItem>,
+}
+
+/// An item of the reply, each data is `Some(_)` only if it is available in the request flags.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ReplyItem {
+    pub is_folder: bool,
+    /// The root of a volume, such as `C:`.
+    pub is_volume: bool,
+    pub filename: Option<OsString>,
+    pub path: Option<OsString>,
+    pub full_path_and_filename: Option<OsString>,
+    pub extension: Option<OsString>,
+    pub size: Option<u64>,
+    pub date_created: Option<u64>,
+    pub date_modified: Option<u64>,
+    pub date_accessed: Option<u64>,
+    pub attributes: Option<u32>,
+    pub file_list_filename: Option<OsString>,
+    pub run_count: Option<u32>,
+    pub date_run: Option<u64>,
+    pub date_recently_changed: Option<u64>,
+    pub highlighted_filename: Option<OsString>,
+    pub highlighted_path: Option<OsString>,
+    pub highlighted_full_path_and_filename: Option<OsString>,
+}
+
+const EVERYTHING_IPC_FOLDER: u32 = 0x00000001;
+const EVERYTHING_IPC_DRIVE: u32 = 0x00000002;
+
+/// The `dwData` and the bytes of the `COPYDATASTRUCT` in `lparam` of a `WM_COPYDATA` message.
+///
+/// The `dwData` is the reply ID set by `Everything_SetReplyID` for the query replies. It
+/// returns `None` if the `lparam` is null.
+///
+/// # Safety
+/// The `lparam` must be the one of a `WM_COPYDATA` message, and the returned bytes are only
+/// valid until the window procedure returns.
+pub unsafe fn copydata_of<'a>(lparam: LPARAM) -> Option<(u32, &'a [u8])> {
+    let cds = (lparam.0 as *const COPYDATASTRUCT).as_ref()?;
+    let data = if cds.lpData.is_null() || cds.cbData == 0 {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(cds.lpData as *const u8, cds.cbData as usize)
+    };
+    Some((cds.dwData as u32, data))
+}
+
+/// Decode the `data` of a query reply in the layout of `version`.
+///
+/// Return `None` if the data is truncated or malformed.
+pub fn parse_query_reply(data: &[u8], version: QueryVersion) -> Option<QueryReply> {
+    match version {
+        QueryVersion::V1 => parse_v1(data),
+        QueryVersion::V2 => parse_v2(data),
+    }
+}
+
+/// The `EVERYTHING_IPC_LISTW`: 7 DWORDs and then the items of 3 DWORDs (`#pragma pack(1)`).
+fn parse_v1(data: &[u8]) -> Option<QueryReply> {
+    let list = Reader(data);
+    let total = list.u32(8)?;
+    let num = list.u32(20)?;
+    let offset = list.u32(24)?;
+    let mut items = Vec::with_capacity(num.min(data.len() as u32 / 12) as usize);
+    for i in 0..num as usize {
+        let at = 28 + i * 12;
+        let flags = list.u32(at)?;
+        items.push(ReplyItem {
+            is_folder: flags & EVERYTHING_IPC_FOLDER != 0,
+            is_volume: flags & EVERYTHING_IPC_DRIVE != 0,
+            filename: Some(list.c_wstr(list.u32(at + 4)? as usize)?),
+            path: Some(list.c_wstr(list.u32(at + 8)? as usize)?),
+            ..Default::default()
+        });
+    }
+    Some(QueryReply {
+        total,
+        offset,
+        request_flags: RequestFlags::default(),
+        sort_type: None,
+        items,
+    })
+}
+
+/// The `EVERYTHING_IPC_LIST2`: 5 DWORDs, the items of 2 DWORDs, and then the data of each
+/// item in the order of the request flag bits.
+fn parse_v2(data: &[u8]) -> Option<QueryReply> {
+    let list = Reader(data);
+    let total = list.u32(0)?;
+    let num = list.u32(4)?;
+    let offset = list.u32(8)?;
+    // The unknown flags have an unknown layout, so the items fail below.
+    let request_flags = RequestFlags::from_bits_retain(list.u32(12)?);
+    let sort_type = Some(SortType::from(list.u32(16)?));
+    let mut items = Vec::with_capacity(num.min(data.len() as u32 / 8) as usize);
+    for i in 0..num as usize {
+        let at = 20 + i * 8;
+        let flags = list.u32(at)?;
+        let mut p = list.u32(at + 4)? as usize;
+        let mut item = ReplyItem {
+            is_folder: flags & EVERYTHING_IPC_FOLDER != 0,
+            is_volume: flags & EVERYTHING_IPC_DRIVE != 0,
+            ..Default::default()
+        };
+        for flag in request_flags.iter() {
+            match flag {
+                RequestFlags::EVERYTHING_REQUEST_FILE_NAME => {
+                    item.filename = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_PATH => item.path = Some(list.len_wstr(&mut p)?),
+                RequestFlags::EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME => {
+                    item.full_path_and_filename = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_EXTENSION => {
+                    item.extension = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_SIZE => item.size = Some(list.u64(&mut p)?),
+                RequestFlags::EVERYTHING_REQUEST_DATE_CREATED => {
+                    item.date_created = Some(list.u64(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_DATE_MODIFIED => {
+                    item.date_modified = Some(list.u64(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_DATE_ACCESSED => {
+                    item.date_accessed = Some(list.u64(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES => {
+                    item.attributes = Some(list.u32(p)?);
+                    p += 4;
+                }
+                RequestFlags::EVERYTHING_REQUEST_FILE_LIST_FILE_NAME => {
+                    item.file_list_filename = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_RUN_COUNT => {
+                    item.run_count = Some(list.u32(p)?);
+                    p += 4;
+                }
+                RequestFlags::EVERYTHING_REQUEST_DATE_RUN => {
+                    item.date_run = Some(list.u64(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED => {
+                    item.date_recently_changed = Some(list.u64(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME => {
+                    item.highlighted_filename = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_PATH => {
+                    item.highlighted_path = Some(list.len_wstr(&mut p)?)
+                }
+                RequestFlags::EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME => {
+                    item.highlighted_full_path_and_filename = Some(list.len_wstr(&mut p)?)
+                }
+                _ => return None, // unknown layout
+            }
+        }
+        items.push(item);
+    }
+    Some(QueryReply {
+        total,
+        offset,
+        request_flags,
+        sort_type,
+        items,
+    })
+}
+
+/// The bounds-checked little-endian reader over the reply.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, at: usize) -> Option<[u8; N]> {
+        self.0.get(at..at.checked_add(N)?)?.try_into().ok()
+    }
+
+    fn u32(&self, at: usize) -> Option<u32> {
+        self.bytes(at).map(u32::from_le_bytes)
+    }
+
+    /// Read the u64 (LARGE_INTEGER or FILETIME) at `*p` and move forward.
+    fn u64(&self, p: &mut usize) -> Option<u64> {
+        let n = self.bytes(*p).map(u64::from_le_bytes)?;
+        *p += 8;
+        Some(n)
+    }
+
+    /// Read `len` UTF-16 code units at `at`.
+    fn wstr(&self, at: usize, len: usize) -> Option<OsString> {
+        let bytes = self.0.get(at..at.checked_add(len.checked_mul(2)?)?)?;
+        let wide: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        Some(OsString::from_wide(&wide))
+    }
+
+    /// Read the null-terminated text at `at`.
+    fn c_wstr(&self, at: usize) -> Option<OsString> {
+        let tail = self.0.get(at..)?;
+        let len = tail.chunks_exact(2).position(|b| b == [0, 0])?;
+        self.wstr(at, len)
+    }
+
+    /// Read the DWORD length and then the null-terminated text of it at `*p`, and move forward.
+    fn len_wstr(&self, p: &mut usize) -> Option<OsString> {
+        let len = self.u32(*p)? as usize;
+        let text = self.wstr(*p + 4, len)?;
+        *p += 4 + (len + 1) * 2;
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn dwords(buf: &mut Vec<u8>, ns: &[u32]) {
+        for n in ns {
+            buf.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+
+    fn wide(buf: &mut Vec<u8>, text: &str) {
+        for unit in text.encode_utf16().chain([0]) {
+            buf.extend_from_slice(&unit.to_le_bytes());
+        }
+    }
+
+    /// Two items, the folder `C:\Windows` and the file `C:\a.txt`.
+    fn v1() -> Vec<u8> {
+        let mut buf = Vec::new();
+        // total folders, total files, total items, folders, files, items, offset
+        dwords(&mut buf, &[1, 1, 2, 1, 1, 2, 3]);
+        let strings = 28 + 2 * 12;
+        let offsets = [strings, strings + 16, strings + 22, strings + 34];
+        dwords(&mut buf, &[EVERYTHING_IPC_FOLDER, offsets[0], offsets[1]]);
+        dwords(&mut buf, &[0, offsets[2], offsets[3]]);
+        wide(&mut buf, "Windows");
+        wide(&mut buf, "C:");
+        wide(&mut buf, "a.txt");
+        wide(&mut buf, "C:");
+        buf
+    }
+
+    /// One file `C:\a.txt` with the file name, path, size and attributes.
+    fn v2() -> Vec<u8> {
+        let flags = RequestFlags::EVERYTHING_REQUEST_FILE_NAME
+            | RequestFlags::EVERYTHING_REQUEST_PATH
+            | RequestFlags::EVERYTHING_REQUEST_SIZE
+            | RequestFlags::EVERYTHING_REQUEST_ATTRIBUTES;
+        let sort = SortType::EVERYTHING_SORT_SIZE_DESCENDING.to_u32();
+        let mut buf = Vec::new();
+        dwords(&mut buf, &[10, 1, 4, flags.bits(), sort]);
+        dwords(&mut buf, &[0, 28]);
+        dwords(&mut buf, &[5]);
+        wide(&mut buf, "a.txt");
+        dwords(&mut buf, &[2]);
+        wide(&mut buf, "C:");
+        buf.extend_from_slice(&42u64.to_le_bytes());
+        dwords(&mut buf, &[0x20]);
+        buf
+    }
+
+    #[test]
+    fn v1_well_formed() {
+        let reply = parse_query_reply(&v1(), QueryVersion::V1).unwrap();
+        assert_eq!(reply.total, 2);
+        assert_eq!(reply.offset, 3);
+        assert_eq!(reply.request_flags, RequestFlags::default());
+        assert_eq!(reply.sort_type, None);
+        assert_eq!(reply.items.len(), 2);
+        assert!(reply.items[0].is_folder);
+        assert_eq!(
+            reply.items[0].filename.as_deref(),
+            Some(OsStr::new("Windows"))
+        );
+        assert_eq!(reply.items[0].path.as_deref(), Some(OsStr::new("C:")));
+        assert!(!reply.items[1].is_folder);
+        assert_eq!(
+            reply.items[1].filename.as_deref(),
+            Some(OsStr::new("a.txt"))
+        );
+        assert_eq!(reply.items[1].size, None);
+    }
+
+    #[test]
+    fn v2_well_formed() {
+        let reply = parse_query_reply(&v2(), QueryVersion::V2).unwrap();
+        assert_eq!(reply.total, 10);
+        assert_eq!(reply.offset, 4);
+        assert_eq!(
+            reply.sort_type,
+            Some(SortType::EVERYTHING_SORT_SIZE_DESCENDING)
+        );
+        let item = &reply.items[..];
+        assert_eq!(item.len(), 1);
+        assert_eq!(item[0].filename.as_deref(), Some(OsStr::new("a.txt")));
+        assert_eq!(item[0].path.as_deref(), Some(OsStr::new("C:")));
+        assert_eq!(item[0].size, Some(42));
+        assert_eq!(item[0].attributes, Some(0x20));
+        assert_eq!(item[0].date_modified, None);
+    }
+
+    #[test]
+    fn truncated_header() {
+        assert_eq!(parse_query_reply(&v1()[..27], QueryVersion::V1), None);
+        assert_eq!(parse_query_reply(&v2()[..19], QueryVersion::V2), None);
+        assert_eq!(parse_query_reply(&[], QueryVersion::V2), None);
+    }
+
+    #[test]
+    fn truncated_data() {
+        let v1 = v1();
+        assert_eq!(
+            parse_query_reply(&v1[..v1.len() - 1], QueryVersion::V1),
+            None
+        );
+        let v2 = v2();
+        assert_eq!(
+            parse_query_reply(&v2[..v2.len() - 1], QueryVersion::V2),
+            None
+        );
+    }
+
+    #[test]
+    fn offset_past_the_end() {
+        let mut v1 = v1();
+        let len = v1.len() as u32;
+        v1[32..36].copy_from_slice(&len.to_le_bytes());
+        assert_eq!(parse_query_reply(&v1, QueryVersion::V1), None);
+
+        let mut v2 = v2();
+        v2[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(parse_query_reply(&v2, QueryVersion::V2), None);
+        // More items than the data has.
+        let mut v2 = self::v2();
+        v2[4..8].copy_from_slice(&1000u32.to_le_bytes());
+        assert_eq!(parse_query_reply(&v2, QueryVersion::V2), None);
+    }
+
+    #[test]
+    fn unknown_request_flags() {
+        let mut v2 = v2();
+        let flags = u32::from_le_bytes(v2[12..16].try_into().unwrap()) | 1 << 31;
+        v2[12..16].copy_from_slice(&flags.to_le_bytes());
+        assert_eq!(parse_query_reply(&v2, QueryVersion::V2), None);
+    }
+
+    #[test]
+    fn unknown_sort_type() {
+        let mut v2 = v2();
+        v2[16..20].copy_from_slice(&1000u32.to_le_bytes());
+        let reply = parse_query_reply(&v2, QueryVersion::V2).unwrap();
+        assert_eq!(reply.sort_type, Some(SortType::Unknown(1000)));
+    }
+}
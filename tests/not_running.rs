@@ -0,0 +1,32 @@
+//! The errors when the Everything instance is not running, which needs no Everything at all.
+#![cfg(all(windows, not(feature = "raw"), not(feature = "async")))]
+
+use std::time::Duration;
+
+use everything_sdk::*;
+
+/// The instance nobody runs, so its IPC window never exists.
+fn not_running() -> Instance {
+    Instance::Named("everything-sdk-rs-test-not-running".to_string())
+}
+
+#[test]
+fn submit_fails_with_ipc_when_not_running() {
+    let mut everything = global().lock().unwrap();
+    everything.set_instance(not_running()).unwrap();
+    assert!(!everything.is_everything_running());
+    let mut searcher = everything.searcher();
+    searcher.set_search("*.rs");
+
+    assert!(matches!(searcher.submit(), Err(EverythingError::Ipc)));
+    assert!(matches!(
+        searcher.submit_timeout(Duration::from_secs(1)),
+        Err(EverythingError::Ipc)
+    ));
+    assert!(matches!(
+        searcher.query_timeout(Duration::from_secs(1)),
+        Err(EverythingError::Ipc)
+    ));
+    // Nothing is left pending, so the searcher is still usable.
+    assert!(matches!(searcher.submit(), Err(EverythingError::Ipc)));
+}